@@ -0,0 +1,162 @@
+//! Compile sass to css at compile time.
+//!
+//! This crate provides the [`scss!`] and [`include_scss!`] macros,
+//! that compiles sass with [rsass](https://docs.rs/rsass) when your
+//! crate is built, and expands to the resulting css as a
+//! `&'static str`.
+//! That way, static stylesheets can be embedded in a program without
+//! a `build.rs` script.
+//!
+//! # Example
+//!
+//! ```
+//! use rsass_macros::{include_scss, scss};
+//!
+//! const BASE: &str = scss!("$c: red;\na { b { color: $c } }");
+//! assert_eq!(BASE, "a b {\n  color: red;\n}\n");
+//!
+//! const STYLE: &str = include_scss!("tests/styles/main.scss", compressed);
+//! assert_eq!(STYLE, "header{color:#336699}main{padding:8px}\n");
+//! ```
+//!
+//! Paths, both the one given to `include_scss!` and any `@use`d or
+//! `@import`ed files, are resolved like a
+//! [`CargoContext`](rsass::input::CargoContext) does it, relative to
+//! the directory containing the manifest of the crate using the macro.
+//! All files that are loaded are included in the expanded code, so the
+//! crate is rebuilt when they change.
+//!
+//! An optional second argument, `expanded` (the default) or
+//! `compressed`, selects the output style.
+//!
+//! Any sass error is reported as a compile error, including the source
+//! position of the error.
+//!
+//! ```compile_fail
+//! // Error: Undefined variable.
+//! const STYLE: &str = rsass_macros::include_scss!("tests/styles/broken");
+//! ```
+#![forbid(unsafe_code)]
+#![forbid(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote_spanned;
+use rsass::input::{
+    CargoLoader, Context, SourceFile, SourceName, TrackingLoader,
+};
+use rsass::output::{Format, Style};
+use rsass::Error;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+/// Compile inline scss to css.
+///
+/// The argument is a string literal containing scss source.
+/// Any files used or imported from it are resolved relative to the
+/// manifest directory of the current crate.
+///
+/// ```
+/// # use rsass_macros::scss;
+/// let css = scss!("p { margin: 2px * 3 }", compressed);
+/// assert_eq!(css, "p{margin:6px}\n");
+/// ```
+#[proc_macro]
+pub fn scss(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as Args);
+    let source = args.source.value();
+    args.expand(|context| {
+        let file = SourceFile::scss_bytes(source, SourceName::root("-"));
+        context.transform(file)
+    })
+}
+
+/// Compile a sass file to css.
+///
+/// The argument is a string literal containing the path to a scss
+/// file, relative to the manifest directory of the current crate.
+/// The path is resolved like a `@use` rule, so the `.scss` suffix may
+/// be omitted.
+///
+/// ```
+/// # use rsass_macros::include_scss;
+/// let css = include_scss!("tests/styles/main");
+/// assert!(css.starts_with("header {\n"));
+/// ```
+#[proc_macro]
+pub fn include_scss(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as Args);
+    let path = args.source.value();
+    args.expand(|mut context| {
+        let file = context.load_root(&path)?;
+        context.transform(file)
+    })
+}
+
+/// The arguments to either macro.
+struct Args {
+    source: LitStr,
+    style: Style,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let source = input.parse()?;
+        let style = if input.parse::<Option<Token![,]>>()?.is_some()
+            && !input.is_empty()
+        {
+            let ident: Ident = input.parse()?;
+            let style = ident.to_string().parse().map_err(|_| {
+                syn::Error::new(
+                    ident.span(),
+                    "Expected an output style, expanded or compressed",
+                )
+            })?;
+            input.parse::<Option<Token![,]>>()?;
+            style
+        } else {
+            Style::Expanded
+        };
+        Ok(Args { source, style })
+    }
+}
+
+impl Args {
+    /// Run `compile` in a context for the current crate, and expand to
+    /// the resulting css or a compile error.
+    fn expand<F>(&self, compile: F) -> TokenStream
+    where
+        F: FnOnce(
+            Context<TrackingLoader<CargoLoader>>,
+        ) -> Result<Vec<u8>, Error>,
+    {
+        let span = self.source.span();
+        let loader = match CargoLoader::for_crate() {
+            Ok(loader) => TrackingLoader::new(loader.without_watch()),
+            Err(e) => return error(span, &Error::from(e)),
+        };
+        let loaded = loader.loaded();
+        let format = Format::default().with_style(self.style);
+        let context = Context::for_loader(loader).with_format(format);
+        let css = match compile(context) {
+            Ok(css) => css,
+            Err(e) => return error(span, &e),
+        };
+        let css = match String::from_utf8(css) {
+            Ok(css) => css,
+            Err(e) => return error(span, &Error::error(e.to_string())),
+        };
+        let loaded = loaded.take();
+        quote_spanned!(span=> {
+            #(const _: &[u8] = include_bytes!(#loaded);)*
+            #css
+        })
+        .into()
+    }
+}
+
+/// Expand to a `compile_error!` for a sass error.
+fn error(span: Span, error: &Error) -> TokenStream {
+    let message = error.to_string();
+    quote_spanned!(span=> compile_error!(#message)).into()
+}
@@ -0,0 +1,36 @@
+//! Tests for the compile-time sass macros.
+use rsass_macros::{include_scss, scss};
+
+#[test]
+fn inline() {
+    assert_eq!(
+        scss!("$gap: 4px;\nul { li { margin: $gap * 2 } }"),
+        "ul li {\n  margin: 8px;\n}\n"
+    );
+}
+
+#[test]
+fn inline_using_file() {
+    assert_eq!(
+        scss!(
+            "@use 'tests/styles/colors' with ($primary: red);\n\
+             a { color: colors.$primary }",
+            compressed,
+        ),
+        "a{color:red}\n"
+    );
+}
+
+#[test]
+fn file() {
+    assert_eq!(
+        include_scss!("tests/styles/main.scss"),
+        "header {\n  color: #336699;\n}\n\nmain {\n  padding: 8px;\n}\n"
+    );
+}
+
+#[test]
+fn file_compressed() {
+    const STYLE: &str = include_scss!("tests/styles/main", compressed);
+    assert_eq!(STYLE, "header{color:#336699}main{padding:8px}\n");
+}
@@ -0,0 +1,189 @@
+use super::{
+    BodyItem, Comment, CssString, Import, Item, Property, Rule, Selectors,
+    Value,
+};
+use crate::output::CssBuf;
+use crate::SourcePos;
+use std::io::{self, Write};
+
+/// An `@` rule in css, e.g. `@media ... { ... }`.
+///
+/// An at-rule has a name, some (possibly empty) args, and an
+/// optional body.
+/// A body-less at-rule is written with a terminating semicolon, like
+/// `@foo bar;`.
+#[derive(Clone, Debug)]
+pub struct AtRule {
+    pub(crate) name: String,
+    pub(crate) args: Value,
+    pub(crate) body: Option<Vec<AtRuleBodyItem>>,
+    pub(crate) pos: Option<SourcePos>,
+}
+
+impl AtRule {
+    /// Create a new at-rule.
+    pub fn new(
+        name: String,
+        args: Value,
+        body: Option<Vec<AtRuleBodyItem>>,
+    ) -> Self {
+        AtRule {
+            name,
+            args,
+            body,
+            pos: None,
+        }
+    }
+    /// Set the source position of this rule.
+    pub fn with_pos(self, pos: SourcePos) -> Self {
+        AtRule {
+            pos: Some(pos),
+            ..self
+        }
+    }
+    /// The name of this rule (without the `@`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// The arguments (or prelude) of this rule.
+    pub fn args(&self) -> &Value {
+        &self.args
+    }
+    /// The body of this rule, if it has one.
+    pub fn body(&self) -> Option<&[AtRuleBodyItem]> {
+        self.body.as_deref()
+    }
+    /// The source position of this rule, if known.
+    pub fn pos(&self) -> Option<&SourcePos> {
+        self.pos.as_ref()
+    }
+
+    /// Get a copy of this rule, with any rules in the body nested
+    /// inside `selectors`.
+    pub(crate) fn nested_in(&self, selectors: &Selectors) -> Self {
+        let body = self.body.as_ref().map(|body| {
+            body.iter()
+                .map(|item| match item {
+                    AtRuleBodyItem::Rule(rule) => {
+                        let mut rule = rule.clone();
+                        rule.selectors = rule.selectors.inside(selectors);
+                        AtRuleBodyItem::Rule(rule)
+                    }
+                    AtRuleBodyItem::AtRule(rule) => {
+                        AtRuleBodyItem::AtRule(rule.nested_in(selectors))
+                    }
+                    item => item.clone(),
+                })
+                .collect()
+        });
+        AtRule {
+            body,
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn write(&self, buf: &mut CssBuf) -> io::Result<()> {
+        buf.do_indent_no_nl();
+        write!(buf, "@{}", self.name)?;
+        if !self.args.is_null() {
+            write!(buf, " {}", self.args.format(buf.format()))?;
+        }
+        if let Some(body) = &self.body {
+            buf.start_block();
+            for item in body {
+                item.write(buf)?;
+            }
+            buf.end_block();
+        } else {
+            buf.add_one(";\n", ";");
+        }
+        Ok(())
+    }
+}
+
+/// Something that may exist inside an at-rule body.
+#[derive(Clone, Debug)]
+pub enum AtRuleBodyItem {
+    /// An `@import` statement with a name and args.
+    Import(Import),
+    /// A comment
+    Comment(Comment),
+    /// A css rule.
+    Rule(Rule),
+    /// A property declaration with a name and a value.
+    Property(Property),
+    /// A custom property declaration with a name and a value.
+    CustomProperty(String, CssString),
+    /// A nested at-rule.
+    AtRule(AtRule),
+    /// An extra empty line, in non-compressed output.
+    Separator,
+}
+
+impl AtRuleBodyItem {
+    pub(crate) fn write(&self, buf: &mut CssBuf) -> io::Result<()> {
+        match self {
+            AtRuleBodyItem::Import(import) => import.write(buf)?,
+            AtRuleBodyItem::Comment(comment) => comment.write(buf),
+            AtRuleBodyItem::Rule(rule) => rule.write(buf)?,
+            AtRuleBodyItem::Property(property) => property.write(buf),
+            AtRuleBodyItem::CustomProperty(name, value) => {
+                BodyItem::write_custom(name, value, buf)?
+            }
+            AtRuleBodyItem::AtRule(rule) => rule.write(buf)?,
+            AtRuleBodyItem::Separator => buf.do_separate(),
+        }
+        Ok(())
+    }
+}
+
+impl From<Rule> for AtRuleBodyItem {
+    fn from(rule: Rule) -> Self {
+        AtRuleBodyItem::Rule(rule)
+    }
+}
+impl From<Comment> for AtRuleBodyItem {
+    fn from(comment: Comment) -> Self {
+        AtRuleBodyItem::Comment(comment)
+    }
+}
+impl From<Import> for AtRuleBodyItem {
+    fn from(import: Import) -> Self {
+        AtRuleBodyItem::Import(import)
+    }
+}
+impl From<Property> for AtRuleBodyItem {
+    fn from(property: Property) -> Self {
+        AtRuleBodyItem::Property(property)
+    }
+}
+impl From<AtRule> for AtRuleBodyItem {
+    fn from(rule: AtRule) -> Self {
+        AtRuleBodyItem::AtRule(rule)
+    }
+}
+impl From<BodyItem> for AtRuleBodyItem {
+    fn from(item: BodyItem) -> Self {
+        match item {
+            BodyItem::Import(import) => AtRuleBodyItem::Import(import),
+            BodyItem::Property(property) => {
+                AtRuleBodyItem::Property(property)
+            }
+            BodyItem::CustomProperty(name, value) => {
+                AtRuleBodyItem::CustomProperty(name, value)
+            }
+            BodyItem::Comment(comment) => AtRuleBodyItem::Comment(comment),
+        }
+    }
+}
+impl From<Item> for AtRuleBodyItem {
+    fn from(item: Item) -> Self {
+        match item {
+            Item::Comment(comment) => AtRuleBodyItem::Comment(comment),
+            Item::Import(import) => AtRuleBodyItem::Import(import),
+            Item::Rule(rule) => AtRuleBodyItem::Rule(rule),
+            Item::AtRule(rule) => AtRuleBodyItem::AtRule(rule),
+            Item::Separator => AtRuleBodyItem::Separator,
+        }
+    }
+}
@@ -12,6 +12,10 @@ impl<T: Into<String>> From<T> for Comment {
 }
 
 impl Comment {
+    /// The text of this comment, without the `/*` and `*/` delimiters.
+    pub fn text(&self) -> &str {
+        &self.0
+    }
     /// Write this comment to a css output buffer.
     pub(crate) fn write(&self, buf: &mut CssBuf) {
         let indent = buf.indent_level();
@@ -1,4 +1,4 @@
-use super::{Comment, CssString, Property, Rule, Value};
+use super::{AtRule, Comment, CssString, Rule, Value};
 use crate::output::CssBuf;
 use std::io::{self, Write};
 
@@ -12,7 +12,9 @@ pub enum Item {
     /// A css rule.
     Rule(Rule),
     /// An `@` rule, e.g. `@media ... { ... }`
-    AtRule(String, String, Vec<AtRuleBodyItem>),
+    AtRule(AtRule),
+    /// An extra empty line, in non-compressed output.
+    Separator,
 }
 
 impl Item {
@@ -21,17 +23,8 @@ impl Item {
             Item::Comment(comment) => comment.write(buf),
             Item::Import(import) => import.write(buf)?,
             Item::Rule(rule) => rule.write(buf)?,
-            Item::AtRule(name, args, body) => {
-                write!(buf, "@{}", name)?;
-                if !args.is_empty() {
-                    write!(buf, " {}", args)?;
-                }
-                buf.start_block();
-                for item in body {
-                    item.write(buf)?;
-                }
-                buf.end_block();
-            }
+            Item::AtRule(rule) => rule.write(buf)?,
+            Item::Separator => buf.do_separate(),
         }
         Ok(())
     }
@@ -52,51 +45,17 @@ impl From<Rule> for Item {
         Item::Rule(rule)
     }
 }
-
-#[derive(Clone, Debug)]
-pub enum AtRuleBodyItem {
-    Import(Import),
-    Comment(Comment),
-    Rule(Rule),
-    Property(Property),
-}
-impl AtRuleBodyItem {
-    pub(crate) fn write(&self, buf: &mut CssBuf) -> io::Result<()> {
-        match self {
-            AtRuleBodyItem::Import(import) => import.write(buf)?,
-            AtRuleBodyItem::Comment(comment) => comment.write(buf),
-            AtRuleBodyItem::Rule(rule) => rule.write(buf)?,
-            AtRuleBodyItem::Property(property) => property.write(buf),
-        }
-        Ok(())
-    }
-}
-impl From<Rule> for AtRuleBodyItem {
-    fn from(rule: Rule) -> Self {
-        AtRuleBodyItem::Rule(rule)
-    }
-}
-impl From<Comment> for AtRuleBodyItem {
-    fn from(rule: Comment) -> Self {
-        AtRuleBodyItem::Comment(rule)
-    }
-}
-impl From<Import> for AtRuleBodyItem {
-    fn from(rule: Import) -> Self {
-        AtRuleBodyItem::Import(rule)
-    }
-}
-impl From<Property> for AtRuleBodyItem {
-    fn from(rule: Property) -> Self {
-        AtRuleBodyItem::Property(rule)
+impl From<AtRule> for Item {
+    fn from(rule: AtRule) -> Item {
+        Item::AtRule(rule)
     }
 }
 
 /// An `@import` rule in css.
 #[derive(Clone, Debug)]
 pub struct Import {
-    name: CssString,
-    args: Value,
+    pub(crate) name: CssString,
+    pub(crate) args: Value,
 }
 
 impl Import {
@@ -104,6 +63,14 @@ impl Import {
     pub fn new(name: CssString, args: Value) -> Self {
         Import { name, args }
     }
+    /// The name (url) of this import.
+    pub fn name(&self) -> &CssString {
+        &self.name
+    }
+    /// The arguments of this import (e.g. media queries).
+    pub fn args(&self) -> &Value {
+        &self.args
+    }
 
     /// Write this comment to a css output buffer.
     pub(crate) fn write(&self, buf: &mut CssBuf) -> io::Result<()> {
@@ -1,4 +1,5 @@
 //! Types for css values and rules.
+mod atrule;
 mod call_args;
 mod comment;
 mod item;
@@ -8,7 +9,11 @@ mod string;
 mod util;
 mod value;
 mod valueformat;
+#[cfg(feature = "serde")]
+mod valueserde;
+pub mod visit;
 
+pub use self::atrule::{AtRule, AtRuleBodyItem};
 pub use self::call_args::CallArgs;
 pub use self::comment::Comment;
 pub use self::item::{Import, Item};
@@ -16,5 +21,7 @@ pub use self::rule::{BodyItem, Property, Rule};
 pub use self::selectors::{BadSelector, Selector, SelectorPart, Selectors};
 pub use self::string::CssString;
 pub use self::value::{Value, ValueMap, ValueToMapError};
+#[cfg(feature = "serde")]
+pub use self::valueserde::{from_value, to_value};
 
 pub(crate) use self::util::{is_calc_name, is_function_name, is_not};
@@ -1,5 +1,6 @@
 use super::{Comment, CssString, Import, Selectors, Value};
 use crate::output::CssBuf;
+use crate::SourcePos;
 use std::io::{self, Write};
 
 /// A css rule.
@@ -10,6 +11,7 @@ use std::io::{self, Write};
 pub struct Rule {
     pub(crate) selectors: Selectors,
     pub(crate) body: Vec<BodyItem>,
+    pub(crate) pos: Option<SourcePos>,
 }
 
 impl Rule {
@@ -18,12 +20,32 @@ impl Rule {
         Rule {
             selectors,
             body: Vec::new(),
+            pos: None,
+        }
+    }
+    /// Set the source position of this rule.
+    pub fn with_pos(self, pos: SourcePos) -> Self {
+        Rule {
+            pos: Some(pos),
+            ..self
         }
     }
     /// Add an item to the body of this rule.
     pub fn push(&mut self, item: BodyItem) {
         self.body.push(item)
     }
+    /// The selectors of this rule.
+    pub fn selectors(&self) -> &Selectors {
+        &self.selectors
+    }
+    /// The body of this rule.
+    pub fn body(&self) -> &[BodyItem] {
+        &self.body
+    }
+    /// The source position of this rule, if known.
+    pub fn pos(&self) -> Option<&SourcePos> {
+        self.pos.as_ref()
+    }
 
     /// Write this rule to a css output buffer.
     pub(crate) fn write(&self, buf: &mut CssBuf) -> io::Result<()> {
@@ -51,7 +73,7 @@ pub enum BodyItem {
     Import(Import),
     /// A property declaration with a name and a value.
     Property(Property),
-    /// A property declaration with a name and a value.
+    /// A custom property declaration with a name and a value.
     CustomProperty(String, CssString),
     /// A comment
     Comment(Comment),
@@ -65,24 +87,32 @@ impl BodyItem {
             BodyItem::Import(import) => import.write(buf)?,
             BodyItem::Property(property) => property.write(buf),
             BodyItem::CustomProperty(ref name, ref val) => {
-                buf.do_indent_no_nl();
-                write!(
-                    buf,
-                    "{}:{}{}",
-                    name,
-                    if val.quotes().is_none() || buf.format().is_compressed()
-                    {
-                        ""
-                    } else {
-                        " "
-                    },
-                    val,
-                )?;
-                buf.add_one(";\n", ";");
+                BodyItem::write_custom(name, val, buf)?
             }
         }
         Ok(())
     }
+
+    pub(crate) fn write_custom(
+        name: &str,
+        val: &CssString,
+        buf: &mut CssBuf,
+    ) -> io::Result<()> {
+        buf.do_indent_no_nl();
+        write!(
+            buf,
+            "{}:{}{}",
+            name,
+            if val.quotes().is_none() || buf.format().is_compressed() {
+                ""
+            } else {
+                " "
+            },
+            val,
+        )?;
+        buf.add_one(";\n", ";");
+        Ok(())
+    }
 }
 
 impl From<Comment> for BodyItem {
@@ -104,14 +134,38 @@ impl From<Property> for BodyItem {
 /// A css property; a name and [Value].
 #[derive(Clone, Debug)]
 pub struct Property {
-    name: String,
-    value: Value,
+    pub(crate) name: String,
+    pub(crate) value: Value,
+    pub(crate) pos: Option<SourcePos>,
 }
 
 impl Property {
     /// Create a new Property.
     pub fn new(name: String, value: Value) -> Self {
-        Property { name, value }
+        Property {
+            name,
+            value,
+            pos: None,
+        }
+    }
+    /// Set the source position of this property.
+    pub fn with_pos(self, pos: SourcePos) -> Self {
+        Property {
+            pos: Some(pos),
+            ..self
+        }
+    }
+    /// The name of this property.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// The value of this property.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+    /// The source position of this property, if known.
+    pub fn pos(&self) -> Option<&SourcePos> {
+        self.pos.as_ref()
     }
     /// Return this property but with a prefix.
     ///
@@ -119,7 +173,7 @@ impl Property {
     pub fn prefix(self, prefix: &str) -> Self {
         Property {
             name: format!("{}-{}", prefix, self.name),
-            value: self.value,
+            ..self
         }
     }
     pub(crate) fn write(&self, buf: &mut CssBuf) {
@@ -43,6 +43,10 @@ impl Selectors {
             backref: Selector::root(),
         }
     }
+    /// Iterate over the individual selectors.
+    pub fn iter(&self) -> impl Iterator<Item = &Selector> {
+        self.s.iter()
+    }
     /// Validate that this selector is ok to use in css.
     ///
     /// `Selectors` can contain backref (`&`), but those must be
@@ -20,7 +20,11 @@ impl<'a> Display for Formatted<'a, Value> {
             }
             Value::Numeric(ref num, _) => num.format(self.format).fmt(out),
             Value::Color(ref rgba, ref name) => {
-                if let Some(ref name) = *name {
+                let colors = self.format.colors;
+                if let Some(name) = name.as_ref().filter(|name| {
+                    colors.keep_source()
+                        && (colors.names || name.starts_with('#'))
+                }) {
                     name.fmt(out)
                 } else {
                     rgba.format(self.format).fmt(out)
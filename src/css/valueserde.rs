@@ -0,0 +1,610 @@
+//! Serde support for css values.
+//!
+//! This is only available if rsass is built with the `serde` feature.
+//!
+//! A [`Value`] is serialized as the natural serde data type for it:
+//! a unitless number as a number, a list as a sequence, a map as a
+//! map, `true` and `false` as booleans and `null` as a unit.
+//! Strings are serialized without their quotes, and other values
+//! (such as numbers with units and colors) are serialized as strings
+//! in css format.
+//!
+//! When deserializing a value, a string that is a number with a
+//! unit, a hex color or a color name is deserialized as a number or
+//! a color, other strings are deserialized as unquoted strings.
+use super::{Value, ValueMap};
+use crate::output::Format;
+use crate::parser::value::{hex_color, number};
+use crate::parser::{code_span, ParseError};
+use crate::sass;
+use crate::value::{ListSeparator, Rgba};
+use crate::Error;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// Convert anything that is serializable to a css [`Value`].
+///
+/// # Example
+///
+/// ```
+/// # use rsass::css::{to_value, Value};
+/// # use std::collections::BTreeMap;
+/// let mut map = BTreeMap::new();
+/// map.insert("gap", "4px");
+/// map.insert("color", "#f00");
+/// let value = to_value(&map).unwrap();
+/// assert_eq!(value.format(Default::default()).to_string(), "(color: #f00, gap: 4px)");
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, Error> {
+    value.serialize(SERIALIZER)
+}
+
+/// Convert a css [`Value`] to anything that is deserializable.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::css::{from_value, Value};
+/// let value = Value::List(
+///     vec![Value::scalar(1), Value::scalar(2)],
+///     None,
+///     false,
+/// );
+/// assert_eq!(from_value::<Vec<i32>>(value).unwrap(), vec![1, 2]);
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    T::deserialize(value)
+}
+
+/// Get the value for a string, as described in the module docs.
+fn parse_str(s: &str) -> Value {
+    if let Ok(num) = ParseError::check(number(code_span(s.as_bytes()))) {
+        return Value::Numeric(num, false);
+    }
+    if let Ok(sass::Value::Color(rgba, raw, _)) =
+        ParseError::check(hex_color(code_span(s.as_bytes())))
+    {
+        return Value::Color(rgba.into(), raw);
+    }
+    if let Some(rgba) = Rgba::from_name(s) {
+        return Value::Color(rgba.into(), Some(s.into()));
+    }
+    s.into()
+}
+
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => s.serialize_unit(),
+            Value::True => s.serialize_bool(true),
+            Value::False => s.serialize_bool(false),
+            Value::Numeric(num, _) if num.unit.is_none() => {
+                match num.value.clone().into_integer() {
+                    Ok(i) => s.serialize_i64(i),
+                    Err(num) => s.serialize_f64(num.into()),
+                }
+            }
+            Value::Literal(v) => s.serialize_str(v.value()),
+            Value::List(items, ..) => s.collect_seq(items),
+            Value::Map(map) => s.collect_map(map.iter().map(|(k, v)| (k, v))),
+            v => s.collect_str(&v.format(Format::default())),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str("a sass value")
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_some<D: de::Deserializer<'de>>(
+        self,
+        d: D,
+    ) -> Result<Value, D::Error> {
+        d.deserialize_any(self)
+    }
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(v.into())
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::scalar(v))
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(match i64::try_from(v) {
+            Ok(v) => Value::scalar(v),
+            Err(_) => Value::scalar(v as f64),
+        })
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::scalar(v))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(parse_str(v))
+    }
+    fn visit_seq<A: de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::List(items, Some(ListSeparator::Comma), false))
+    }
+    fn visit_map<A: de::MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> Result<Value, A::Error> {
+        let mut result = ValueMap::new();
+        while let Some(MapKey(key)) = map.next_key()? {
+            result.insert(key, map.next_value()?);
+        }
+        Ok(Value::Map(result))
+    }
+}
+
+/// A map key is like any value, except a string is always a string.
+struct MapKey(Value);
+
+impl<'de> de::Deserialize<'de> for MapKey {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_any(MapKeyVisitor).map(MapKey)
+    }
+}
+
+struct MapKeyVisitor;
+
+impl<'de> Visitor<'de> for MapKeyVisitor {
+    type Value = Value;
+
+    fn expecting(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str("a sass map key")
+    }
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        ValueVisitor.visit_bool(v)
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        ValueVisitor.visit_i64(v)
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        ValueVisitor.visit_u64(v)
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        ValueVisitor.visit_f64(v)
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(v.into())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::S(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::S(msg.to_string())
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::True => visitor.visit_bool(true),
+            Value::False => visitor.visit_bool(false),
+            Value::Numeric(num, _) if num.unit.is_none() => {
+                match num.value.into_integer() {
+                    Ok(i) => visitor.visit_i64(i),
+                    Err(num) => visitor.visit_f64(num.into()),
+                }
+            }
+            Value::Literal(s) => visitor.visit_string(s.take_value()),
+            Value::List(items, ..) => visitor.visit_seq(
+                de::value::SeqDeserializer::new(items.into_iter()),
+            ),
+            Value::Map(map) => visitor
+                .visit_map(de::value::MapDeserializer::new(map.into_iter())),
+            v => {
+                visitor.visit_string(v.format(Format::default()).to_string())
+            }
+        }
+    }
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            v => visitor.visit_some(v),
+        }
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::Literal(s) => {
+                visitor.visit_enum(s.take_value().into_deserializer())
+            }
+            Value::Map(map) if map.len() == 1 => {
+                visitor.visit_enum(de::value::MapAccessDeserializer::new(
+                    de::value::MapDeserializer::new(map.into_iter()),
+                ))
+            }
+            v => v.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// A serializer creating a css [`Value`].
+///
+/// A string that is a map `key` is always serialized as a string.
+#[derive(Clone, Copy)]
+struct ValueSerializer {
+    key: bool,
+}
+
+const SERIALIZER: ValueSerializer = ValueSerializer { key: false };
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = ListSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(v.into())
+    }
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::scalar(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        ValueVisitor.visit_u64(v)
+    }
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        self.serialize_f64(v.into())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::scalar(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        if self.key {
+            Ok(v.into())
+        } else {
+            Ok(parse_str(v))
+        }
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        v.serialize(self)
+    }
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(variant.into())
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let value = value.serialize(SERIALIZER)?;
+        Ok(Value::Map(ValueMap::singleton(variant.into(), value)))
+    }
+    fn serialize_seq(
+        self,
+        len: Option<usize>,
+    ) -> Result<ListSerializer, Error> {
+        Ok(ListSerializer {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, Error> {
+        Ok(ListSerializer {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            variant: None,
+            map: ValueMap::new(),
+            key: None,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, Error> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            variant: Some(variant),
+            map: ValueMap::new(),
+            key: None,
+        })
+    }
+}
+
+/// Wrap a value for an enum variant as a single-entry map.
+fn in_variant(variant: Option<&'static str>, value: Value) -> Value {
+    match variant {
+        Some(variant) => {
+            Value::Map(ValueMap::singleton(variant.into(), value))
+        }
+        None => value,
+    }
+}
+
+struct ListSerializer {
+    variant: Option<&'static str>,
+    items: Vec<Value>,
+}
+
+impl ListSerializer {
+    fn push<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.items.push(value.serialize(SERIALIZER)?);
+        Ok(())
+    }
+    fn done(self) -> Result<Value, Error> {
+        let list = Value::List(self.items, Some(ListSeparator::Comma), false);
+        Ok(in_variant(self.variant, list))
+    }
+}
+
+impl ser::SerializeSeq for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+impl ser::SerializeTupleVariant for ListSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+struct MapSerializer {
+    variant: Option<&'static str>,
+    map: ValueMap,
+    key: Option<Value>,
+}
+
+impl MapSerializer {
+    fn done(self) -> Result<Value, Error> {
+        Ok(in_variant(self.variant, Value::Map(self.map)))
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_key<T: Serialize + ?Sized>(
+        &mut self,
+        key: &T,
+    ) -> Result<(), Error> {
+        self.key = Some(key.serialize(ValueSerializer { key: true })?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::S("Map value without a key".into()))?;
+        self.map.insert(key, value.serialize(SERIALIZER)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.map.insert(key.into(), value.serialize(SERIALIZER)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+    fn end(self) -> Result<Value, Error> {
+        self.done()
+    }
+}
@@ -0,0 +1,390 @@
+//! Traversal of the css tree.
+//!
+//! Implement [`Visit`] to inspect, or [`VisitMut`] to modify, a css
+//! tree, e.g. the result of
+//! [`Context::transform_to_ast`][crate::input::Context::transform_to_ast].
+//! Just like for the [sass syntax tree](crate::sass::visit), each
+//! method of the traits has a default implementation that calls the
+//! corresponding `walk_` function, which visits the children of the
+//! node.
+//!
+//! # Example
+//!
+//! Rename a property everywhere:
+//!
+//! ```
+//! # use rsass::input::MemoryContext;
+//! use rsass::css::visit::VisitMut;
+//! use rsass::css::Property;
+//! use rsass::output::{write_css, Format};
+//!
+//! struct Rename;
+//!
+//! impl VisitMut for Rename {
+//!     fn visit_property_mut(&mut self, property: &mut Property) {
+//!         if property.name() == "colour" {
+//!             *property = Property::new("color".into(), property.value().clone());
+//!         }
+//!     }
+//! }
+//!
+//! let mut context = MemoryContext::new();
+//! context.insert("main.scss", "a { colour: red; @media print { colour: black } }");
+//! let main = context.load_root("main")?;
+//! let mut css = context.transform_to_ast(main)?;
+//! Rename.visit_items_mut(&mut css);
+//! assert_eq!(
+//!     String::from_utf8(write_css(&css, Format::default())).unwrap(),
+//!     "a {\n  color: red;\n}\n@media print {\n  a {\n    color: black;\n  }\n}\n",
+//! );
+//! # Ok::<(), rsass::Error>(())
+//! ```
+use super::{
+    AtRule, AtRuleBodyItem, BodyItem, CallArgs, Comment, Import, Item,
+    Property, Rule, Selectors, Value,
+};
+
+/// A visitor of a css tree.
+///
+/// See the [module documentation](self).
+pub trait Visit {
+    /// Visit a sequence of top-level items.
+    fn visit_items(&mut self, items: &[Item]) {
+        walk_items(self, items)
+    }
+    /// Visit a top-level item.
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item)
+    }
+    /// Visit a rule.
+    fn visit_rule(&mut self, rule: &Rule) {
+        walk_rule(self, rule)
+    }
+    /// Visit an item in the body of a rule.
+    fn visit_body_item(&mut self, item: &BodyItem) {
+        walk_body_item(self, item)
+    }
+    /// Visit an at-rule.
+    fn visit_at_rule(&mut self, rule: &AtRule) {
+        walk_at_rule(self, rule)
+    }
+    /// Visit an item in the body of an at-rule.
+    fn visit_at_rule_body_item(&mut self, item: &AtRuleBodyItem) {
+        walk_at_rule_body_item(self, item)
+    }
+    /// Visit a property.
+    fn visit_property(&mut self, property: &Property) {
+        walk_property(self, property)
+    }
+    /// Visit an `@import`.
+    fn visit_import(&mut self, import: &Import) {
+        walk_import(self, import)
+    }
+    /// Visit a comment.
+    fn visit_comment(&mut self, _comment: &Comment) {}
+    /// Visit the selectors of a rule.
+    fn visit_selectors(&mut self, _selectors: &Selectors) {}
+    /// Visit a value.
+    fn visit_value(&mut self, value: &Value) {
+        walk_value(self, value)
+    }
+    /// Visit the arguments of a function call.
+    fn visit_call_args(&mut self, args: &CallArgs) {
+        walk_call_args(self, args)
+    }
+}
+
+/// Visit each item in `items`.
+pub fn walk_items<V: Visit + ?Sized>(v: &mut V, items: &[Item]) {
+    for item in items {
+        v.visit_item(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_item<V: Visit + ?Sized>(v: &mut V, item: &Item) {
+    match item {
+        Item::Comment(comment) => v.visit_comment(comment),
+        Item::Import(import) => v.visit_import(import),
+        Item::Rule(rule) => v.visit_rule(rule),
+        Item::AtRule(rule) => v.visit_at_rule(rule),
+        Item::Separator => (),
+    }
+}
+
+/// Visit the selectors and then each body item of `rule`.
+pub fn walk_rule<V: Visit + ?Sized>(v: &mut V, rule: &Rule) {
+    v.visit_selectors(&rule.selectors);
+    for item in &rule.body {
+        v.visit_body_item(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_body_item<V: Visit + ?Sized>(v: &mut V, item: &BodyItem) {
+    match item {
+        BodyItem::Import(import) => v.visit_import(import),
+        BodyItem::Property(property) => v.visit_property(property),
+        BodyItem::CustomProperty(..) => (),
+        BodyItem::Comment(comment) => v.visit_comment(comment),
+    }
+}
+
+/// Visit the args and then any body items of `rule`.
+pub fn walk_at_rule<V: Visit + ?Sized>(v: &mut V, rule: &AtRule) {
+    v.visit_value(&rule.args);
+    for item in rule.body.iter().flatten() {
+        v.visit_at_rule_body_item(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_at_rule_body_item<V: Visit + ?Sized>(
+    v: &mut V,
+    item: &AtRuleBodyItem,
+) {
+    match item {
+        AtRuleBodyItem::Import(import) => v.visit_import(import),
+        AtRuleBodyItem::Comment(comment) => v.visit_comment(comment),
+        AtRuleBodyItem::Rule(rule) => v.visit_rule(rule),
+        AtRuleBodyItem::Property(property) => v.visit_property(property),
+        AtRuleBodyItem::AtRule(rule) => v.visit_at_rule(rule),
+        AtRuleBodyItem::CustomProperty(..) | AtRuleBodyItem::Separator => (),
+    }
+}
+
+/// Visit the value of `property`.
+pub fn walk_property<V: Visit + ?Sized>(v: &mut V, property: &Property) {
+    v.visit_value(&property.value);
+}
+
+/// Visit the args of `import`.
+pub fn walk_import<V: Visit + ?Sized>(v: &mut V, import: &Import) {
+    v.visit_value(&import.args);
+}
+
+/// Visit the component values of `value`.
+pub fn walk_value<V: Visit + ?Sized>(v: &mut V, value: &Value) {
+    match value {
+        Value::Call(_, args) | Value::ArgList(args) => {
+            v.visit_call_args(args)
+        }
+        Value::List(items, ..) => {
+            for item in items {
+                v.visit_value(item);
+            }
+        }
+        Value::Paren(value) | Value::UnaryOp(_, value) => {
+            v.visit_value(value)
+        }
+        Value::BinOp(a, _, _, _, b) => {
+            v.visit_value(a);
+            v.visit_value(b);
+        }
+        Value::Map(map) => {
+            for (key, value) in map.iter() {
+                v.visit_value(key);
+                v.visit_value(value);
+            }
+        }
+        Value::Bang(_)
+        | Value::Function(..)
+        | Value::Literal(_)
+        | Value::Numeric(..)
+        | Value::Color(..)
+        | Value::Null
+        | Value::True
+        | Value::False
+        | Value::UnicodeRange(_) => (),
+    }
+}
+
+/// Visit the positional and then the named values of `args`.
+pub fn walk_call_args<V: Visit + ?Sized>(v: &mut V, args: &CallArgs) {
+    for value in &args.positional {
+        v.visit_value(value);
+    }
+    for (_, value) in args.named.iter() {
+        v.visit_value(value);
+    }
+}
+
+/// A visitor that can modify a css tree in place.
+///
+/// This is like [`Visit`], but with mutable references.
+/// To replace a node, assign to it in the corresponding method.
+///
+/// See also the [module documentation](self).
+pub trait VisitMut {
+    /// Visit a sequence of top-level items.
+    fn visit_items_mut(&mut self, items: &mut Vec<Item>) {
+        walk_items_mut(self, items)
+    }
+    /// Visit a top-level item.
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        walk_item_mut(self, item)
+    }
+    /// Visit a rule.
+    fn visit_rule_mut(&mut self, rule: &mut Rule) {
+        walk_rule_mut(self, rule)
+    }
+    /// Visit an item in the body of a rule.
+    fn visit_body_item_mut(&mut self, item: &mut BodyItem) {
+        walk_body_item_mut(self, item)
+    }
+    /// Visit an at-rule.
+    fn visit_at_rule_mut(&mut self, rule: &mut AtRule) {
+        walk_at_rule_mut(self, rule)
+    }
+    /// Visit an item in the body of an at-rule.
+    fn visit_at_rule_body_item_mut(&mut self, item: &mut AtRuleBodyItem) {
+        walk_at_rule_body_item_mut(self, item)
+    }
+    /// Visit a property.
+    fn visit_property_mut(&mut self, property: &mut Property) {
+        walk_property_mut(self, property)
+    }
+    /// Visit an `@import`.
+    fn visit_import_mut(&mut self, import: &mut Import) {
+        walk_import_mut(self, import)
+    }
+    /// Visit a comment.
+    fn visit_comment_mut(&mut self, _comment: &mut Comment) {}
+    /// Visit the selectors of a rule.
+    fn visit_selectors_mut(&mut self, _selectors: &mut Selectors) {}
+    /// Visit a value.
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        walk_value_mut(self, value)
+    }
+    /// Visit the arguments of a function call.
+    fn visit_call_args_mut(&mut self, args: &mut CallArgs) {
+        walk_call_args_mut(self, args)
+    }
+}
+
+/// Visit each item in `items`.
+pub fn walk_items_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    items: &mut Vec<Item>,
+) {
+    for item in items {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_item_mut<V: VisitMut + ?Sized>(v: &mut V, item: &mut Item) {
+    match item {
+        Item::Comment(comment) => v.visit_comment_mut(comment),
+        Item::Import(import) => v.visit_import_mut(import),
+        Item::Rule(rule) => v.visit_rule_mut(rule),
+        Item::AtRule(rule) => v.visit_at_rule_mut(rule),
+        Item::Separator => (),
+    }
+}
+
+/// Visit the selectors and then each body item of `rule`.
+pub fn walk_rule_mut<V: VisitMut + ?Sized>(v: &mut V, rule: &mut Rule) {
+    v.visit_selectors_mut(&mut rule.selectors);
+    for item in &mut rule.body {
+        v.visit_body_item_mut(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_body_item_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    item: &mut BodyItem,
+) {
+    match item {
+        BodyItem::Import(import) => v.visit_import_mut(import),
+        BodyItem::Property(property) => v.visit_property_mut(property),
+        BodyItem::CustomProperty(..) => (),
+        BodyItem::Comment(comment) => v.visit_comment_mut(comment),
+    }
+}
+
+/// Visit the args and then any body items of `rule`.
+pub fn walk_at_rule_mut<V: VisitMut + ?Sized>(v: &mut V, rule: &mut AtRule) {
+    v.visit_value_mut(&mut rule.args);
+    for item in rule.body.iter_mut().flatten() {
+        v.visit_at_rule_body_item_mut(item);
+    }
+}
+
+/// Visit the content of `item`.
+pub fn walk_at_rule_body_item_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    item: &mut AtRuleBodyItem,
+) {
+    match item {
+        AtRuleBodyItem::Import(import) => v.visit_import_mut(import),
+        AtRuleBodyItem::Comment(comment) => v.visit_comment_mut(comment),
+        AtRuleBodyItem::Rule(rule) => v.visit_rule_mut(rule),
+        AtRuleBodyItem::Property(property) => v.visit_property_mut(property),
+        AtRuleBodyItem::AtRule(rule) => v.visit_at_rule_mut(rule),
+        AtRuleBodyItem::CustomProperty(..) | AtRuleBodyItem::Separator => (),
+    }
+}
+
+/// Visit the value of `property`.
+pub fn walk_property_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    property: &mut Property,
+) {
+    v.visit_value_mut(&mut property.value);
+}
+
+/// Visit the args of `import`.
+pub fn walk_import_mut<V: VisitMut + ?Sized>(v: &mut V, import: &mut Import) {
+    v.visit_value_mut(&mut import.args);
+}
+
+/// Visit the component values of `value`.
+pub fn walk_value_mut<V: VisitMut + ?Sized>(v: &mut V, value: &mut Value) {
+    match value {
+        Value::Call(_, args) | Value::ArgList(args) => {
+            v.visit_call_args_mut(args)
+        }
+        Value::List(items, ..) => {
+            for item in items {
+                v.visit_value_mut(item);
+            }
+        }
+        Value::Paren(value) | Value::UnaryOp(_, value) => {
+            v.visit_value_mut(value)
+        }
+        Value::BinOp(a, _, _, _, b) => {
+            v.visit_value_mut(a);
+            v.visit_value_mut(b);
+        }
+        Value::Map(map) => {
+            for (key, value) in map.iter_mut() {
+                v.visit_value_mut(key);
+                v.visit_value_mut(value);
+            }
+        }
+        Value::Bang(_)
+        | Value::Function(..)
+        | Value::Literal(_)
+        | Value::Numeric(..)
+        | Value::Color(..)
+        | Value::Null
+        | Value::True
+        | Value::False
+        | Value::UnicodeRange(_) => (),
+    }
+}
+
+/// Visit the positional and then the named values of `args`.
+pub fn walk_call_args_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    args: &mut CallArgs,
+) {
+    for value in &mut args.positional {
+        v.visit_value_mut(value);
+    }
+    for (_, value) in args.named.iter_mut() {
+        v.visit_value_mut(value);
+    }
+}
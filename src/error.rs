@@ -26,6 +26,12 @@ pub enum Error {
     ///
     /// The bool is true for a used module and false for an import.
     ImportLoop(bool, SourcePos, Option<SourcePos>),
+    /// Tried to configure a module at pos, but it was already
+    /// loaded at pos.
+    ///
+    /// The module name is shown in the message if given (for
+    /// `meta.load-css`).
+    AlreadyLoaded(Option<String>, SourcePos, SourcePos),
     /// A range error
     BadRange(RangeError),
     /// Error parsing sass data.
@@ -80,6 +86,28 @@ impl fmt::Debug for Error {
                     pos.show(out)
                 }
             }
+            Error::AlreadyLoaded(ref name, ref pos, ref oldpos) => {
+                writeln!(
+                    out,
+                    "{} was already loaded, so it can't be configured \
+                     using \"with\".",
+                    name.as_deref().unwrap_or("This module"),
+                )?;
+                if pos.same_file_as(oldpos) {
+                    show_in_file(
+                        out,
+                        pos,
+                        " new load",
+                        oldpos,
+                        " original load",
+                    )?;
+                } else {
+                    pos.show_detail(out, '^', " new load")?;
+                    writeln!(out)?;
+                    oldpos.show_detail(out, '=', " original load")?;
+                }
+                pos.show_files(out)
+            }
             Error::BadCall(ref msg, ref callpos, ref declpos) => {
                 writeln!(out, "{}", msg)?;
                 if let Some(declpos) = declpos {
@@ -196,6 +224,14 @@ pub enum Invalid {
     InScope(ScopeError),
     /// An `@error` reached.
     AtError(String),
+    /// More than one file was found for an import or module url.
+    ///
+    /// Contains the canonical urls of the files found.
+    AmbiguousImport(Vec<String>),
+    /// A plain css `@import` after other css is already written.
+    ///
+    /// This happens only when writing the output as it is evaluated.
+    LateImport,
 }
 impl Invalid {
     /// Combine this with a position to get a proper error.
@@ -224,6 +260,18 @@ impl fmt::Display for Invalid {
             }
             Invalid::InScope(err) => err.fmt(out),
             Invalid::AtError(msg) => msg.fmt(out),
+            Invalid::AmbiguousImport(found) => {
+                write!(out, "It's not clear which file to import. Found:")?;
+                for url in found {
+                    write!(out, "\n  {}", url)?;
+                }
+                Ok(())
+            }
+            Invalid::LateImport => {
+                "This @import can't be moved to the start of the output, \
+                 since other css is already written."
+                    .fmt(out)
+            }
         }
     }
 }
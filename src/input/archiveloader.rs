@@ -0,0 +1,219 @@
+use super::fsloader::canonical_path;
+use super::{LoadError, Loader};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// A [`Loader`] for files in `.zip` and `.tar` archives, and in
+/// directories.
+///
+/// Like a [`FsLoader`][super::FsLoader], this loader has a list of
+/// paths to search for files, but each path can be either a directory
+/// or an archive.
+/// An archive is read once, when it is added, so a stylesheet can be
+/// compiled from an archive without extracting it.
+/// The name resolution of
+/// [`Context::find_file`][super::Context::find_file] (partials,
+/// directory index files, etc) works just as for files on disk.
+///
+/// This loader requires the `archive` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use rsass::input::{ArchiveLoader, Context};
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut loader = ArchiveLoader::new();
+/// loader.push_path("themes/dark.zip".as_ref())?;
+/// loader.push_path("scss".as_ref())?;
+/// let mut context = Context::for_loader(loader);
+/// let main = context.load_root("main")?;
+/// let css = context.transform(main)?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default)]
+pub struct ArchiveLoader {
+    path: Vec<Base>,
+}
+
+#[derive(Debug)]
+enum Base {
+    Dir(PathBuf),
+    Archive(PathBuf, BTreeMap<String, Vec<u8>>),
+}
+
+impl ArchiveLoader {
+    /// Create a new loader without any paths.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a loader for a single archive.
+    pub fn for_archive(path: &Path) -> Result<Self, LoadError> {
+        let mut loader = Self::new();
+        loader.push_archive(path)?;
+        Ok(loader)
+    }
+
+    /// Add a path to search for files.
+    ///
+    /// If `path` has a `.zip` or `.tar` suffix, it is read as an
+    /// archive, otherwise it is used as a directory.
+    pub fn push_path(&mut self, path: &Path) -> Result<(), LoadError> {
+        if ArchiveFormat::for_path(path).is_some() {
+            self.push_archive(path)
+        } else {
+            self.push_dir(path);
+            Ok(())
+        }
+    }
+
+    /// Add a directory to search for files.
+    pub fn push_dir(&mut self, path: &Path) {
+        self.path.push(Base::Dir(path.into()));
+    }
+
+    /// Add an archive to search for files.
+    ///
+    /// All stylesheets (`.scss`, `.sass` and `.css` files) in the
+    /// archive are read when it is added, other files are ignored.
+    /// The format of the archive is determined from the suffix of
+    /// `path`, which should be `.zip` or `.tar`.
+    pub fn push_archive(&mut self, path: &Path) -> Result<(), LoadError> {
+        let name = path.display().to_string();
+        let format = ArchiveFormat::for_path(path)
+            .ok_or_else(|| LoadError::UnknownFormat(name.clone()))?;
+        let input_error = |e| LoadError::Input(name.clone(), e);
+        let file = File::open(path).map_err(input_error)?;
+        let files = match format {
+            ArchiveFormat::Zip => read_zip(file),
+            ArchiveFormat::Tar => read_tar(file),
+        }
+        .map_err(input_error)?;
+        tracing::debug!(?path, files = files.len(), "read archive");
+        self.path.push(Base::Archive(path.into(), files));
+        Ok(())
+    }
+}
+
+impl Loader for ArchiveLoader {
+    type File = Box<dyn Read>;
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.find_canonical(url)?.map(|(_, file)| file))
+    }
+
+    /// Find a file.
+    ///
+    /// The canonical url of a file in a directory is its absolute
+    /// path, and the canonical url of a file in an archive is the
+    /// absolute path of the archive followed by the name of the file.
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
+        if !url.is_empty() {
+            for base in &self.path {
+                match base {
+                    Base::Dir(dir) => {
+                        let full = dir.join(url);
+                        if full.is_file() {
+                            tracing::debug!(?full, "opening file");
+                            return File::open(&full)
+                                .map_err(|e| {
+                                    let name = full.display().to_string();
+                                    LoadError::Input(name, e)
+                                })
+                                .map(|f| {
+                                    let url = canonical_path(&full);
+                                    Some((url, Box::new(f) as Self::File))
+                                });
+                        }
+                    }
+                    Base::Archive(archive, files) => {
+                        if let Some(data) = files.get(url) {
+                            tracing::debug!(
+                                ?archive,
+                                url,
+                                "found in archive"
+                            );
+                            let url = format!(
+                                "{}/{}",
+                                canonical_path(archive),
+                                url
+                            );
+                            let file = Cursor::new(data.clone());
+                            return Ok(Some((url, Box::new(file))));
+                        }
+                    }
+                }
+            }
+            tracing::trace!(url, "Not found");
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+}
+
+impl ArchiveFormat {
+    fn for_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "zip" => Some(ArchiveFormat::Zip),
+            "tar" => Some(ArchiveFormat::Tar),
+            _ => None,
+        }
+    }
+}
+
+fn read_zip(file: File) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut files = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_file() && is_stylesheet(entry.name()) {
+            let name = entry_name(entry.name());
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            files.insert(name, data);
+        }
+    }
+    Ok(files)
+}
+
+fn read_tar(file: File) -> io::Result<BTreeMap<String, Vec<u8>>> {
+    let mut archive = tar::Archive::new(file);
+    let mut files = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry_name(&entry.path()?.to_string_lossy());
+        if entry.header().entry_type().is_file() && is_stylesheet(&name) {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            files.insert(name, data);
+        }
+    }
+    Ok(files)
+}
+
+/// Check if an archive entry name is the name of a stylesheet.
+fn is_stylesheet(name: &str) -> bool {
+    name.ends_with(".scss")
+        || name.ends_with(".sass")
+        || name.ends_with(".css")
+}
+
+/// Normalize the name of an archive entry to a relative url.
+fn entry_name(name: &str) -> String {
+    let name = name.replace('\\', "/");
+    let mut name = name.as_str();
+    while let Some(rest) = name.strip_prefix("./") {
+        name = rest;
+    }
+    name.trim_start_matches('/').to_string()
+}
@@ -1,3 +1,4 @@
+use super::fsloader::canonical_path;
 use super::{LoadError, Loader, SourceFile, SourceName};
 use std::path::{Path, PathBuf};
 
@@ -11,6 +12,7 @@ use std::path::{Path, PathBuf};
 #[derive(Debug)]
 pub struct CargoLoader {
     path: Vec<PathBuf>,
+    watch: bool,
 }
 
 impl CargoLoader {
@@ -23,9 +25,20 @@ impl CargoLoader {
     pub fn for_crate() -> Result<Self, LoadError> {
         Ok(Self {
             path: vec![get_pkg_base()?],
+            watch: true,
         })
     }
 
+    /// Don't print `cargo:rerun-if-changed` messages.
+    ///
+    /// This is useful when loading files from something that is not a
+    /// build script, such as a procedural macro, where changes are
+    /// tracked in some other way.
+    pub fn without_watch(mut self) -> Self {
+        self.watch = false;
+        self
+    }
+
     /// Add a path to search for files.
     ///
     /// The path can be relative to the crate manifest directory, or
@@ -52,14 +65,16 @@ impl CargoLoader {
         let mut f = std::fs::File::open(&path)
             .map_err(|e| LoadError::Input(path.display().to_string(), e))?;
         cargo_watch(&path);
+        let canonical = canonical_path(&path);
         let (path, name) = if let Some(base) = path.parent() {
             (vec![base.to_path_buf()], path.strip_prefix(base).unwrap())
         } else {
             (vec![get_pkg_base()?], path.as_ref())
         };
-        let loader = Self { path };
+        let loader = Self { path, watch: true };
         let source = SourceName::root(name.display().to_string());
-        let source = SourceFile::read(&mut f, source)?;
+        let source =
+            SourceFile::read(&mut f, source)?.with_canonical_url(canonical);
         Ok((loader, source))
     }
 }
@@ -68,6 +83,14 @@ impl Loader for CargoLoader {
     type File = std::fs::File;
 
     fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.find_canonical(url)?.map(|(_, file)| file))
+    }
+
+    /// Find a file, using the absolute path as the canonical url.
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
         if !url.is_empty() {
             for base in &self.path {
                 let full = base.join(url);
@@ -76,8 +99,10 @@ impl Loader for CargoLoader {
                     let file = Self::File::open(&full).map_err(|e| {
                         LoadError::Input(full.display().to_string(), e)
                     })?;
-                    cargo_watch(&full);
-                    return Ok(Some(file));
+                    if self.watch {
+                        cargo_watch(&full);
+                    }
+                    return Ok(Some((canonical_path(&full), file)));
                 }
                 tracing::trace!(?full, "Not found");
             }
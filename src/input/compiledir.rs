@@ -0,0 +1,270 @@
+use super::{Context, FsLoader, LoadError, TrackingLoader};
+use crate::output::Format;
+use crate::Error;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Compile a directory of stylesheets, for use in a `build.rs` script.
+///
+/// Each non-partial `.scss` file in the source directory (recursively)
+/// is an entry point, and is compiled to a `.css` file with the same
+/// relative path in the output directory.
+/// A rust module with constants for the path and a content hash of
+/// each output file is also generated, so the program can refer to
+/// them (e.g. to serve them with a far-future expires header).
+///
+/// Source maps are not supported yet, since rsass does not keep
+/// track of the source positions of the generated css.
+///
+/// In `build.rs`:
+///
+/// ```no_run
+/// # use rsass::input::CompileDir;
+/// # use rsass::output::{Format, Style};
+/// # fn main() -> Result<(), rsass::Error> {
+/// CompileDir::new()
+///     .with_format(Format::default().with_style(Style::Compressed))
+///     .build("scss".as_ref(), "css")?;
+/// # Ok(()) }
+/// ```
+///
+/// And in the program, for a `scss/admin/main.scss` entry point:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/css.rs"));
+///
+/// let url = format!("/static/{}?v={}", ADMIN_MAIN_CSS, ADMIN_MAIN_HASH);
+/// let css = std::fs::read(concat!(env!("OUT_DIR"), "/css/admin/main.css"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CompileDir {
+    format: Format,
+}
+
+/// A stylesheet compiled by [`CompileDir`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledFile {
+    /// The path of the output css file, relative to the output
+    /// directory, with `/` as separator.
+    pub name: String,
+    /// A hash of the content of the output css file, as 16 hex digits.
+    pub hash: String,
+    /// The paths of the entry point and all files it depends on.
+    pub dependencies: Vec<PathBuf>,
+}
+
+impl CompileDir {
+    /// Create a new `CompileDir` with the default format.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the output format.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Compile the stylesheets in `src` from a build script.
+    ///
+    /// If `src` is relative, it will be resolved from the directory
+    /// containing the manifest of your package.
+    /// The css files are written to the directory `out` in the
+    /// `OUT_DIR` of the build, and the rust module to `out` with an
+    /// `.rs` suffix in the `OUT_DIR`.
+    /// A `cargo:rerun-if-changed` message is printed for `src` and
+    /// for each file that any entry point depends on.
+    pub fn build(
+        &self,
+        src: &Path,
+        out: &str,
+    ) -> Result<Vec<CompiledFile>, Error> {
+        let src = if src.is_absolute() {
+            src.into()
+        } else {
+            env_path("CARGO_MANIFEST_DIR")?.join(src)
+        };
+        let out = env_path("OUT_DIR")?.join(out);
+        println!("cargo:rerun-if-changed={}", src.display());
+        let compiled = self.write_to(&src, &out)?;
+        let mut watched = Vec::new();
+        for path in compiled.iter().flat_map(|f| &f.dependencies) {
+            if !watched.contains(&path) {
+                println!("cargo:rerun-if-changed={}", path.display());
+                watched.push(path);
+            }
+        }
+        Ok(compiled)
+    }
+
+    /// Compile the stylesheets in `src` to the directory `out`.
+    ///
+    /// This is the part of [`build`][Self::build] that does not
+    /// depend on being called from cargo.
+    /// The rust module is written to `out` with an `.rs` suffix.
+    pub fn write_to(
+        &self,
+        src: &Path,
+        out: &Path,
+    ) -> Result<Vec<CompiledFile>, Error> {
+        let mut entries = Vec::new();
+        collect_entries(src, src, &mut entries)?;
+        entries.sort();
+        check_idents(&entries)?;
+        std::fs::create_dir_all(out)
+            .map_err(|e| LoadError::Input(out.display().to_string(), e))?;
+        let mut compiled = Vec::with_capacity(entries.len());
+        for entry in entries {
+            compiled.push(self.compile_file(src, &entry, out)?);
+        }
+        let module = out.with_extension("rs");
+        write_file(&module, write_module(&compiled).as_bytes())?;
+        Ok(compiled)
+    }
+
+    /// Compile the entry point `name` in `src` to the same name in `out`.
+    fn compile_file(
+        &self,
+        src: &Path,
+        name: &str,
+        out: &Path,
+    ) -> Result<CompiledFile, Error> {
+        let loader = TrackingLoader::new(FsLoader::for_dir(src));
+        let loaded = loader.loaded();
+        let mut context =
+            Context::for_loader(loader).with_format(self.format);
+        let file = context.load_root(name)?;
+        let css = context.transform(file)?;
+        let dependencies =
+            loaded.take().into_iter().map(PathBuf::from).collect();
+
+        let name = format!("{}.css", &name[..name.len() - ".scss".len()]);
+        let path = out.join(&name);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                LoadError::Input(dir.display().to_string(), e)
+            })?;
+        }
+        write_file(&path, &css)?;
+        Ok(CompiledFile {
+            hash: content_hash(&css),
+            name,
+            dependencies,
+        })
+    }
+}
+
+/// Find all non-partial scss files in `dir`, recursively.
+///
+/// The found files are named by their path relative to `base`, with
+/// `/` as separator.
+fn collect_entries(
+    base: &Path,
+    dir: &Path,
+    files: &mut Vec<String>,
+) -> Result<(), LoadError> {
+    let input_error = |e| LoadError::Input(dir.display().to_string(), e);
+    for entry in std::fs::read_dir(dir).map_err(input_error)? {
+        let path = entry.map_err(input_error)?.path();
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        if path.is_dir() {
+            collect_entries(base, &path, files)?;
+        } else if file_name.ends_with(".scss") && !file_name.starts_with('_')
+        {
+            files.push(
+                path.strip_prefix(base)
+                    .unwrap_or(&path)
+                    .iter()
+                    .map(|part| part.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Generate a rust module with constants for the compiled files.
+///
+/// For `admin/main.css`, the constants are `ADMIN_MAIN_CSS` for the
+/// name and `ADMIN_MAIN_HASH` for the content hash.
+fn write_module(compiled: &[CompiledFile]) -> String {
+    let mut module = String::new();
+    for file in compiled {
+        let ident = ident(&file.name[..file.name.len() - ".css".len()]);
+        module.push_str(&format!(
+            "/// The path of `{name}`, relative to the output directory.\n\
+             pub const {ident}_CSS: &str = {name:?};\n\
+             /// A hash of the content of `{name}`.\n\
+             pub const {ident}_HASH: &str = {hash:?};\n",
+            name = file.name,
+            ident = ident,
+            hash = file.hash,
+        ));
+    }
+    module
+}
+
+/// Check that no two entry points get the same constant names.
+///
+/// E.g. `admin/main.scss` and `admin-main.scss` would both get
+/// `ADMIN_MAIN_CSS`, which would not compile.
+fn check_idents(entries: &[String]) -> Result<(), Error> {
+    let mut seen = BTreeMap::new();
+    for entry in entries {
+        let ident = ident(&entry[..entry.len() - ".scss".len()]);
+        if let Some(other) = seen.insert(ident, entry) {
+            return Err(Error::error(format!(
+                "Entry points {:?} and {:?} would get the same \
+                 constant names",
+                other, entry,
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The rust identifier prefix for the constants of a file.
+///
+/// For a `stem` of `admin/main`, this is `ADMIN_MAIN`.
+fn ident(stem: &str) -> String {
+    let ident = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// Write `data` to the file `path`.
+fn write_file(path: &Path, data: &[u8]) -> Result<(), LoadError> {
+    std::fs::write(path, data)
+        .map_err(|e| LoadError::Input(path.display().to_string(), e))
+}
+
+/// A 64-bit FNV-1a hash of `data`, as 16 hex digits.
+///
+/// This is not a cryptographic hash, but it is stable between builds
+/// and platforms, which is what matters for cache busting.
+fn content_hash(data: &[u8]) -> String {
+    let hash = data.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Get a path from an environment variable set by cargo.
+fn env_path(var: &str) -> Result<PathBuf, LoadError> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .ok_or(LoadError::NotCalledFromCargo)
+}
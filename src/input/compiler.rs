@@ -0,0 +1,338 @@
+use super::{
+    Context, Importer, Loader, ModuleInit, Parsed, SourceFile, SourceName,
+};
+use crate::output::Format;
+use crate::{css, sass, Error, ScopeRef, SourcePos};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{Arc, RwLock};
+use std::{fmt, thread};
+
+/// A compiler that can be shared between threads.
+///
+/// Unlike a [`Context`], which is consumed by a transformation, a
+/// `Compiler` can compile any number of entry points, and it is
+/// cheap to clone.
+/// All clones share the same loader and the same cache of parsed
+/// files, so a file used by many entry points is only parsed once.
+/// Each compilation still gets its own global scope.
+/// Importers, custom modules and global definitions are shared as
+/// well, so they must be `Send + Sync`.
+///
+/// [`compile_many`][Self::compile_many] uses 4 threads by default,
+/// regardless of the number of cores.
+/// Use [`with_threads`][Self::with_threads] to use more (or fewer)
+/// threads.
+///
+/// The cache assumes that files are not changed during the lifetime
+/// of the compiler, see [`clear_cache`][Self::clear_cache].
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{Compiler, MemoryLoader};
+/// let mut loader = MemoryLoader::new();
+/// loader.insert("_colors.scss", "$c: red;");
+/// loader.insert("a.scss", "@use 'colors';\na { b: colors.$c }");
+/// loader.insert("b.scss", "@use 'colors';\nb { a: colors.$c }");
+/// loader.insert("c.scss", "c { d: $undefined }");
+///
+/// let compiler = Compiler::new(loader);
+/// let result = compiler.compile_many(vec!["a", "b", "c"]);
+/// assert_eq!(result[0].as_ref().unwrap(), b"a {\n  b: red;\n}\n");
+/// assert_eq!(result[1].as_ref().unwrap(), b"b {\n  a: red;\n}\n");
+/// assert!(result[2].is_err());
+/// ```
+pub struct Compiler<AnyLoader> {
+    loader: Arc<AnyLoader>,
+    format: Format,
+    threads: usize,
+    cache: Arc<ParseCache>,
+    importers: Vec<Arc<dyn Importer>>,
+    modules: BTreeMap<String, Arc<ModuleInit>>,
+    global: Option<Arc<ModuleInit>>,
+}
+
+impl<AnyLoader: Loader> Compiler<AnyLoader> {
+    /// Create a new `Compiler` for a given file [`Loader`].
+    pub fn new(loader: AnyLoader) -> Self {
+        Compiler {
+            loader: Arc::new(loader),
+            format: Format::default(),
+            threads: 4,
+            cache: Arc::default(),
+            importers: Vec::new(),
+            modules: BTreeMap::new(),
+            global: None,
+        }
+    }
+
+    /// Set the output format for this compiler.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the number of threads used by
+    /// [`compile_many`][Self::compile_many].
+    ///
+    /// The default is 4, regardless of the number of cores.
+    /// On rust 1.59 or later, `std::thread::available_parallelism`
+    /// can be used to get the number of cores.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Add an [`Importer`] to this compiler.
+    ///
+    /// See [`Context::push_importer`].
+    pub fn with_importer(
+        mut self,
+        importer: impl Importer + 'static,
+    ) -> Self {
+        self.importers.push(Arc::new(importer));
+        self
+    }
+
+    /// Add a custom built-in module to this compiler.
+    ///
+    /// See [`Context::define_module`].
+    pub fn define_module(
+        &mut self,
+        name: impl Into<String>,
+        init: impl Fn(&ScopeRef) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.modules.insert(name.into(), Arc::new(init));
+    }
+
+    /// Add a custom built-in module to this compiler.
+    ///
+    /// See [`define_module`][Self::define_module].
+    pub fn with_module(
+        mut self,
+        name: impl Into<String>,
+        init: impl Fn(&ScopeRef) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.define_module(name, init);
+        self
+    }
+
+    /// Set a function to predefine variables, functions or mixins.
+    ///
+    /// Each compilation gets its own global scope, so `init` is
+    /// called with the global scope before each compilation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::{Compiler, MemoryLoader};
+    /// # use rsass::sass::Name;
+    /// # use rsass::value::{Numeric, Unit};
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut loader = MemoryLoader::new();
+    /// loader.insert("a.scss", "a { gap: $gap }");
+    /// let compiler = Compiler::new(loader).with_global_scope(|scope| {
+    ///     let gap = Numeric::new(4, Unit::Px);
+    ///     scope.define(Name::from_static("gap"), gap.into())?;
+    ///     Ok(())
+    /// });
+    /// assert_eq!(compiler.compile("a")?, b"a {\n  gap: 4px;\n}\n");
+    /// # Ok(()) }
+    /// ```
+    pub fn with_global_scope(
+        mut self,
+        init: impl Fn(&ScopeRef) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.global = Some(Arc::new(init));
+        self
+    }
+
+    /// Compile the entry point `url` to css.
+    pub fn compile(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let mut context = Context::for_loader(&*self.loader)
+            .with_format(self.format)
+            .with_parse_cache(self.cache.clone())
+            .with_shared(&self.importers, &self.modules);
+        if let Some(init) = &self.global {
+            init(&context.get_scope())?;
+        }
+        let file = context.load_root(url)?;
+        context.transform(file)
+    }
+
+    /// Forget all parsed files.
+    ///
+    /// Any file that has changed since it was parsed by this compiler
+    /// (or any of its clones) will be read again.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+}
+
+impl<AnyLoader> Compiler<AnyLoader>
+where
+    AnyLoader: Loader + Send + Sync + 'static,
+{
+    /// Compile a list of entry points on a pool of threads.
+    ///
+    /// The result has one item per entry point, in the same order as
+    /// the `urls`.
+    pub fn compile_many<I>(&self, urls: I) -> Vec<Result<Vec<u8>, Error>>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let urls =
+            Arc::new(urls.into_iter().map(Into::into).collect::<Vec<_>>());
+        let next = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = channel();
+        let workers = (0..self.threads.min(urls.len()))
+            .map(|_| {
+                let (compiler, urls, next, tx) =
+                    (self.clone(), urls.clone(), next.clone(), tx.clone());
+                thread::spawn(move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    match urls.get(i) {
+                        Some(url) => {
+                            let _ = tx.send((i, compiler.compile(url)));
+                        }
+                        None => break,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(tx);
+        let mut result = (0..urls.len()).map(|_| None).collect::<Vec<_>>();
+        for (i, css) in rx {
+            result[i] = Some(css);
+        }
+        for worker in workers {
+            if let Err(panic) = worker.join() {
+                std::panic::resume_unwind(panic);
+            }
+        }
+        result.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+impl<AnyLoader> Clone for Compiler<AnyLoader> {
+    fn clone(&self) -> Self {
+        Compiler {
+            loader: self.loader.clone(),
+            format: self.format,
+            threads: self.threads,
+            cache: self.cache.clone(),
+            importers: self.importers.clone(),
+            modules: self.modules.clone(),
+            global: self.global.clone(),
+        }
+    }
+}
+
+impl<AnyLoader: fmt::Debug> fmt::Debug for Compiler<AnyLoader> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compiler")
+            .field("loader", &self.loader)
+            .field("format", &self.format)
+            .field("threads", &self.threads)
+            .field("cache", &self.cache)
+            .field("importers", &self.importers)
+            .field("modules", &self.modules.keys())
+            .field("global", &self.global.is_some())
+            .finish()
+    }
+}
+
+/// Parsed files, by canonical url.
+///
+/// The positions in a parsed file refers to the [`SourceName`] it was
+/// parsed with, which includes the import chain.
+/// When a file is reused with another import chain (typically from
+/// another entry point), a copy of it is rebased to that name.
+#[derive(Default)]
+pub(crate) struct ParseCache(
+    RwLock<BTreeMap<String, (SourceName, Arc<Parsed>)>>,
+);
+
+impl ParseCache {
+    /// Get a parsed file from the cache, or parse and add it.
+    pub(crate) fn parse(
+        &self,
+        file: &SourceFile,
+    ) -> Result<Arc<Parsed>, Error> {
+        let url = file.canonical_url();
+        let cached = self.0.read().unwrap().get(url).cloned();
+        if let Some((source, parsed)) = cached {
+            return Ok(if &source == file.source() {
+                parsed
+            } else {
+                Arc::new(rebase(&parsed, file.source()))
+            });
+        }
+        let parsed = Arc::new(file.parse()?);
+        self.0
+            .write()
+            .unwrap()
+            .entry(url.into())
+            .or_insert_with(|| (file.source().clone(), parsed.clone()));
+        Ok(parsed)
+    }
+
+    fn clear(&self) {
+        self.0.write().unwrap().clear();
+    }
+}
+
+/// Copy a parsed file, with all positions referring to `source`.
+fn rebase(parsed: &Parsed, source: &SourceName) -> Parsed {
+    let mut parsed = parsed.clone();
+    let mut rebase = Rebase(source);
+    match &mut parsed {
+        Parsed::Scss(items) => {
+            sass::visit::VisitMut::visit_items_mut(&mut rebase, items)
+        }
+        Parsed::Css(items) => {
+            css::visit::VisitMut::visit_items_mut(&mut rebase, items)
+        }
+    }
+    parsed
+}
+
+struct Rebase<'a>(&'a SourceName);
+
+impl sass::visit::VisitMut for Rebase<'_> {
+    fn visit_pos_mut(&mut self, pos: &mut SourcePos) {
+        pos.set_file(self.0);
+    }
+}
+
+impl css::visit::VisitMut for Rebase<'_> {
+    fn visit_rule_mut(&mut self, rule: &mut css::Rule) {
+        if let Some(pos) = &mut rule.pos {
+            pos.set_file(self.0);
+        }
+        css::visit::walk_rule_mut(self, rule)
+    }
+    fn visit_at_rule_mut(&mut self, rule: &mut css::AtRule) {
+        if let Some(pos) = &mut rule.pos {
+            pos.set_file(self.0);
+        }
+        css::visit::walk_at_rule_mut(self, rule)
+    }
+    fn visit_property_mut(&mut self, property: &mut css::Property) {
+        if let Some(pos) = &mut property.pos {
+            pos.set_file(self.0);
+        }
+        css::visit::walk_property_mut(self, property)
+    }
+}
+
+impl fmt::Debug for ParseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.0.read().unwrap().keys())
+            .finish()
+    }
+}
@@ -1,17 +1,23 @@
+use super::compiler::ParseCache;
 use super::{
-    CargoLoader, FsLoader, LoadError, Loader, SourceFile, SourceKind,
+    CargoLoader, FsLoader, Importer, LoadError, Loader, MemoryLoader, Parsed,
+    SourceFile, SourceKind,
 };
-use crate::output::{handle_parsed, CssBuf, CssHead, Format};
+use crate::css;
+use crate::error::Invalid;
+use crate::output::{handle_parsed, write_css, CssHead, CssItems, Format};
 use crate::{Error, ScopeRef};
+use std::io::Write;
+use std::sync::Arc;
 use std::{borrow::Cow, collections::BTreeMap, fmt, path::Path};
 use tracing::instrument;
 
 /// Utility keeping track of loading files.
 ///
 /// The context is generic over the [`Loader`].
-/// [`FsContext`] and [`CargoContext`] are type aliases for `Context`
-/// where the loader is a [`FsLoader`] or [`CargoLoader`],
-/// respectively.
+/// [`FsContext`], [`CargoContext`] and [`MemoryContext`] are type
+/// aliases for `Context` where the loader is a [`FsLoader`],
+/// [`CargoLoader`] or [`MemoryLoader`], respectively.
 ///
 /// # Examples
 ///
@@ -23,7 +29,7 @@ use tracing::instrument;
 /// # use rsass::output::{Format, Style};
 /// # fn main() -> Result<(), rsass::Error> {
 /// let context = FsContext::for_cwd()
-///     .with_format(Format { style: Style::Compressed, precision: 2 });
+///     .with_format(Format::default().with_style(Style::Compressed).with_precision(2));
 /// let scss_input = SourceFile::scss_bytes(
 ///     "$gap: 4em / 3;
 ///     \np {\
@@ -43,7 +49,7 @@ use tracing::instrument;
 /// # use rsass::input::{FsContext, SourceFile, SourceName};
 /// # use rsass::output::{Format, Style};
 /// # fn main() -> Result<(), rsass::Error> {
-/// # let context = FsContext::for_cwd().with_format(Format { style: Style::Compressed, precision: 2 });
+/// # let context = FsContext::for_cwd().with_format(Format::default().with_style(Style::Compressed).with_precision(2));
 /// let css_input = SourceFile::css_bytes(
 ///     "p {\
 ///     \n    margin: 1.333333333em 0;\
@@ -58,11 +64,17 @@ use tracing::instrument;
 /// ```
 pub struct Context<Loader> {
     loader: Loader,
+    importers: Vec<Arc<dyn Importer>>,
+    modules: BTreeMap<String, Arc<ModuleInit>>,
     scope: Option<ScopeRef>,
     loading: BTreeMap<String, SourceKind>,
-    // TODO: Maybe have a map to loaded SourceFiles as well?  Or even Parsed?
+    cache: Option<Arc<ParseCache>>,
 }
 
+/// A function defining the members of a custom built-in module.
+pub(crate) type ModuleInit =
+    dyn Fn(&ScopeRef) -> Result<(), Error> + Send + Sync;
+
 /// A file-system based [`Context`].
 pub type FsContext = Context<FsLoader>;
 
@@ -127,37 +139,232 @@ impl CargoContext {
     }
 }
 
+/// A [`Context`] for files kept in memory.
+///
+/// See [`MemoryLoader`] for an example.
+pub type MemoryContext = Context<MemoryLoader>;
+
+impl MemoryContext {
+    /// Create a new `Context` without any files.
+    pub fn new() -> Self {
+        Context::for_loader(MemoryLoader::new())
+    }
+
+    /// Add a file, or update it if it already exists.
+    ///
+    /// Returns the previous content of the file, if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        self.loader.insert(name, data)
+    }
+
+    /// Remove a file.
+    ///
+    /// Returns the content of the removed file, if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.loader.remove(name)
+    }
+}
+
+impl Default for MemoryContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<AnyLoader: Loader> Context<AnyLoader> {
     /// Create a new `Context` for a given file [`Loader`].
     pub fn for_loader(loader: AnyLoader) -> Self {
         Context {
             loader,
+            importers: Vec::new(),
+            modules: BTreeMap::new(),
             scope: None,
             loading: Default::default(),
+            cache: None,
         }
     }
 
     /// Transform some input source to css.
     ///
     /// The css output is returned as a raw byte vector.
-    pub fn transform(mut self, file: SourceFile) -> Result<Vec<u8>, Error> {
+    pub fn transform(self, file: SourceFile) -> Result<Vec<u8>, Error> {
+        let format = self.format();
+        let items = self.transform_to_ast(file)?;
+        Ok(write_css(&items, format))
+    }
+
+    /// Transform some input source to css, written to `out`.
+    ///
+    /// Each top-level item is written as soon as it is transformed,
+    /// except that the start of the output is held back until it is
+    /// known if it needs a `@charset` rule (or a byte order mark, in
+    /// compressed format).
+    /// That is decided by the [`Charset`][crate::output::Charset] of
+    /// the format.
+    /// With the default `Charset::Auto`, the output is held back
+    /// until the first non-ascii item or the end of the input, so
+    /// set the charset to `Always` or `Never` to avoid keeping a
+    /// large plain ascii output in memory.
+    ///
+    /// Plain css `@import`s found before the output is started are
+    /// written first.
+    /// It is an error to find an `@import` after that, since it
+    /// can't be moved to the start of the output.
+    /// Use [`transform`][Self::transform] to get the imports moved
+    /// to the start anyway.
+    ///
+    /// The css of modules is not kept, so a module that is included
+    /// by `meta.load-css` after it is loaded is evaluated again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::{FsContext, SourceFile, SourceName};
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut out = Vec::new();
+    /// FsContext::for_cwd().transform_to_writer(
+    ///     SourceFile::scss_bytes(
+    ///         "a { b: c }\n@import url(foo.css);",
+    ///         SourceName::root("-"),
+    ///     ),
+    ///     &mut out,
+    /// )?;
+    /// assert_eq!(
+    ///     String::from_utf8(out).unwrap(),
+    ///     "@import url(foo.css);\na {\n  b: c;\n}\n",
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn transform_to_writer(
+        self,
+        file: SourceFile,
+        out: impl Write,
+    ) -> Result<(), Error> {
+        let mut head = CssHead::for_writer(out, self.format());
+        let mut body = CssItems::output();
+        self.transform_impl(file, &mut head, &mut body)?;
+        head.finish(body)
+    }
+
+    /// Transform some input source to a css tree.
+    ///
+    /// This is the same transformation as [`transform`][Self::transform]
+    /// does, but the result is a vector of [`css::Item`], which can be
+    /// inspected or modified before it is serialized with
+    /// [`write_css`][crate::output::write_css].
+    ///
+    /// Note that comments are only kept if the format of this
+    /// context is not compressed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::{FsContext, SourceFile, SourceName};
+    /// # use rsass::output::{write_css, Format, Style};
+    /// # use rsass::css::Item;
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let items = FsContext::for_cwd().transform_to_ast(
+    ///     SourceFile::scss_bytes(
+    ///         "a { b { c: d } }",
+    ///         SourceName::root("-"),
+    ///     ),
+    /// )?;
+    /// match &items[..] {
+    ///     [Item::Rule(rule)] => assert_eq!(rule.selectors().to_string(), "a b"),
+    ///     _ => panic!("Expected a single rule, got {:?}", items),
+    /// }
+    /// let format = Format::default().with_style(Style::Compressed).with_precision(5);
+    /// assert_eq!(write_css(&items, format), b"a b{c:d}\n");
+    /// # Ok(()) }
+    /// ```
+    pub fn transform_to_ast(
+        self,
+        file: SourceFile,
+    ) -> Result<Vec<css::Item>, Error> {
+        let mut head = CssHead::new();
+        let mut body = CssItems::output();
+        self.transform_impl(file, &mut head, &mut body)?;
+        Ok(head.combine_final(body))
+    }
+
+    /// Transform some input source to css, and get the resulting
+    /// module.
+    ///
+    /// This is the same transformation as [`transform`][Self::transform]
+    /// does, but the scope of the transformed input is returned
+    /// together with the css output.
+    /// The scope contains the final values of the variables, and the
+    /// functions and mixins, of the input, including the members it
+    /// forwards (as a `@use` of the input would see them).
+    /// Values can be converted to rust types by
+    /// [`FromSassValue`][crate::sass::FromSassValue] (or by
+    /// `Scope::deserialize_variables` if rsass is built with the
+    /// `serde` feature).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::MemoryContext;
+    /// # use rsass::sass::{FromSassValue, Name};
+    /// # use rsass::value::Numeric;
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut context = MemoryContext::new();
+    /// context.insert("_sizes.scss", "$gap: 2px * 4;");
+    /// context.insert("main.scss", "@forward 'sizes';\n$cols: 12;");
+    /// let main = context.load_root("main")?;
+    /// let (css, module) = context.transform_to_module(main)?;
+    /// assert_eq!(css, b"");
+    /// let gap = Numeric::from_sass_value(module.get(&Name::from("gap"))?)
+    ///     .map_err(rsass::Error::S)?;
+    /// assert_eq!(gap.format(Default::default()).to_string(), "8px");
+    /// assert_eq!(i64::from_sass_value(module.get(&Name::from("cols"))?), Ok(12));
+    /// # Ok(()) }
+    /// ```
+    pub fn transform_to_module(
+        self,
+        file: SourceFile,
+    ) -> Result<(Vec<u8>, ScopeRef), Error> {
+        let format = self.format();
+        let mut head = CssHead::new();
+        let mut body = CssItems::output();
+        let scope = self.transform_impl(file, &mut head, &mut body)?;
+        let items = head.combine_final(body);
+        Ok((write_css(&items, format), scope.with_forwarded()))
+    }
+
+    fn transform_impl(
+        mut self,
+        file: SourceFile,
+        head: &mut CssHead,
+        body: &mut CssItems,
+    ) -> Result<ScopeRef, Error> {
         let scope = self
             .scope
             .clone()
             .unwrap_or_else(|| ScopeRef::new_global(Default::default()));
         self.lock_loading(&file, false)?;
-        let mut head = CssHead::new();
-        let mut body = CssBuf::new(scope.get_format());
         handle_parsed(
-            file.parse()?,
-            &mut head,
+            &*self.parse(&file)?,
+            head,
             None,
-            &mut body,
-            scope,
+            body,
+            scope.clone(),
             &mut self,
         )?;
         self.unlock_loading(&file);
-        Ok(head.combine_final(body))
+        Ok(scope)
+    }
+
+    /// Get the output format of this context.
+    fn format(&self) -> Format {
+        self.scope
+            .as_ref()
+            .map(|scope| scope.get_format())
+            .unwrap_or_default()
     }
 
     /// Set the output format for this context.
@@ -185,20 +392,124 @@ impl<AnyLoader: Loader> Context<AnyLoader> {
             .clone()
     }
 
+    /// Add an [`Importer`] to this context.
+    ///
+    /// Importers are tried in the order they are added, before the
+    /// [`Loader`] of this context.
+    pub fn push_importer(&mut self, importer: impl Importer + 'static) {
+        self.importers.push(Arc::new(importer));
+    }
+
+    /// Add an [`Importer`] to this context.
+    ///
+    /// See [`push_importer`][Self::push_importer].
+    pub fn with_importer(
+        mut self,
+        importer: impl Importer + 'static,
+    ) -> Self {
+        self.push_importer(importer);
+        self
+    }
+
+    /// Add a custom built-in module to this context.
+    ///
+    /// The module can be loaded by `@use` or `@forward` with the
+    /// given `name`, e.g. `"app:tokens"`, in the same way as the
+    /// `sass:` modules.
+    /// The first time the module is loaded, `init` is called with an
+    /// empty module scope to define the variables, functions and
+    /// mixins of the module.
+    /// Variables defined with [`Scope::define_default`] can be
+    /// configured with a `with` clause.
+    ///
+    /// [`Scope::define_default`]: crate::Scope::define_default
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::MemoryContext;
+    /// # use rsass::sass::Name;
+    /// # use rsass::value::Numeric;
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut context = MemoryContext::new();
+    /// context.define_module("app:tokens", |module| {
+    ///     module.define_default(
+    ///         Name::from_static("gap"),
+    ///         Numeric::new(4, rsass::value::Unit::Px).into(),
+    ///     )?;
+    ///     Ok(())
+    /// });
+    /// context.insert(
+    ///     "main.scss",
+    ///     "@use 'app:tokens' with ($gap: 1em);\na { gap: tokens.$gap }",
+    /// );
+    /// let main = context.load_root("main")?;
+    /// assert_eq!(context.transform(main)?, b"a {\n  gap: 1em;\n}\n");
+    /// # Ok(()) }
+    /// ```
+    pub fn define_module(
+        &mut self,
+        name: impl Into<String>,
+        init: impl Fn(&ScopeRef) -> Result<(), Error> + Send + Sync + 'static,
+    ) {
+        self.modules.insert(name.into(), Arc::new(init));
+    }
+
+    /// Add a custom built-in module to this context.
+    ///
+    /// See [`define_module`][Self::define_module].
+    pub fn with_module(
+        mut self,
+        name: impl Into<String>,
+        init: impl Fn(&ScopeRef) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.define_module(name, init);
+        self
+    }
+
+    /// Get the init function of a custom built-in module.
+    pub(crate) fn custom_module(&self, name: &str) -> Option<&ModuleInit> {
+        self.modules.get(name).map(AsRef::as_ref)
+    }
+
+    /// Load a root stylesheet through the [`Loader`].
+    ///
+    /// The `url` is resolved the same way as by
+    /// [`find_file`][Self::find_file], but it is an error if no file
+    /// is found.
+    /// The returned file is ready to be [transformed][Self::transform].
+    pub fn load_root(&mut self, url: &str) -> Result<SourceFile, Error> {
+        let file = self
+            .find_file(url, SourceKind::Root)?
+            .ok_or_else(|| LoadError::NotFound(url.into()))?;
+        self.unlock_loading(&file);
+        Ok(file)
+    }
+
     /// Find a file.
     ///
     /// This method handles sass file name resolution, but delegates
     /// the actual checking for existing files to the [`Loader`].
     ///
     /// Given a url like `my/util`, this method will check for
-    /// `my/util`, `my/util.scss`, `my/_util.scss`,
-    /// `my/util/index.scss`, and `my/util/_index.scss`.
-    /// The variants that are not a directory index will also be
-    /// checked for `.css` files (and in the future it may also check
-    /// for `.sass` files if rsass suports that format).
+    /// `my/_util.sass`, `my/util.sass`, `my/_util.scss` and
+    /// `my/util.scss`.
+    /// Only if none of those exist, `my/_util.css` and `my/util.css`
+    /// are checked.
+    /// Only if none of those exist either, the same checks are done
+    /// for `my/util/index` as a directory index file.
+    /// A url with an explicit `.sass`, `.scss` or `.css` suffix is
+    /// checked as is, and as a partial.
+    ///
+    /// It is an error if more than one file is found in any of these
+    /// steps, since it is not clear which one to load.
+    /// Note that the `sass` (indented) format is not supported by
+    /// rsass, but a `.sass` file is still found, and gives an error
+    /// when it is read.
     ///
     /// If `from` indicates that the loading is for an `@import` rule,
-    /// some [extra file names][import-only] are checked.
+    /// [import-only files][import-only] are checked before each
+    /// step.
     ///
     /// The `Context` keeps track of "locked" files (files currently beeing
     /// parsed or transformed into css).
@@ -213,38 +524,37 @@ impl<AnyLoader: Loader> Context<AnyLoader> {
         url: &str,
         from: SourceKind,
     ) -> Result<Option<SourceFile>, Error> {
-        let names: &[&dyn Fn(&str, &str) -> String] = if from.is_import() {
-            &[
-                // base will either be empty or end with a slash.
-                &|base, name| format!("{}{}.import.scss", base, name),
-                &|base, name| format!("{}_{}.import.scss", base, name),
-                &|base, name| format!("{}{}.scss", base, name),
-                &|base, name| format!("{}_{}.scss", base, name),
-                &|base, name| format!("{}{}/index.import.scss", base, name),
-                &|base, name| format!("{}{}/_index.import.scss", base, name),
-                &|base, name| format!("{}{}/index.scss", base, name),
-                &|base, name| format!("{}{}/_index.scss", base, name),
-                &|base, name| format!("{}{}.css", base, name),
-                &|base, name| format!("{}_{}.css", base, name),
-            ]
-        } else {
-            &[
-                // base will either be empty or end with a slash.
-                &|base, name| format!("{}{}.scss", base, name),
-                &|base, name| format!("{}_{}.scss", base, name),
-                &|base, name| format!("{}{}/index.scss", base, name),
-                &|base, name| format!("{}{}/_index.scss", base, name),
-                &|base, name| format!("{}{}.css", base, name),
-                &|base, name| format!("{}_{}.css", base, name),
-            ]
-        };
+        let is_module = !from.is_import();
+        let base = from.next().map(|pos| pos.file_url());
+        for importer in &self.importers {
+            if let Some(imported) = importer.import(url, base)? {
+                let (path, format, data) = imported.into_parts();
+                let source = from.url(&path);
+                let file = SourceFile::from_bytes(data, format, source);
+                self.lock_loading(&file, is_module)?;
+                return Ok(Some(file));
+            }
+        }
         // Note: Should a "full stack" of bases be used here?
         // Or is this fine?
         let url = relative(&from, url);
-        if let Some((path, mut file)) = self.do_find_file(&url, names)? {
-            let is_module = !from.is_import();
+        let url = normalize(&url);
+        let found = match self.do_find_file(&url, from.is_import()) {
+            Ok(found) => found,
+            Err(LoadError::Ambiguous(found)) => {
+                return Err(match from.next() {
+                    Some(pos) => {
+                        Invalid::AmbiguousImport(found).at(pos.clone())
+                    }
+                    None => LoadError::Ambiguous(found).into(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if let Some((path, canonical, mut file)) = found {
             let source = from.url(&path);
-            let file = SourceFile::read(&mut file, source)?;
+            let file = SourceFile::read(&mut file, source)?
+                .with_canonical_url(canonical);
             self.lock_loading(&file, is_module)?;
             Ok(Some(file))
         } else {
@@ -252,33 +562,79 @@ impl<AnyLoader: Loader> Context<AnyLoader> {
         }
     }
 
-    /// Find a file in a given filecontext matching a url over a set of
-    /// name rules.
+    /// Find exactly one file for a url, with the sass name resolution
+    /// rules.
     fn do_find_file(
         &self,
         url: &str,
-        names: &[&dyn Fn(&str, &str) -> String],
-    ) -> Result<Option<(String, AnyLoader::File)>, LoadError> {
-        if url.ends_with(".css")
-            || url.ends_with(".sass")
-            || url.ends_with(".scss")
+        import: bool,
+    ) -> Result<Option<Found<AnyLoader::File>>, LoadError> {
+        let (base, name) = split_name(url);
+        if let Some(ext) = [".sass", ".scss", ".css"]
+            .iter()
+            .find(|ext| name.ends_with(*ext))
         {
-            self.loader
-                .find_file(url)
-                .map(|file| file.map(|file| (url.into(), file)))
-        } else {
-            let (base, name) = url
-                .rfind('/')
-                .map(|p| url.split_at(p + 1))
-                .unwrap_or(("", url));
-
-            for name in names.iter().map(|f| f(base, name)) {
-                if let Some(result) = self.loader.find_file(&name)? {
-                    return Ok(Some((name, result)));
+            if import {
+                let stem = &name[..name.len() - ext.len()];
+                let name = format!("{}.import{}", stem, ext);
+                if let Some(found) = one(self.try_path(base, &name)?)? {
+                    return Ok(Some(found));
                 }
             }
-            Ok(None)
+            return one(self.try_path(base, name)?);
         }
+        if import {
+            let name = format!("{}.import", name);
+            if let Some(found) = one(self.try_extensions(base, &name)?)? {
+                return Ok(Some(found));
+            }
+        }
+        if let Some(found) = one(self.try_extensions(base, name)?)? {
+            return Ok(Some(found));
+        }
+        let dir = format!("{}{}/", base, name);
+        if import {
+            if let Some(found) =
+                one(self.try_extensions(&dir, "index.import")?)?
+            {
+                return Ok(Some(found));
+            }
+        }
+        one(self.try_extensions(&dir, "index")?)
+    }
+
+    /// Find the existing files for a name with the `.sass` or `.scss`
+    /// suffix, or if there is none, with the `.css` suffix.
+    fn try_extensions(
+        &self,
+        base: &str,
+        name: &str,
+    ) -> Result<Vec<Found<AnyLoader::File>>, LoadError> {
+        let mut found = self.try_path(base, &format!("{}.sass", name))?;
+        found.extend(self.try_path(base, &format!("{}.scss", name))?);
+        if found.is_empty() {
+            found = self.try_path(base, &format!("{}.css", name))?;
+        }
+        Ok(found)
+    }
+
+    /// Find the existing files for a name, as a partial and as is.
+    fn try_path(
+        &self,
+        base: &str,
+        name: &str,
+    ) -> Result<Vec<Found<AnyLoader::File>>, LoadError> {
+        let mut found = Vec::new();
+        for name in
+            &[format!("{}_{}", base, name), format!("{}{}", base, name)]
+        {
+            if let Some((canonical, file)) =
+                self.loader.find_canonical(name)?
+            {
+                found.push((name.clone(), canonical, file));
+            }
+        }
+        Ok(found)
     }
 
     pub(crate) fn lock_loading(
@@ -286,7 +642,7 @@ impl<AnyLoader: Loader> Context<AnyLoader> {
         file: &SourceFile,
         as_module: bool,
     ) -> Result<(), Error> {
-        let name = file.source().name();
+        let name = file.canonical_url();
         let pos = &file.source().imported;
         if let Some(old) = self.loading.insert(name.into(), pos.clone()) {
             Err(Error::ImportLoop(
@@ -299,37 +655,157 @@ impl<AnyLoader: Loader> Context<AnyLoader> {
         }
     }
 
+    /// Use a shared cache for parsed files.
+    pub(crate) fn with_parse_cache(mut self, cache: Arc<ParseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Add importers and custom modules shared with a `Compiler`.
+    pub(crate) fn with_shared(
+        mut self,
+        importers: &[Arc<dyn Importer>],
+        modules: &BTreeMap<String, Arc<ModuleInit>>,
+    ) -> Self {
+        self.importers.extend(importers.iter().cloned());
+        self.modules.extend(
+            modules
+                .iter()
+                .map(|(name, init)| (name.clone(), init.clone())),
+        );
+        self
+    }
+
+    /// Parse a file, or get it from the parse cache, if any.
+    pub(crate) fn parse(
+        &self,
+        file: &SourceFile,
+    ) -> Result<Arc<Parsed>, Error> {
+        match &self.cache {
+            Some(cache) => cache.parse(file),
+            None => Ok(Arc::new(file.parse()?)),
+        }
+    }
+
     /// Unlock a file that is locked for input processing.
     ///
     /// The lock exists to break circular dependency chains.
     /// Each file that is locked (by [`Self::find_file`]) needs to be unlocked
     /// when processing of it is done.
     pub fn unlock_loading(&mut self, file: &SourceFile) {
-        self.loading.remove(file.path());
+        self.loading.remove(file.canonical_url());
     }
 }
 
 /// Make a url relative to a given base.
+///
+/// A url with a scheme (like `pkg:tokens`) is absolute and returned
+/// as is.
+/// Otherwise, it is resolved in the directory of the base, keeping
+/// the scheme of the base, if any.
 fn relative<'a>(base: &SourceKind, url: &'a str) -> Cow<'a, str> {
+    if split_scheme(url).is_some() {
+        return url.into();
+    }
     base.next()
-        .map(|pos| pos.file_url())
-        .and_then(|base| {
-            base.rfind('/')
-                .map(|p| base.split_at(p + 1).0)
-                .map(|base| format!("{}{}", base, url).into())
-        })
+        .map(|pos| split_name(pos.file_url()).0)
+        .filter(|base| !base.is_empty())
+        .map(|base| format!("{}{}", base, url).into())
         .unwrap_or_else(|| url.into())
 }
 
+/// A found file: the name, the canonical url and the file itself.
+type Found<File> = (String, String, File);
+
+/// Get the only found file, if any.
+///
+/// It is an error if more than one file is found.
+fn one<File>(
+    mut found: Vec<Found<File>>,
+) -> Result<Option<Found<File>>, LoadError> {
+    if found.len() > 1 {
+        Err(LoadError::Ambiguous(
+            found
+                .into_iter()
+                .map(|(_, canonical, _)| canonical)
+                .collect(),
+        ))
+    } else {
+        Ok(found.pop())
+    }
+}
+
+/// Normalize `.` and `..` segments in a url.
+///
+/// A `..` segment removes the segment before it, if any.
+/// Leading `..` segments of a relative url are kept, since they may
+/// be meaningful to the loader.
+fn normalize(url: &str) -> Cow<'_, str> {
+    let (scheme, path) = split_scheme(url).unwrap_or(("", url));
+    if !path.split('/').any(|s| s == "." || s == "..") {
+        return url.into();
+    }
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => (),
+            ".." => match segments.last() {
+                Some(&"..") | None => segments.push(".."),
+                Some(&"") if segments.len() == 1 => (),
+                Some(_) => {
+                    segments.pop();
+                }
+            },
+            segment => segments.push(segment),
+        }
+    }
+    if segments == [""] {
+        segments.push("");
+    }
+    format!("{}{}", scheme, segments.join("/")).into()
+}
+
+/// Split a url in a base (the scheme and directory part, either empty
+/// or ending with a slash or colon) and a name.
+fn split_name(url: &str) -> (&str, &str) {
+    url.rfind('/')
+        .map(|p| p + 1)
+        .or_else(|| split_scheme(url).map(|(scheme, _)| scheme.len()))
+        .map(|p| url.split_at(p))
+        .unwrap_or(("", url))
+}
+
+/// Split a url in a scheme (including the colon) and the rest.
+///
+/// Returns None if the url has no scheme.
+/// Single-letter schemes are not considered, to avoid mistaking a
+/// windows drive letter for a scheme.
+fn split_scheme(url: &str) -> Option<(&str, &str)> {
+    let end = url.find(':')?;
+    let scheme = &url[..end];
+    let mut chars = scheme.chars();
+    if scheme.len() > 1
+        && chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    {
+        Some(url.split_at(end + 1))
+    } else {
+        None
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Context<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Context")
             .field("loader", &self.loader)
+            .field("importers", &self.importers)
+            .field("modules", &self.modules.keys())
             .field(
                 "scope",
                 &if self.scope.is_some() { "loaded" } else { "no" },
             )
             .field("locked", &self.loading.keys())
+            .field("cache", &self.cache)
             .finish()
     }
 }
@@ -0,0 +1,180 @@
+use super::{LoadError, Loader};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A [`Loader`] for stylesheets embedded in the program.
+///
+/// The files are a static table of names and contents, normally
+/// generated by a build script with [`EmbeddedLoader::embed_dir`] and
+/// included with the [`include_scss_dir!`][crate::include_scss_dir]
+/// macro.
+/// The name resolution of
+/// [`Context::find_file`][super::Context::find_file] (partials,
+/// directory index files, etc) works just as for files on disk.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{Context, EmbeddedLoader};
+/// static FILES: &[(&str, &[u8])] = &[
+///     ("main.scss", b"@use 'lib/colors';\na { b: colors.$c }"),
+///     ("lib/colors/_index.scss", b"$c: red;"),
+/// ];
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut context = Context::for_loader(EmbeddedLoader::new(FILES));
+/// let main = context.load_root("main")?;
+/// assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EmbeddedLoader {
+    files: &'static [(&'static str, &'static [u8])],
+}
+
+impl EmbeddedLoader {
+    /// Create a loader for a table of file names and contents.
+    pub const fn new(
+        files: &'static [(&'static str, &'static [u8])],
+    ) -> Self {
+        EmbeddedLoader { files }
+    }
+
+    /// Get the content of a file.
+    pub fn get(&self, name: &str) -> Option<&'static [u8]> {
+        self.files
+            .iter()
+            .find(|(file, _)| *file == name)
+            .map(|(_, data)| *data)
+    }
+
+    /// Generate a table of the stylesheets in a directory, for use in a
+    /// `build.rs` script.
+    ///
+    /// All `.scss` and `.css` files in `dir` (recursively) are
+    /// included, named by their path relative to `dir`.
+    /// The table is written to the file `name` in the `OUT_DIR` of
+    /// the build, and can be used with
+    /// [`include_scss_dir!`][crate::include_scss_dir].
+    /// If `dir` is relative, it will be resolved from the directory
+    /// containing the manifest of your package.
+    /// A `cargo:rerun-if-changed` message is printed for `dir`.
+    ///
+    /// In `build.rs`:
+    ///
+    /// ```no_run
+    /// # use rsass::input::EmbeddedLoader;
+    /// # fn main() -> Result<(), rsass::input::LoadError> {
+    /// EmbeddedLoader::embed_dir("scss".as_ref(), "scss.rs")?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// And in the program:
+    ///
+    /// ```no_run
+    /// use rsass::include_scss_dir;
+    /// use rsass::input::Context;
+    /// # // The real macro needs the `OUT_DIR` of a build script.
+    /// # macro_rules! include_scss_dir {
+    /// #     ($name:expr) => { rsass::input::EmbeddedLoader::new(&[]) };
+    /// # }
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut context = Context::for_loader(include_scss_dir!("scss.rs"));
+    /// let main = context.load_root("main")?;
+    /// let css = context.transform(main)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn embed_dir(dir: &Path, name: &str) -> Result<(), LoadError> {
+        let dir = if dir.is_absolute() {
+            dir.into()
+        } else {
+            env_path("CARGO_MANIFEST_DIR")?.join(dir)
+        };
+        let out = env_path("OUT_DIR")?.join(name);
+        println!("cargo:rerun-if-changed={}", dir.display());
+        Self::write_table(&dir, &out)
+    }
+
+    /// Write a table of the stylesheets in `dir` to the file `out`.
+    ///
+    /// This is the part of [`embed_dir`][Self::embed_dir] that does
+    /// not depend on being called from cargo.
+    /// The table is a rust expression that refers to the files by
+    /// absolute path, so it should be used from the same machine.
+    pub fn write_table(dir: &Path, out: &Path) -> Result<(), LoadError> {
+        let mut files = Vec::new();
+        collect_files(dir, &mut files)?;
+        files.sort();
+        let mut table = String::from("&[\n");
+        for path in files {
+            let name = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .iter()
+                .map(|part| part.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let path = path.canonicalize().unwrap_or(path);
+            table.push_str(&format!(
+                "    ({:?}, include_bytes!({:?})),\n",
+                name,
+                path.display().to_string(),
+            ));
+        }
+        table.push_str("]\n");
+        let output_error = |e| LoadError::Input(out.display().to_string(), e);
+        std::fs::File::create(out)
+            .and_then(|mut f| f.write_all(table.as_bytes()))
+            .map_err(output_error)
+    }
+}
+
+impl Loader for EmbeddedLoader {
+    type File = &'static [u8];
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.get(url))
+    }
+}
+
+/// Include a table of stylesheets generated by
+/// [`EmbeddedLoader::embed_dir`] in a build script.
+///
+/// The argument is the name of the generated file in `OUT_DIR`.
+/// The result is an [`EmbeddedLoader`][crate::input::EmbeddedLoader].
+#[macro_export]
+macro_rules! include_scss_dir {
+    ($name:expr) => {
+        $crate::input::EmbeddedLoader::new(include!(concat!(
+            env!("OUT_DIR"),
+            "/",
+            $name
+        )))
+    };
+}
+
+/// Find all scss and css files in `dir`, recursively.
+fn collect_files(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), LoadError> {
+    let input_error = |e| LoadError::Input(dir.display().to_string(), e);
+    for entry in std::fs::read_dir(dir).map_err(input_error)? {
+        let path = entry.map_err(input_error)?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path
+            .extension()
+            .map_or(false, |ext| ext == "scss" || ext == "css")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Get a path from an environment variable set by cargo.
+fn env_path(var: &str) -> Result<PathBuf, LoadError> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .ok_or(LoadError::NotCalledFromCargo)
+}
@@ -17,6 +17,13 @@ impl FsLoader {
         }
     }
 
+    /// Create a new FsLoader that only finds files in `dir`.
+    pub(crate) fn for_dir(dir: &Path) -> Self {
+        Self {
+            path: vec![dir.into()],
+        }
+    }
+
     /// Add a path to search for files.
     pub fn push_path(&mut self, path: &Path) {
         self.path.push(path.into());
@@ -26,6 +33,7 @@ impl FsLoader {
     pub fn for_path(path: &Path) -> Result<(Self, SourceFile), LoadError> {
         let mut f = std::fs::File::open(&path)
             .map_err(|e| LoadError::Input(path.display().to_string(), e))?;
+        let canonical = canonical_path(path);
         let (path, name) = if let Some(base) = path.parent() {
             (vec![base.to_path_buf()], path.strip_prefix(base).unwrap())
         } else {
@@ -33,7 +41,8 @@ impl FsLoader {
         };
         let loader = Self { path };
         let source = SourceName::root(name.display().to_string());
-        let source = SourceFile::read(&mut f, source)?;
+        let source =
+            SourceFile::read(&mut f, source)?.with_canonical_url(canonical);
         Ok((loader, source))
     }
 }
@@ -42,6 +51,14 @@ impl Loader for FsLoader {
     type File = std::fs::File;
 
     fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.find_canonical(url)?.map(|(_, file)| file))
+    }
+
+    /// Find a file, using the absolute path as the canonical url.
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
         if !url.is_empty() {
             for base in &self.path {
                 let full = base.join(url);
@@ -51,7 +68,7 @@ impl Loader for FsLoader {
                         .map_err(|e| {
                             LoadError::Input(full.display().to_string(), e)
                         })
-                        .map(Some);
+                        .map(|file| Some((canonical_path(&full), file)));
                 }
                 tracing::trace!(?full, "Not found");
             }
@@ -59,3 +76,16 @@ impl Loader for FsLoader {
         Ok(None)
     }
 }
+
+/// Get the canonical url for an existing file.
+///
+/// This is the absolute path of the file, with any symbolic links
+/// resolved.
+/// If the path can not be canonicalized, it is used as is.
+pub(crate) fn canonical_path(path: &Path) -> String {
+    path.canonicalize()
+        .as_deref()
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
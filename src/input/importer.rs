@@ -0,0 +1,109 @@
+use super::{LoadError, SourceFormat};
+use std::fmt;
+
+/// An importer provides stylesheets for urls, e.g. by generating them.
+///
+/// Importers are added to a [`Context`][super::Context] with
+/// [`push_importer`][super::Context::push_importer], and are tried in
+/// order before the [`Loader`][super::Loader] of the context.
+///
+/// Unlike a `Loader`, an importer gets the url exactly as written in
+/// the `@use`, `@forward` or `@import` rule, and returns the content
+/// of the stylesheet together with its format and a canonical url.
+/// The canonical url is used for module deduplication and loop
+/// detection, and as the base of relative urls in the returned
+/// stylesheet.
+///
+/// Importers must be `Send + Sync`, so they can be shared by the
+/// threads of a [`Compiler`][super::Compiler].
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{Imported, Importer, LoadError, MemoryContext};
+/// #[derive(Debug)]
+/// struct Tokens;
+///
+/// impl Importer for Tokens {
+///     fn import(
+///         &self,
+///         url: &str,
+///         _base: Option<&str>,
+///     ) -> Result<Option<Imported>, LoadError> {
+///         if url == "tokens" {
+///             Ok(Some(Imported::scss("gen:tokens.scss", "$primary: red;")))
+///         } else {
+///             Ok(None)
+///         }
+///     }
+/// }
+///
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut context = MemoryContext::new().with_importer(Tokens);
+/// context.insert("main.scss", "@use 'tokens';\na { b: tokens.$primary }");
+/// let main = context.load_root("main")?;
+/// assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+/// # Ok(()) }
+/// ```
+pub trait Importer: fmt::Debug + Send + Sync {
+    /// Import a stylesheet for `url`.
+    ///
+    /// The `base` is the url of the importing stylesheet, if any.
+    /// This is the name the stylesheet was loaded by (as in its
+    /// [`SourceName`][super::SourceName]), e.g. `sub/_x.scss` for a
+    /// file found by a [`Loader`][super::Loader], which is not
+    /// necessarily the canonical url of the file.
+    /// For a stylesheet provided by an importer, it is the url given
+    /// in the [`Imported`].
+    ///
+    /// If this importer does not handle the url, `Ok(None)` should be
+    /// returned, so the next importer (or the loader) is tried.
+    fn import(
+        &self,
+        url: &str,
+        base: Option<&str>,
+    ) -> Result<Option<Imported>, LoadError>;
+}
+
+/// A stylesheet returned by an [`Importer`].
+#[derive(Clone, Debug)]
+pub struct Imported {
+    url: String,
+    format: SourceFormat,
+    data: Vec<u8>,
+}
+
+impl Imported {
+    /// Create an imported stylesheet with a canonical url, a format
+    /// and the content.
+    pub fn new(
+        url: impl Into<String>,
+        format: SourceFormat,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        Imported {
+            url: url.into(),
+            format,
+            data: data.into(),
+        }
+    }
+    /// Create an imported stylesheet in the scss format.
+    pub fn scss(url: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self::new(url, SourceFormat::Scss, data)
+    }
+    /// Create an imported stylesheet in the css format.
+    pub fn css(url: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self::new(url, SourceFormat::Css, data)
+    }
+    /// The canonical url of this stylesheet.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// The format of this stylesheet.
+    pub fn format(&self) -> SourceFormat {
+        self.format
+    }
+    pub(crate) fn into_parts(self) -> (String, SourceFormat, Vec<u8>) {
+        (self.url, self.format, self.data)
+    }
+}
@@ -2,17 +2,22 @@ use std::fmt;
 
 /// A file context manages finding and loading files.
 ///
+/// Rsass provides [`FsLoader`][super::FsLoader],
+/// [`CargoLoader`][super::CargoLoader] and
+/// [`MemoryLoader`][super::MemoryLoader], but it is also possible to
+/// implement a custom loader.
+///
 /// # Example
 /// ```
 /// use std::collections::HashMap;
 /// use rsass::input::{Loader, LoadError};
 ///
 /// #[derive(Clone, Debug)]
-/// struct MemoryLoader<'a> {
+/// struct StaticLoader<'a> {
 ///     files: HashMap<String, &'a[u8]>,
 /// }
 ///
-/// impl<'a> Loader for MemoryLoader<'a> {
+/// impl<'a> Loader for StaticLoader<'a> {
 ///     type File = &'a [u8];
 ///
 ///     fn find_file(&self, name: &str) -> Result<Option<Self::File>, LoadError> {
@@ -41,6 +46,43 @@ pub trait Loader: Sized + std::fmt::Debug {
     /// url instead of by path to ensure universal compatibility of style sheets.
     /// This effectively mandates the use of forward slashes on all platforms.
     fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError>;
+
+    /// Find a file, and get its canonical url.
+    ///
+    /// The canonical url identifies the file, and is used for module
+    /// deduplication and loop detection, so two urls that refer to the
+    /// same file should give the same canonical url.
+    /// Note that `.` and `..` segments are already normalized in the
+    /// `url` given to this method.
+    ///
+    /// The default implementation calls [`find_file`][Self::find_file]
+    /// and uses the url itself as the canonical url, which is fine for
+    /// a loader where each file has only one name.
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
+        Ok(self.find_file(url)?.map(|file| (url.into(), file)))
+    }
+}
+
+/// A reference to a loader is also a loader.
+///
+/// This makes it possible to use a shared loader for many contexts,
+/// as the [`Compiler`][super::Compiler] does.
+impl<L: Loader> Loader for &L {
+    type File = L::File;
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        (*self).find_file(url)
+    }
+
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
+        (*self).find_canonical(url)
+    }
 }
 
 /// An error loading a file.
@@ -52,6 +94,12 @@ pub enum LoadError {
     UnknownFormat(String),
     /// Expected a cargo environment, but none found.
     NotCalledFromCargo,
+    /// No stylesheet was found for the given url.
+    NotFound(String),
+    /// More than one file was found for a url.
+    ///
+    /// Contains the canonical urls of the files found.
+    Ambiguous(Vec<String>),
 }
 impl std::error::Error for LoadError {}
 
@@ -73,6 +121,16 @@ impl fmt::Debug for LoadError {
             LoadError::NotCalledFromCargo => {
                 write!(out, "Expected a cargo environment, but none found.")
             }
+            LoadError::NotFound(name) => {
+                write!(out, "No stylesheet found for {:?}.", name)
+            }
+            LoadError::Ambiguous(found) => {
+                write!(out, "It's not clear which file to import. Found:")?;
+                for name in found {
+                    write!(out, "\n  {}", name)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,64 @@
+use super::{LoadError, Loader};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+/// A [`Loader`] for files kept in memory.
+///
+/// Files are stored by name, and the name resolution of
+/// [`Context::find_file`][super::Context::find_file] (partials,
+/// directory index files, etc) works just as for files on disk.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::MemoryContext;
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut context = MemoryContext::new();
+/// context.insert("main.scss", "@use 'lib/colors';\na { b: colors.$c }");
+/// context.insert("lib/colors/_index.scss", "$c: red;");
+/// let main = context.load_root("main")?;
+/// assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryLoader {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryLoader {
+    /// Create a new loader without any files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, or update it if it already exists.
+    ///
+    /// Returns the previous content of the file, if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        self.files.insert(name.into(), data.into())
+    }
+
+    /// Remove a file.
+    ///
+    /// Returns the content of the removed file, if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.files.remove(name)
+    }
+
+    /// Get the content of a file.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.files.get(name).map(AsRef::as_ref)
+    }
+}
+
+impl Loader for MemoryLoader {
+    type File = Cursor<Vec<u8>>;
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.files.get(url).map(|data| Cursor::new(data.clone())))
+    }
+}
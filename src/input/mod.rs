@@ -1,14 +1,37 @@
 //! Finding and loading files.
+#[cfg(feature = "archive")]
+mod archiveloader;
 mod cargoloader;
+mod compiledir;
+mod compiler;
 mod context;
+mod embeddedloader;
 mod fsloader;
+mod importer;
 mod loader;
+mod memoryloader;
+#[cfg(feature = "node")]
+mod nodeimporter;
+mod routingloader;
 mod sourcefile;
 mod sourcename;
+mod trackingloader;
 
+#[cfg(feature = "archive")]
+pub use archiveloader::ArchiveLoader;
 pub use cargoloader::CargoLoader;
-pub use context::{CargoContext, Context, FsContext};
+pub use compiledir::{CompileDir, CompiledFile};
+pub use compiler::Compiler;
+pub(crate) use context::ModuleInit;
+pub use context::{CargoContext, Context, FsContext, MemoryContext};
+pub use embeddedloader::EmbeddedLoader;
 pub use fsloader::FsLoader;
+pub use importer::{Imported, Importer};
 pub use loader::{LoadError, Loader};
-pub use sourcefile::{Parsed, SourceFile};
+pub use memoryloader::MemoryLoader;
+#[cfg(feature = "node")]
+pub use nodeimporter::NodePackageImporter;
+pub use routingloader::RoutingLoader;
+pub use sourcefile::{Parsed, SourceFile, SourceFormat};
 pub use sourcename::{SourceKind, SourceName};
+pub use trackingloader::{LoadedUrls, TrackingLoader};
@@ -0,0 +1,240 @@
+use super::{Imported, Importer, LoadError, SourceFormat};
+use serde_json::Value as Json;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+
+/// An [`Importer`] for sass in node packages.
+///
+/// This handles urls like `pkg:bootstrap` and `~bootstrap/scss/grid`,
+/// by walking up from the directory of the importing file to find a
+/// `node_modules` directory containing the package, so stylesheets
+/// can be compiled from a vendored `node_modules` directory without
+/// node installed.
+///
+/// For a url without a subpath, the `exports` field of the
+/// `package.json` is checked first (using the `sass`, `style` or
+/// `default` condition), then the `sass` and `style` fields, and
+/// finally an `index` file in the package directory.
+/// A subpath is resolved through `exports` if possible, otherwise as
+/// a path in the package directory.
+/// The usual sass name variants (partials, `.css` files and
+/// directory index files) are checked.
+///
+/// This is an importer rather than a [`Loader`][super::Loader], since
+/// it needs the url of the importing file.
+/// The canonical url of a found file is its absolute path, so
+/// relative urls in a package stylesheet are loaded by the loader of
+/// the context, which should be a file system loader (e.g. the one in
+/// a [`FsContext`][super::FsContext]).
+///
+/// This importer requires the `node` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use rsass::input::{FsContext, NodePackageImporter};
+/// # fn main() -> Result<(), rsass::Error> {
+/// let (context, main) = FsContext::for_path("src/style.scss".as_ref())?;
+/// let context = context.with_importer(NodePackageImporter::new("src"));
+/// let css = context.transform(main)?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct NodePackageImporter {
+    root: PathBuf,
+}
+
+impl NodePackageImporter {
+    /// Create a new importer.
+    ///
+    /// The `root` is the directory that urls of importing files are
+    /// relative to, i.e. the base directory of the loader used.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        NodePackageImporter { root: root.into() }
+    }
+
+    /// Find a stylesheet in a package directory.
+    fn resolve(
+        &self,
+        package: &Path,
+        subpath: &str,
+    ) -> Result<Option<PathBuf>, LoadError> {
+        let manifest = read_manifest(package)?;
+        if let Some(exports) = manifest.get("exports") {
+            for candidate in variants(subpath) {
+                if let Some(found) = exports_target(exports, &candidate)
+                    .and_then(|target| find_file(&package.join(target)))
+                {
+                    return Ok(Some(found));
+                }
+            }
+        }
+        if subpath.is_empty() {
+            for field in &["sass", "style"] {
+                if let Some(found) = manifest
+                    .get(field)
+                    .and_then(Json::as_str)
+                    .and_then(|path| find_file(&package.join(path)))
+                {
+                    return Ok(Some(found));
+                }
+            }
+            Ok(find_file(&package.join("index")))
+        } else {
+            Ok(find_file(&package.join(subpath)))
+        }
+    }
+}
+
+impl Importer for NodePackageImporter {
+    fn import(
+        &self,
+        url: &str,
+        base: Option<&str>,
+    ) -> Result<Option<Imported>, LoadError> {
+        let spec = match url.strip_prefix("pkg:") {
+            Some(spec) => spec,
+            None => match url.strip_prefix('~') {
+                Some(spec) => spec,
+                None => return Ok(None),
+            },
+        };
+        let (name, subpath) = split_package(spec);
+        let start = base
+            .and_then(|base| self.root.join(base).parent().map(PathBuf::from))
+            .unwrap_or_else(|| self.root.clone());
+        let start = start.canonicalize().unwrap_or(start);
+        for dir in start.ancestors() {
+            let package = dir.join("node_modules").join(name);
+            if package.is_dir() {
+                tracing::debug!(?package, subpath, "found package");
+                return match self.resolve(&package, subpath)? {
+                    Some(path) => {
+                        let url = path.to_string_lossy().replace('\\', "/");
+                        let format = SourceFormat::try_from(url.as_ref())?;
+                        let data = std::fs::read(&path)
+                            .map_err(|e| LoadError::Input(url.clone(), e))?;
+                        Ok(Some(Imported::new(url, format, data)))
+                    }
+                    None => Ok(None),
+                };
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Split a package url in the package name and the subpath.
+///
+/// The name of a scoped package contains a slash.
+fn split_package(spec: &str) -> (&str, &str) {
+    let skip = if spec.starts_with('@') {
+        spec.find('/').map(|p| p + 1).unwrap_or(0)
+    } else {
+        0
+    };
+    match spec[skip..].find('/') {
+        Some(p) => (&spec[..skip + p], &spec[skip + p + 1..]),
+        None => (spec, ""),
+    }
+}
+
+/// Read the `package.json` of a package, if it exists.
+fn read_manifest(package: &Path) -> Result<Json, LoadError> {
+    let path = package.join("package.json");
+    if !path.is_file() {
+        return Ok(Json::Null);
+    }
+    let name = path.display().to_string();
+    let data = std::fs::read(&path)
+        .map_err(|e| LoadError::Input(name.clone(), e))?;
+    serde_json::from_slice(&data)
+        .map_err(|e| LoadError::Input(name, std::io::Error::from(e)))
+}
+
+/// The subpath and its sass name variants, to look for in `exports`.
+fn variants(subpath: &str) -> Vec<String> {
+    if subpath.is_empty() {
+        return vec![String::new()];
+    }
+    let (dir, name) = subpath
+        .rfind('/')
+        .map(|p| subpath.split_at(p + 1))
+        .unwrap_or(("", subpath));
+    vec![
+        subpath.to_string(),
+        format!("{}{}.scss", dir, name),
+        format!("{}_{}.scss", dir, name),
+        format!("{}{}/index.scss", dir, name),
+        format!("{}{}/_index.scss", dir, name),
+        format!("{}{}.css", dir, name),
+    ]
+}
+
+/// Find the target for a subpath in the `exports` of a package.
+fn exports_target(exports: &Json, subpath: &str) -> Option<String> {
+    let is_subpath_map = exports
+        .as_object()
+        .map_or(false, |map| map.keys().any(|key| key.starts_with('.')));
+    if !is_subpath_map {
+        return if subpath.is_empty() {
+            condition_target(exports).map(String::from)
+        } else {
+            None
+        };
+    }
+    let map = exports.as_object()?;
+    let key = if subpath.is_empty() {
+        ".".to_string()
+    } else {
+        format!("./{}", subpath)
+    };
+    if let Some(target) = map.get(&key) {
+        return condition_target(target).map(String::from);
+    }
+    // Subpath patterns, like "./scss/*": "./src/*.scss"
+    map.iter().find_map(|(pattern, target)| {
+        let star = pattern.find('*')?;
+        let (prefix, suffix) = (&pattern[..star], &pattern[star + 1..]);
+        let star = key.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        let target = condition_target(target)?;
+        Some(target.replacen('*', star, 1))
+    })
+}
+
+/// Get the target from an `exports` value, preferring sass conditions.
+fn condition_target(target: &Json) -> Option<&str> {
+    match target {
+        Json::String(path) => Some(path),
+        Json::Object(conditions) => ["sass", "style", "default"]
+            .iter()
+            .find_map(|c| conditions.get(*c).and_then(condition_target)),
+        Json::Array(alternatives) => {
+            alternatives.iter().find_map(condition_target)
+        }
+        _ => None,
+    }
+}
+
+/// Find an existing stylesheet for a path, trying the usual sass
+/// name variants if the path has no known suffix.
+fn find_file(path: &Path) -> Option<PathBuf> {
+    let has_suffix = path
+        .extension()
+        .map_or(false, |ext| ext == "scss" || ext == "css");
+    if has_suffix {
+        return Some(path.to_path_buf()).filter(|path| path.is_file());
+    }
+    let dir = path.parent()?;
+    let name = path.file_name()?.to_str()?;
+    [
+        dir.join(format!("{}.scss", name)),
+        dir.join(format!("_{}.scss", name)),
+        path.join("index.scss"),
+        path.join("_index.scss"),
+        dir.join(format!("{}.css", name)),
+        dir.join(format!("_{}.css", name)),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
@@ -0,0 +1,134 @@
+use super::{LoadError, Loader};
+use std::fmt;
+use std::io::Read;
+
+/// A [`Loader`] that routes urls to other loaders by prefix.
+///
+/// Routes are checked in the order they are added.
+/// For each route where the url starts with the prefix, the prefix
+/// is removed and the rest of the url is given to the loader of that
+/// route.
+/// If that loader does not find the file, the next matching route is
+/// checked.
+///
+/// A prefix is typically a url scheme, like `pkg:`.
+/// Relative urls in a file loaded with a scheme are resolved with the
+/// same scheme, so files in a package can use each other.
+/// A route with an empty prefix matches all urls, see
+/// [`with_fallback`][Self::with_fallback].
+///
+/// The loaders of the routes must be `Send + Sync`, so a
+/// `RoutingLoader` can be used by a [`Compiler`][super::Compiler].
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{Context, MemoryLoader, RoutingLoader};
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut tokens = MemoryLoader::new();
+/// tokens.insert("tokens/_index.scss", "@forward 'colors';");
+/// tokens.insert("tokens/_colors.scss", "$primary: red;");
+/// let mut local = MemoryLoader::new();
+/// local.insert(
+///     "main.scss",
+///     "@use 'pkg:tokens';\na { b: tokens.$primary }",
+/// );
+/// let loader = RoutingLoader::new()
+///     .with_route("pkg:", tokens)
+///     .with_fallback(local);
+/// let mut context = Context::for_loader(loader);
+/// let main = context.load_root("main")?;
+/// assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default)]
+pub struct RoutingLoader {
+    routes: Vec<(String, Box<dyn DynLoader>)>,
+}
+
+impl RoutingLoader {
+    /// Create a new loader without any routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route for urls starting with `prefix`.
+    pub fn push_route<L>(&mut self, prefix: impl Into<String>, loader: L)
+    where
+        L: Loader + Send + Sync + 'static,
+    {
+        self.routes.push((prefix.into(), Box::new(loader)));
+    }
+
+    /// Add a route for urls starting with `prefix`.
+    pub fn with_route<L>(
+        mut self,
+        prefix: impl Into<String>,
+        loader: L,
+    ) -> Self
+    where
+        L: Loader + Send + Sync + 'static,
+    {
+        self.push_route(prefix, loader);
+        self
+    }
+
+    /// Add a route for all urls.
+    ///
+    /// This is the same as a route with an empty prefix.
+    pub fn with_fallback<L>(self, loader: L) -> Self
+    where
+        L: Loader + Send + Sync + 'static,
+    {
+        self.with_route("", loader)
+    }
+}
+
+impl Loader for RoutingLoader {
+    type File = Box<dyn Read>;
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.find_canonical(url)?.map(|(_, file)| file))
+    }
+
+    /// Find a file.
+    ///
+    /// The canonical url is the canonical url from the loader of the
+    /// route, with the prefix of the route.
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
+        for (prefix, loader) in &self.routes {
+            if let Some(rest) = url.strip_prefix(prefix.as_str()) {
+                if let Some((canonical, file)) = loader.find_dyn(rest)? {
+                    return Ok(Some((
+                        format!("{}{}", prefix, canonical),
+                        file,
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// An object-safe version of [`Loader`].
+trait DynLoader: fmt::Debug + Send + Sync {
+    fn find_dyn(&self, url: &str) -> Result<Option<Found>, LoadError>;
+}
+
+/// The canonical url and content of a found file.
+type Found = (String, Box<dyn Read>);
+
+impl<L> DynLoader for L
+where
+    L: Loader + Send + Sync,
+    L::File: 'static,
+{
+    fn find_dyn(&self, url: &str) -> Result<Option<Found>, LoadError> {
+        Ok(self
+            .find_canonical(url)?
+            .map(|(canonical, f)| (canonical, Box::new(f) as Box<dyn Read>)))
+    }
+}
@@ -20,6 +20,7 @@ use std::io::Read;
 pub struct SourceFile {
     data: Vec<u8>,
     source: SourceName,
+    canonical: String,
     format: SourceFormat,
 }
 
@@ -41,6 +42,7 @@ impl SourceFile {
             .map_err(|e| LoadError::Input(source.name().to_string(), e))?;
         Ok(SourceFile {
             data,
+            canonical: source.name().into(),
             source,
             format,
         })
@@ -55,6 +57,7 @@ impl SourceFile {
     pub fn scss_bytes(data: impl Into<Vec<u8>>, source: SourceName) -> Self {
         SourceFile {
             data: data.into(),
+            canonical: source.name().into(),
             source,
             format: SourceFormat::Scss,
         }
@@ -69,11 +72,27 @@ impl SourceFile {
     pub fn css_bytes(data: impl Into<Vec<u8>>, source: SourceName) -> Self {
         SourceFile {
             data: data.into(),
+            canonical: source.name().into(),
             source,
             format: SourceFormat::Css,
         }
     }
 
+    /// Handle some raw byte data as an input file in a given format
+    /// with a given source name.
+    pub fn from_bytes(
+        data: impl Into<Vec<u8>>,
+        format: SourceFormat,
+        source: SourceName,
+    ) -> Self {
+        SourceFile {
+            data: data.into(),
+            canonical: source.name().into(),
+            source,
+            format,
+        }
+    }
+
     /// Parse this source.
     ///
     /// The correct parser will be applied based on the (known) format
@@ -93,8 +112,17 @@ impl SourceFile {
     pub(crate) fn source(&self) -> &SourceName {
         &self.source
     }
-    pub(crate) fn path(&self) -> &str {
-        self.source.name()
+    /// The canonical url of this source, identifying the file.
+    ///
+    /// This is the same as the source name, unless the file was
+    /// found by a [`Loader`][super::Loader] that provides another
+    /// canonical url.
+    pub(crate) fn canonical_url(&self) -> &str {
+        &self.canonical
+    }
+    pub(crate) fn with_canonical_url(mut self, url: String) -> Self {
+        self.canonical = url;
+        self
     }
 }
 
@@ -103,7 +131,8 @@ impl SourceFile {
 /// Rsass handles the scss format and raw css.
 /// TODO: In the future, the sass format may also be supported.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum SourceFormat {
+#[non_exhaustive]
+pub enum SourceFormat {
     /// The scss format is the main input format.
     Scss,
     /// The css format
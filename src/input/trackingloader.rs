@@ -0,0 +1,97 @@
+use super::{LoadError, Loader};
+use std::sync::{Arc, Mutex};
+
+/// A [`Loader`] that remembers the canonical urls of all files it
+/// loads.
+///
+/// This is useful to find the dependencies of a compiled stylesheet,
+/// e.g. to tell cargo when to rerun a build script.
+/// The list of urls is shared with the [`LoadedUrls`] handle from
+/// [`loaded`][Self::loaded], so it can be read after the loader is
+/// moved into a [`Context`][super::Context].
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{Context, MemoryLoader, TrackingLoader};
+/// # fn main() -> Result<(), rsass::Error> {
+/// let mut files = MemoryLoader::new();
+/// files.insert("main.scss", "@use 'colors';\na { b: colors.$c }");
+/// files.insert("_colors.scss", "$c: red;");
+/// let loader = TrackingLoader::new(files);
+/// let loaded = loader.loaded();
+/// let mut context = Context::for_loader(loader);
+/// let main = context.load_root("main")?;
+/// context.transform(main)?;
+/// assert_eq!(loaded.take(), ["main.scss", "_colors.scss"]);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct TrackingLoader<L> {
+    loader: L,
+    loaded: LoadedUrls,
+}
+
+/// The canonical urls of the files loaded by a [`TrackingLoader`].
+///
+/// Each url is listed once, in the order the files were first loaded.
+#[derive(Clone, Debug, Default)]
+pub struct LoadedUrls(Arc<Mutex<Vec<String>>>);
+
+impl<L: Loader> TrackingLoader<L> {
+    /// Create a new `TrackingLoader`, that loads files with `loader`.
+    pub fn new(loader: L) -> Self {
+        TrackingLoader {
+            loader,
+            loaded: LoadedUrls::default(),
+        }
+    }
+
+    /// Get a handle to the urls loaded so far, and later.
+    pub fn loaded(&self) -> LoadedUrls {
+        self.loaded.clone()
+    }
+}
+
+impl LoadedUrls {
+    /// Get a copy of the loaded urls.
+    pub fn get(&self) -> Vec<String> {
+        self.lock().clone()
+    }
+
+    /// Get the loaded urls, leaving the list empty.
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.lock())
+    }
+
+    fn push(&self, url: &str) {
+        let mut urls = self.lock();
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.into());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        // The list is always consistent, even if a thread panicked.
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<L: Loader> Loader for TrackingLoader<L> {
+    type File = L::File;
+
+    fn find_file(&self, url: &str) -> Result<Option<Self::File>, LoadError> {
+        Ok(self.find_canonical(url)?.map(|(_, file)| file))
+    }
+
+    fn find_canonical(
+        &self,
+        url: &str,
+    ) -> Result<Option<(String, Self::File)>, LoadError> {
+        let found = self.loader.find_canonical(url)?;
+        if let Some((canonical, _)) = &found {
+            self.loaded.push(canonical);
+        }
+        Ok(found)
+    }
+}
@@ -9,10 +9,8 @@
 //! use rsass::{compile_scss_path, output};
 //!
 //! let path = "tests/basic/14_imports/a.scss".as_ref();
-//! let format = output::Format {
-//!     style: output::Style::Compressed,
-//!     .. Default::default()
-//! };
+//! let format = output::Format::default()
+//!     .with_style(output::Style::Compressed);
 //! let css = compile_scss_path(path, format).unwrap();
 //!
 //! assert_eq!(css, b"div span{moo:goo}\n")
@@ -115,7 +113,7 @@ pub fn compile_scss(input: &[u8], format: Format) -> Result<Vec<u8>, Error> {
 /// assert_eq!(
 ///     compile_scss_path(
 ///         "tests/basic/14_imports/a.scss".as_ref(),
-///         Format { style: Style::Compressed, precision: 5 },
+///         Format::default().with_style(Style::Compressed).with_precision(5),
 ///     ).unwrap(),
 ///     b"div span{moo:goo}\n"
 /// )
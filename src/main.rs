@@ -1,8 +1,9 @@
 use clap::Parser;
-use rsass::output::{Format, Style};
+use rsass::output::{to_json, write_css_to, Format, Style};
 use rsass::{input, Error};
 use std::io::{stdout, Write};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 fn main() -> Result<(), Error> {
     Args::parse().run()
@@ -31,6 +32,16 @@ struct Args {
                 possible_values = Style::variants())]
     style: Style,
 
+    /// Write the result as css or as a json representation of the css.
+    #[clap(long, ignore_case = true,
+                default_value = "css",
+                possible_values = &["css", "json"])]
+    output_format: OutputFormat,
+
+    /// Include source positions in json output.
+    #[clap(long)]
+    positions: bool,
+
     /// Some kind of forced ascii output
     /// (Not implemented, but set by the sass-spec test runner)
     #[cfg(feature = "unimplemented_args")]
@@ -64,18 +75,46 @@ struct Args {
 
 impl Args {
     fn run(self) -> Result<(), Error> {
-        let format = Format {
-            style: self.style,
-            precision: self.precision,
-        };
+        let format = Format::default()
+            .with_style(self.style)
+            .with_precision(self.precision);
         for name in &self.input {
             let (mut context, source) = input::FsContext::for_path(name)?;
             if let Some(include_path) = &self.load_path {
                 context.push_path(include_path.as_ref());
             }
-            let result = context.with_format(format).transform(source)?;
-            stdout().write_all(&result)?;
+            let context = context.with_format(format);
+            match self.output_format {
+                OutputFormat::Css => {
+                    let items = context.transform_to_ast(source)?;
+                    write_css_to(&items, format, stdout().lock())?;
+                }
+                OutputFormat::Json => {
+                    let items = context.transform_to_ast(source)?;
+                    let json = to_json(&items, format, self.positions);
+                    serde_json::to_writer_pretty(stdout(), &json)
+                        .map_err(std::io::Error::from)?;
+                    writeln!(stdout())?;
+                }
+            }
         }
         Ok(())
     }
 }
+
+/// The kind of output to write.
+enum OutputFormat {
+    Css,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_ref() {
+            "css" => Ok(OutputFormat::Css),
+            "json" => Ok(OutputFormat::Json),
+            s => Err(format!("Output format {:?} not supported", s)),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use std::iter::FromIterator;
-use std::slice::Iter;
+use std::slice::{Iter, IterMut};
 use std::vec::IntoIter;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -33,6 +33,9 @@ impl<K: Clone + PartialEq, V: Clone> OrderMap<K, V> {
     pub fn values(&self) -> impl Iterator<Item = &'_ V> {
         self.0.iter().map(|&(ref _k, ref v)| v)
     }
+    pub fn iter_mut(&mut self) -> IterMut<'_, (K, V)> {
+        self.0.iter_mut()
+    }
     pub fn get(&self, key: &K) -> Option<&V> {
         for &(ref k, ref v) in &self.0 {
             if k == key {
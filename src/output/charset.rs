@@ -0,0 +1,37 @@
+/// When to start the css output with a `@charset` rule.
+///
+/// In compressed output, a byte order mark is used instead of the
+/// `@charset` rule.
+/// The default is to write it only if the output is not plain ascii.
+///
+/// When streaming the output with
+/// [`Context::transform_to_writer`][crate::input::Context::transform_to_writer],
+/// the `Auto` choice means that the output must be held back until
+/// the first non-ascii item (or the end of the input).
+/// With `Always` or `Never`, each item can be written at once.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::{compile_scss, output::{Charset, Format}};
+/// let format = Format::default().with_charset(Charset::Never);
+/// assert_eq!(
+///     compile_scss("a { b: \"é\" }".as_bytes(), format).unwrap(),
+///     "a {\n  b: \"é\";\n}\n".as_bytes(),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset {
+    /// Write a `@charset` rule if the output is not plain ascii.
+    Auto,
+    /// Always write a `@charset` rule.
+    Always,
+    /// Never write a `@charset` rule.
+    Never,
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Charset::Auto
+    }
+}
@@ -0,0 +1,94 @@
+/// How colors are written in css output.
+///
+/// The default is to keep the source representation in expanded
+/// output and use the shortest representation in compressed output.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::{compile_scss, output::{ColorFormat, ColorPolicy, Format}};
+/// let format = Format::default()
+///     .with_colors(ColorFormat::new(ColorPolicy::Hex).without_names());
+/// assert_eq!(
+///     compile_scss(b"a { b: red; c: #ABC; d: rgb(1, 2, 3) }", format).unwrap(),
+///     b"a {\n  b: #ff0000;\n  c: #aabbcc;\n  d: #010203;\n}\n",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorFormat {
+    /// Which representation to use for colors.
+    pub policy: ColorPolicy,
+    /// Use the space-separated syntax from css color level 4 for color
+    /// functions, e.g. `rgb(1 2 3 / 0.5)` rather than `rgba(1, 2, 3, 0.5)`.
+    pub modern_syntax: bool,
+    /// Allow color names, like `red` or `transparent`, in output.
+    pub names: bool,
+}
+
+/// The main choice of how a color is represented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorPolicy {
+    /// Preserve the source representation in expanded output, use
+    /// the shortest representation in compressed output.
+    Auto,
+    /// Preserve the representation from the source where possible.
+    Preserve,
+    /// Use the shortest representation.
+    Shortest,
+    /// Always use lowercase six-digit hexadecimal, e.g. `#ff0000`.
+    ///
+    /// Colors that are not fully opaque are written with eight digits.
+    Hex,
+    /// Always use the `rgb()` function (or `rgba()`, for colors
+    /// that are not fully opaque, unless the modern syntax is used).
+    RgbFunction,
+}
+
+impl ColorFormat {
+    /// Create a color format with the given policy.
+    ///
+    /// Color names are allowed and the legacy function syntax is used.
+    pub const fn new(policy: ColorPolicy) -> Self {
+        ColorFormat {
+            policy,
+            modern_syntax: false,
+            names: true,
+        }
+    }
+    /// Use the modern (css color level 4) syntax for color functions.
+    pub fn modern(self) -> Self {
+        ColorFormat {
+            modern_syntax: true,
+            ..self
+        }
+    }
+    /// Never write color names.
+    pub fn without_names(self) -> Self {
+        ColorFormat {
+            names: false,
+            ..self
+        }
+    }
+
+    /// The policy to use, given if the output is compressed.
+    pub(crate) fn policy_for(&self, compressed: bool) -> ColorPolicy {
+        match self.policy {
+            ColorPolicy::Auto if compressed => ColorPolicy::Shortest,
+            ColorPolicy::Auto => ColorPolicy::Preserve,
+            policy => policy,
+        }
+    }
+    /// True if colors may be written as in the source.
+    ///
+    /// Note that this is true for [`ColorPolicy::Auto`] even in
+    /// compressed output.
+    pub(crate) fn keep_source(&self) -> bool {
+        matches!(self.policy, ColorPolicy::Auto | ColorPolicy::Preserve)
+    }
+}
+
+impl Default for ColorFormat {
+    fn default() -> Self {
+        ColorFormat::new(ColorPolicy::Auto)
+    }
+}
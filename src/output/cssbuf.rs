@@ -1,73 +1,557 @@
-use super::Format;
-use crate::css::Import;
-use crate::{Error, ScopeRef};
-use std::collections::BTreeMap;
+use super::{Charset, Format};
+use crate::css::{Import, Item, Rule};
+use crate::error::Invalid;
+use crate::sass::Config;
+use crate::{Error, ScopeRef, SourcePos};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{self, Write};
+use std::mem::take;
 
-/// A [CssBuf] for imports, that also keeps track of loaded modules.
-pub struct CssHead {
+/// The state of a transformation that is shared by all files.
+///
+/// This is where the hoisted imports go, and where the finished
+/// top-level items are sent by [`flush`][Self::flush].
+/// It also keeps track of loaded modules.
+pub struct CssHead<'w> {
     imports: Vec<Import>,
-    modules: BTreeMap<String, ScopeRef>,
+    modules: BTreeMap<String, LoadedModule>,
+    /// The number of modules loaded so far.
+    loaded: usize,
+    output: Output<'w>,
 }
 
-impl CssHead {
+/// Where the finished top-level items go.
+enum Output<'w> {
+    /// Collect the items, to be returned as a tree.
+    Items(Vec<Item>),
+    /// Write each item as it is finished.
+    Writer(Box<CssWriter<Box<dyn Write + 'w>>>),
+}
+
+/// A module that is loaded, with the css it produced.
+struct LoadedModule {
+    scope: ScopeRef,
+    css: ModuleCss,
+    pos: SourcePos,
+    /// A copy of the configuration the module was loaded with.
+    config: Config,
+    /// The number of modules loaded before this module.
+    before: usize,
+    /// The number of modules loaded before this module was done.
+    seq: usize,
+}
+
+/// The css produced by a module.
+pub enum ModuleCss {
+    /// A range of the items in the output.
+    Output(usize, usize),
+    /// Items that are not part of the output.
+    Items(Vec<Item>),
+    /// The css is written to the output, and not kept.
+    Written,
+}
+
+impl<'w> CssHead<'w> {
+    /// Create a head that collects the output items.
     pub fn new() -> Self {
+        Self::with_output(Output::Items(Vec::new()))
+    }
+    /// Create a head that writes the output to `out` as it is finished.
+    pub fn for_writer(out: impl Write + 'w, format: Format) -> Self {
+        Self::with_output(Output::Writer(Box::new(CssWriter::new(
+            Box::new(out),
+            format,
+        ))))
+    }
+    fn with_output(output: Output<'w>) -> Self {
         CssHead {
             imports: Default::default(),
             modules: Default::default(),
+            loaded: 0,
+            output,
         }
     }
-    pub fn add_import(&mut self, import: Import) {
-        self.imports.push(import)
+
+    /// Add a plain css import, to be written before all other output.
+    ///
+    /// When writing the output, it is an error to add an import after
+    /// other items are written.
+    pub fn add_import(
+        &mut self,
+        import: Import,
+        pos: &SourcePos,
+    ) -> Result<(), Error> {
+        match &mut self.output {
+            Output::Items(_) => self.imports.push(import),
+            Output::Writer(writer) => {
+                if writer.is_started() {
+                    return Err(Invalid::LateImport.at(pos.clone()));
+                }
+                writer.add_import(&import)?;
+            }
+        }
+        Ok(())
     }
 
+    /// Move the finished items in `buf` to the output.
+    ///
+    /// This does nothing unless `buf` is the buffer for the output, as
+    /// created by [`CssItems::output`].
+    pub fn flush(&mut self, buf: &mut CssItems) -> Result<(), Error> {
+        if !buf.output {
+            return Ok(());
+        }
+        match &mut self.output {
+            Output::Items(items) => items.append(&mut buf.items),
+            Output::Writer(writer) => {
+                for item in buf.items.drain(..) {
+                    writer.write_item(&item)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Start evaluating a module to the output.
+    ///
+    /// Returns the start position, to give to
+    /// [`end_module`][Self::end_module].
+    pub fn start_module(&self) -> usize {
+        match &self.output {
+            Output::Items(items) => items.len(),
+            Output::Writer(_) => 0,
+        }
+    }
+    /// Get the css of a module evaluated to the output since `start`.
+    pub fn end_module(&self, start: usize) -> ModuleCss {
+        match &self.output {
+            Output::Items(items) => ModuleCss::Output(start, items.len()),
+            Output::Writer(_) => ModuleCss::Written,
+        }
+    }
+
+    /// Get a loaded module, or load it with `init`.
+    ///
+    /// The `init` function returns the module scope and the css
+    /// produced by the module.
+    /// It is an error to configure a module that is already loaded,
+    /// unless it was loaded with the same configuration, as given by
+    /// the origin of the `config`.
+    /// The `name` is used in the error message, if given.
     pub fn load_module<Init>(
         &mut self,
         path: &str,
+        name: Option<&str>,
+        config: &Config,
+        pos: &SourcePos,
         init: Init,
     ) -> Result<ScopeRef, Error>
     where
-        Init: FnOnce(&mut Self) -> Result<ScopeRef, Error>,
+        Init: FnOnce(&mut Self) -> Result<(ScopeRef, ModuleCss), Error>,
     {
         if let Some(loaded) = self.modules.get(path) {
-            return Ok(loaded.clone());
+            if config.origin().is_some()
+                && config.origin() != loaded.config.origin()
+            {
+                return Err(Error::AlreadyLoaded(
+                    name.map(String::from),
+                    pos.clone(),
+                    loaded.pos.clone(),
+                ));
+            }
+            return Ok(loaded.scope.clone());
+        }
+        let before = self.loaded;
+        let copy = config.copy();
+        let (scope, css) = init(self)?;
+        let module = LoadedModule {
+            scope: scope.clone(),
+            css,
+            pos: pos.clone(),
+            config: copy,
+            before,
+            seq: self.loaded,
+        };
+        self.loaded += 1;
+        self.modules.insert(path.into(), module);
+        Ok(scope)
+    }
+
+    /// Get the css produced by a loaded module.
+    ///
+    /// Separators at the start are skipped.
+    /// Returns `None` if the css is written to the output, see
+    /// [`evaluate_again`][Self::evaluate_again].
+    pub fn module_css(&self, path: &str) -> Option<Vec<Item>> {
+        let items = match self.modules.get(path).map(|module| &module.css) {
+            Some(ModuleCss::Items(items)) => &items[..],
+            Some(ModuleCss::Output(start, end)) => match &self.output {
+                Output::Items(items) => &items[*start..*end],
+                Output::Writer(_) => return None,
+            },
+            Some(ModuleCss::Written) => return None,
+            None => &[],
+        };
+        Some(
+            items
+                .iter()
+                .skip_while(|item| matches!(item, Item::Separator))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Evaluate a loaded module again with `eval`, to get its css.
+    ///
+    /// The `eval` function gets a copy of the configuration the
+    /// module was loaded with.
+    /// It is called with only the modules that were loaded before the
+    /// module, so the modules loaded by the module are evaluated
+    /// again as well (and forgotten afterwards).
+    pub fn evaluate_again(
+        &mut self,
+        path: &str,
+        eval: impl FnOnce(&mut Self, Config) -> Result<Vec<Item>, Error>,
+    ) -> Result<Vec<Item>, Error> {
+        let (config, before) = match self.modules.get(path) {
+            Some(module) => (module.config.copy(), module.before),
+            None => return Ok(Vec::new()),
+        };
+        let (outer, later): (BTreeMap<_, _>, BTreeMap<_, _>) =
+            take(&mut self.modules)
+                .into_iter()
+                .partition(|(_, module)| module.seq < before);
+        let keep = outer.keys().cloned().collect::<BTreeSet<_>>();
+        self.modules = outer;
+        let result = eval(self, config);
+        self.modules = take(&mut self.modules)
+            .into_iter()
+            .filter(|(path, _)| keep.contains(path))
+            .chain(later)
+            .collect();
+        let items = result?;
+        Ok(items
+            .into_iter()
+            .skip_while(|item| matches!(item, Item::Separator))
+            .collect())
+    }
+
+    /// Call `f` with no modules loaded.
+    ///
+    /// This is used for `@import`, where the imported file loads its
+    /// own modules.
+    pub fn with_new_modules<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let outer = take(&mut self.modules);
+        let result = f(self);
+        self.modules = outer;
+        result
+    }
+
+    /// Combine the hoisted imports with the output items.
+    ///
+    /// Any items left in `body` are flushed first.
+    pub fn combine_final(mut self, mut body: CssItems) -> Vec<Item> {
+        self.flush(&mut body)
+            .expect("Collecting items should not fail");
+        match self.output {
+            Output::Items(items) => self
+                .imports
+                .into_iter()
+                .map(Item::Import)
+                .chain(items)
+                .collect(),
+            Output::Writer(_) => Vec::new(),
         }
-        let module = init(self)?;
-        self.modules.insert(path.into(), module.clone());
-        Ok(module)
     }
 
-    pub fn merge_imports(&mut self, other: Self) {
-        self.imports.extend(other.imports);
+    /// Write the end of the output.
+    ///
+    /// Any items left in `body` are flushed first.
+    pub fn finish(mut self, mut body: CssItems) -> Result<(), Error> {
+        self.flush(&mut body)?;
+        if let Output::Writer(writer) = self.output {
+            writer.finish()?;
+        }
+        Ok(())
     }
+}
 
-    pub fn combine_final(&self, body: CssBuf) -> Vec<u8> {
-        let mut buf = CssBuf::new_as(&body);
-        for i in &self.imports {
-            i.write(&mut buf).unwrap();
+/// A collection of css items, as created from transforming sass.
+///
+/// This is where the output of the transformation goes, before it
+/// is sent to the output of the [`CssHead`] or included in a
+/// surrounding rule.
+pub struct CssItems {
+    items: Vec<Item>,
+    root_level: bool,
+    separate: bool,
+    /// True if this is the buffer for the output.
+    output: bool,
+}
+
+impl CssItems {
+    pub fn new() -> Self {
+        CssItems {
+            items: Vec::new(),
+            root_level: true,
+            separate: false,
+            output: false,
+        }
+    }
+    /// Create the buffer for the output.
+    ///
+    /// Top-level items are moved from this buffer to the output by
+    /// [`CssHead::flush`] as soon as they are finished.
+    pub fn output() -> Self {
+        CssItems {
+            output: true,
+            ..Self::new()
         }
-        let mut result = vec![];
-        let compressed = body.format.is_compressed();
-        if !buf.is_ascii() || !body.is_ascii() {
-            if compressed {
-                // U+FEFF is byte order mark, used to show encoding.
-                result.extend_from_slice("\u{feff}".as_bytes());
-            } else {
-                result.extend_from_slice(b"@charset \"UTF-8\";\n");
+    }
+    /// Create a new buffer at the same level as this.
+    pub fn new_as(orig: &Self) -> Self {
+        CssItems {
+            root_level: orig.root_level,
+            ..Self::new()
+        }
+    }
+    /// Create a new buffer for the body of an at-rule.
+    pub fn new_nested() -> Self {
+        CssItems {
+            root_level: false,
+            ..Self::new()
+        }
+    }
+
+    /// Mark the start of a new group of items.
+    ///
+    /// Groups are separated by an empty line in non-compressed output.
+    pub fn do_separate(&mut self) {
+        if self.separate {
+            self.items.push(Item::Separator);
+        } else {
+            self.separate = true;
+        }
+    }
+    pub fn is_root_level(&self) -> bool {
+        self.root_level
+    }
+    /// True if this is the buffer for the output.
+    pub fn is_output(&self) -> bool {
+        self.output
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn push(&mut self, item: impl Into<Item>) {
+        self.items.push(item.into())
+    }
+    /// Add a rule, unless it is empty.
+    pub fn push_rule(&mut self, rule: Rule) {
+        if !rule.body.is_empty() {
+            self.items.push(rule.into())
+        }
+    }
+    pub fn join(&mut self, sub: Self) {
+        self.items.extend(sub.items);
+    }
+    /// The number of items in this buffer.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    /// Get a copy of the items from `start`.
+    pub fn items_from(&self, start: usize) -> Vec<Item> {
+        self.items[start..].to_vec()
+    }
+    pub fn into_items(self) -> Vec<Item> {
+        self.items
+    }
+}
+
+/// Serialize css items to a byte vector in the given format.
+pub fn write_css(items: &[Item], format: Format) -> Vec<u8> {
+    let mut result = Vec::new();
+    write_css_to(items, format, &mut result)
+        .expect("Writing to a Vec should not fail");
+    result
+}
+
+/// Serialize css items to a writer in the given format.
+///
+/// The output is the same as from [`write_css`], but each top-level
+/// item is serialized and written to `out` as soon as it is known if
+/// the output needs a `@charset` rule (or a byte order mark, in
+/// compressed format).
+/// With the default [`Charset::Auto`][super::Charset::Auto], that is
+/// when the first non-ascii item is found, or at the end.
+pub fn write_css_to(
+    items: &[Item],
+    format: Format,
+    out: impl Write,
+) -> io::Result<()> {
+    let mut writer = CssWriter::new(out, format);
+    let mut items = items.iter().peekable();
+    while let Some(Item::Import(import)) = items.peek() {
+        writer.add_import(import)?;
+        items.next();
+    }
+    for item in items {
+        writer.write_item(item)?;
+    }
+    writer.finish()
+}
+
+/// Writes css to an `io::Write`, one top-level item at a time.
+///
+/// Imports are written first, so they should be added before any
+/// other item is written, see [`is_started`][Self::is_started].
+/// Until it is known if the output needs a `@charset` rule (or a byte
+/// order mark, in compressed format), the serialized items are held
+/// back.
+struct CssWriter<W> {
+    out: TrimEnd<W>,
+    compressed: bool,
+    imports: CssBuf,
+    body: CssBuf,
+    /// If the output needs a `@charset` rule, once that is known.
+    charset: Option<bool>,
+    /// The serialized items, while `charset` is not known.
+    pending: Vec<u8>,
+    /// True when the imports and pending items are written.
+    started: bool,
+}
+
+impl<W: Write> CssWriter<W> {
+    fn new(out: W, format: Format) -> Self {
+        let compressed = format.is_compressed();
+        CssWriter {
+            out: TrimEnd::new(out, compressed),
+            compressed,
+            imports: CssBuf::new(format),
+            body: CssBuf::new(format),
+            charset: match format.charset {
+                Charset::Auto => None,
+                Charset::Always => Some(true),
+                Charset::Never => Some(false),
+            },
+            pending: Vec::new(),
+            started: false,
+        }
+    }
+    /// True if the imports are written, so an import added now would
+    /// be written after other items.
+    fn is_started(&self) -> bool {
+        self.started
+    }
+    fn add_import(&mut self, import: &Import) -> io::Result<()> {
+        import.write(&mut self.imports)?;
+        if self.charset.is_none() && !self.imports.is_ascii() {
+            self.charset = Some(true);
+        }
+        if self.started {
+            self.imports.flush_to(&mut self.out)?;
+        }
+        Ok(())
+    }
+    fn write_item(&mut self, item: &Item) -> io::Result<()> {
+        item.write(&mut self.body)?;
+        if self.charset.is_none() {
+            if self.body.is_ascii() {
+                return self.body.flush_to(&mut self.pending);
             }
+            self.charset = Some(true);
         }
-        result.extend(buf.buf);
-        result.extend(body.buf);
-        while result.last() == Some(&b'\n') {
-            result.pop();
+        self.start()?;
+        self.body.flush_to(&mut self.out)
+    }
+    /// Write the start of the output, the imports and any pending
+    /// items, unless that is already done.
+    fn start(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            if self.charset == Some(true) {
+                if self.compressed {
+                    // U+FEFF is byte order mark, used to show encoding.
+                    self.out.write_all("\u{feff}".as_bytes())?;
+                } else {
+                    self.out.write_all(b"@charset \"UTF-8\";\n")?;
+                }
+            }
+            self.imports.flush_to(&mut self.out)?;
+            self.out.write_all(&take(&mut self.pending))?;
         }
-        if compressed && result.last() == Some(&b';') {
-            result.pop();
+        Ok(())
+    }
+    fn finish(mut self) -> io::Result<()> {
+        self.start()?;
+        self.out.finish()
+    }
+}
+
+/// A writer that holds back trailing newlines (and, for compressed
+/// output, a final semicolon), so the output ends with exactly one
+/// newline (or is completely empty).
+struct TrimEnd<W> {
+    out: W,
+    pending: Vec<u8>,
+    compressed: bool,
+    written: bool,
+}
+
+impl<W: Write> TrimEnd<W> {
+    fn new(out: W, compressed: bool) -> Self {
+        TrimEnd {
+            out,
+            pending: Vec::new(),
+            compressed,
+            written: false,
+        }
+    }
+    /// The length of the part at the end of `data` that may be trimmed.
+    fn tail_len(&self, data: &[u8]) -> usize {
+        let mut len = data.iter().rev().take_while(|b| **b == b'\n').count();
+        if self.compressed
+            && data.len() > len
+            && data[data.len() - len - 1] == b';'
+        {
+            len += 1;
         }
-        if !result.is_empty() {
-            result.push(b'\n');
+        len
+    }
+    fn finish(mut self) -> io::Result<()> {
+        if self.written {
+            self.out.write_all(b"\n")?;
         }
-        result
+        self.out.flush()
+    }
+}
+
+impl<W: Write> Write for TrimEnd<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let tail = self.tail_len(data);
+        if tail < data.len() {
+            self.out.write_all(&self.pending)?;
+            self.pending.clear();
+            self.out.write_all(&data[..data.len() - tail])?;
+            self.written = true;
+            self.pending.extend_from_slice(&data[data.len() - tail..]);
+        } else {
+            self.pending.extend_from_slice(data);
+            let keep = self.pending.len() - self.tail_len(&self.pending);
+            if keep > 0 {
+                self.out.write_all(&self.pending[..keep])?;
+                self.written = true;
+                self.pending.drain(..keep);
+            }
+        }
+        Ok(data.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
     }
 }
 
@@ -75,22 +559,17 @@ pub struct CssBuf {
     buf: Vec<u8>,
     format: Format,
     indent: usize,
-    separate: bool,
+    /// True if anything has been written with [`flush_to`][Self::flush_to].
+    flushed: bool,
 }
 
 impl CssBuf {
     pub fn new(format: Format) -> CssBuf {
-        CssBuf::_new(format, 0)
-    }
-    pub fn new_as(orig: &Self) -> CssBuf {
-        CssBuf::_new(orig.format, orig.indent)
-    }
-    fn _new(format: Format, indent: usize) -> CssBuf {
         CssBuf {
             buf: Vec::new(),
             format,
-            indent,
-            separate: false,
+            indent: 0,
+            flushed: false,
         }
     }
     pub(crate) fn format(&self) -> Format {
@@ -118,13 +597,12 @@ impl CssBuf {
         self.add_one("}\n", "}");
     }
 
+    /// Add an empty line, unless compressed or at the start of output.
     pub fn do_separate(&mut self) {
-        if self.separate {
-            if !self.format.is_compressed() && !self.buf.is_empty() {
-                self.buf.push(b'\n');
-            }
-        } else {
-            self.separate = true;
+        if !self.format.is_compressed()
+            && (self.flushed || !self.buf.is_empty())
+        {
+            self.buf.push(b'\n');
         }
     }
     pub fn do_indent(&mut self) {
@@ -137,19 +615,20 @@ impl CssBuf {
         }
     }
 
-    pub fn is_root_level(&self) -> bool {
-        self.indent == 0
-    }
-    pub fn is_empty(&self) -> bool {
-        self.buf.is_empty()
-    }
     pub fn is_ascii(&self) -> bool {
         self.buf.is_ascii()
     }
 
-    pub fn join(&mut self, sub: Self) {
-        self.buf.extend_from_slice(&sub.buf);
+    /// Write the content of this buffer to `out` and clear it.
+    ///
+    /// Should only be done between top-level items.
+    pub fn flush_to(&mut self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(&self.buf)?;
+        self.flushed |= !self.buf.is_empty();
+        self.buf.clear();
+        Ok(())
     }
+
     pub fn add_str(&mut self, sub: &str) {
         self.buf.extend_from_slice(sub.as_bytes())
     }
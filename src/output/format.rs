@@ -1,15 +1,27 @@
-use super::Style;
+use super::{Charset, ColorFormat, NumberFormat, Style};
 
 /// Specifies the format for outputing css.
 ///
-/// The format is the style (expanded or compressed) and the precision
-/// for numeric values.
+/// The format is the style (expanded or compressed), the precision
+/// and other details for numeric values, how colors are written and
+/// when the output starts with a `@charset` rule.
+///
+/// A format is created from the default and modified by the `with_`
+/// methods, e.g.
+/// `Format::default().with_style(Style::Compressed).with_precision(5)`.
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub struct Format {
     /// The style of this format (expanded, compressed or introspection)
     pub style: Style,
     /// Number of decimals to use for numeric output.
     pub precision: usize,
+    /// How to write colors.
+    pub colors: ColorFormat,
+    /// How to write numbers, apart from the precision.
+    pub numbers: NumberFormat,
+    /// When to start the output with a `@charset` rule.
+    pub charset: Charset,
 }
 
 impl Format {
@@ -20,6 +32,27 @@ impl Format {
             ..Default::default()
         }
     }
+    /// Use the given style.
+    pub fn with_style(self, style: Style) -> Self {
+        Format { style, ..self }
+    }
+    /// Use the given number of decimals for numeric output.
+    pub fn with_precision(self, precision: usize) -> Self {
+        Format { precision, ..self }
+    }
+    /// Use the given format for colors.
+    pub fn with_colors(self, colors: ColorFormat) -> Self {
+        Format { colors, ..self }
+    }
+    /// Use the given format for numbers.
+    pub fn with_numbers(self, numbers: NumberFormat) -> Self {
+        Format { numbers, ..self }
+    }
+    /// Use the given choice of when to write a `@charset` rule.
+    pub fn with_charset(self, charset: Charset) -> Self {
+        Format { charset, ..self }
+    }
+
     /// Return true if this is a compressed format.
     pub fn is_compressed(&self) -> bool {
         self.style == Style::Compressed
@@ -45,6 +78,9 @@ impl Default for Format {
         Format {
             style: Style::Expanded,
             precision: 10,
+            colors: ColorFormat::default(),
+            numbers: NumberFormat::default(),
+            charset: Charset::default(),
         }
     }
 }
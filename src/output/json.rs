@@ -0,0 +1,185 @@
+//! Serialize a css tree as json.
+//!
+//! This is only available if rsass is built with the `json` feature.
+use super::Format;
+use crate::css::{
+    AtRule, AtRuleBodyItem, BodyItem, Import, Item, Property, Rule,
+};
+use crate::SourcePos;
+use serde_json::{json, Value as Json};
+
+/// Convert a css tree to json.
+///
+/// The result is an array of nodes, each an object with a `"type"`:
+///
+/// * A `"rule"` has a list of `"selectors"` and a list of
+///   `"declarations"`.
+///   If the rule contains any comments or imports, they are listed
+///   separately as `"comments"` and `"imports"`.
+/// * A `"declaration"` has a `"name"` and a `"value"`.
+/// * An `"at-rule"` has a `"name"`, a `"prelude"` and, unless it is
+///   body-less, a list of `"children"`.
+/// * An `"import"` has a `"url"` and optionally `"args"`.
+/// * A `"comment"` has its `"text"`.
+///
+/// Values and selectors are formatted as strings in the given
+/// `format`.
+/// If `with_pos` is true, nodes with a known source position also
+/// get a `"pos"` object with `"file"`, `"line"` and `"column"`.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::input::{FsContext, SourceFile, SourceName};
+/// # use rsass::output::to_json;
+/// # use serde_json::json;
+/// # fn main() -> Result<(), rsass::Error> {
+/// let items = FsContext::for_cwd().transform_to_ast(
+///     SourceFile::scss_bytes("a, b { c: 1px + 2px }", SourceName::root("-")),
+/// )?;
+/// assert_eq!(
+///     to_json(&items, Default::default(), false),
+///     json!([{
+///         "type": "rule",
+///         "selectors": ["a", "b"],
+///         "declarations": [
+///             {"type": "declaration", "name": "c", "value": "3px"},
+///         ],
+///     }]),
+/// );
+/// # Ok(()) }
+/// ```
+pub fn to_json(items: &[Item], format: Format, with_pos: bool) -> Json {
+    let writer = JsonWriter { format, with_pos };
+    Json::Array(items.iter().filter_map(|i| writer.item(i)).collect())
+}
+
+struct JsonWriter {
+    format: Format,
+    with_pos: bool,
+}
+
+impl JsonWriter {
+    fn item(&self, item: &Item) -> Option<Json> {
+        match item {
+            Item::Comment(c) => Some(comment(c.text())),
+            Item::Import(import) => Some(self.import(import)),
+            Item::Rule(rule) => Some(self.rule(rule)),
+            Item::AtRule(rule) => Some(self.at_rule(rule)),
+            Item::Separator => None,
+        }
+    }
+
+    fn at_rule_item(&self, item: &AtRuleBodyItem) -> Option<Json> {
+        match item {
+            AtRuleBodyItem::Import(import) => Some(self.import(import)),
+            AtRuleBodyItem::Comment(c) => Some(comment(c.text())),
+            AtRuleBodyItem::Rule(rule) => Some(self.rule(rule)),
+            AtRuleBodyItem::Property(prop) => Some(self.property(prop)),
+            AtRuleBodyItem::CustomProperty(name, value) => {
+                Some(declaration(name, value.to_string()))
+            }
+            AtRuleBodyItem::AtRule(rule) => Some(self.at_rule(rule)),
+            AtRuleBodyItem::Separator => None,
+        }
+    }
+
+    fn rule(&self, rule: &Rule) -> Json {
+        let selectors = rule
+            .selectors()
+            .iter()
+            .map(|s| {
+                Json::from(if self.format.is_compressed() {
+                    format!("{:#}", s)
+                } else {
+                    s.to_string()
+                })
+            })
+            .collect::<Vec<_>>();
+        let (mut declarations, mut comments, mut imports) =
+            (vec![], vec![], vec![]);
+        for item in rule.body() {
+            match item {
+                BodyItem::Import(import) => imports.push(self.import(import)),
+                BodyItem::Property(prop) => {
+                    declarations.push(self.property(prop))
+                }
+                BodyItem::CustomProperty(name, value) => {
+                    declarations.push(declaration(name, value.to_string()))
+                }
+                BodyItem::Comment(c) => comments.push(comment(c.text())),
+            }
+        }
+        let mut result = json!({
+            "type": "rule",
+            "selectors": selectors,
+            "declarations": declarations,
+        });
+        if !comments.is_empty() {
+            result["comments"] = comments.into();
+        }
+        if !imports.is_empty() {
+            result["imports"] = imports.into();
+        }
+        self.with_pos(result, rule.pos())
+    }
+
+    fn property(&self, prop: &Property) -> Json {
+        let value = prop.value().format(self.format).to_string();
+        self.with_pos(declaration(prop.name(), value), prop.pos())
+    }
+
+    fn at_rule(&self, rule: &AtRule) -> Json {
+        let mut result = json!({
+            "type": "at-rule",
+            "name": rule.name(),
+            "prelude": rule.args().format(self.format).to_string(),
+        });
+        if let Some(body) = rule.body() {
+            result["children"] = body
+                .iter()
+                .filter_map(|i| self.at_rule_item(i))
+                .collect::<Vec<_>>()
+                .into();
+        }
+        self.with_pos(result, rule.pos())
+    }
+
+    fn import(&self, import: &Import) -> Json {
+        let mut result = json!({
+            "type": "import",
+            "url": import.name().value(),
+        });
+        if !import.args().is_null() {
+            result["args"] =
+                import.args().format(self.format).to_string().into();
+        }
+        result
+    }
+
+    fn with_pos(&self, mut json: Json, pos: Option<&SourcePos>) -> Json {
+        if let (true, Some(pos)) = (self.with_pos, pos) {
+            json["pos"] = json!({
+                "file": pos.file_url(),
+                "line": pos.line_no(),
+                "column": pos.line_pos(),
+            });
+        }
+        json
+    }
+}
+
+fn declaration(name: &str, value: String) -> Json {
+    json!({
+        "type": "declaration",
+        "name": name,
+        "value": value,
+    })
+}
+
+fn comment(text: &str) -> Json {
+    json!({
+        "type": "comment",
+        "text": text,
+    })
+}
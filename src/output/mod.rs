@@ -1,11 +1,22 @@
 //! Types describing how to format output.
+mod charset;
+mod colors;
 mod cssbuf;
 mod format;
+#[cfg(feature = "json")]
+mod json;
+mod numbers;
 mod style;
 mod transform;
 
+pub use charset::Charset;
+pub use colors::{ColorFormat, ColorPolicy};
+pub use cssbuf::{write_css, write_css_to};
 pub use format::{Format, Formatted};
+#[cfg(feature = "json")]
+pub use json::to_json;
+pub use numbers::{NumberFormat, Rounding};
 pub use style::Style;
 
-pub(crate) use cssbuf::{CssBuf, CssHead};
+pub(crate) use cssbuf::{CssBuf, CssHead, CssItems};
 pub(crate) use transform::handle_parsed;
@@ -0,0 +1,71 @@
+/// How numbers are written in css output, in addition to the
+/// [`precision`](super::Format::precision) of the format.
+///
+/// The default is to never use scientific notation, to round halves
+/// away from zero, and to skip the leading zero of numbers between -1
+/// and 1 only in compressed output.
+///
+/// # Example
+///
+/// ```
+/// # use rsass::{compile_scss, output::{Format, NumberFormat, Rounding}};
+/// let format = Format::default().with_precision(2).with_numbers(
+///     NumberFormat {
+///         leading_zero: Some(false),
+///         rounding: Rounding::HalfEven,
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(
+///     compile_scss(b"a { b: 0.125; c: 0.135 }", format).unwrap(),
+///     b"a {\n  b: .12;\n  c: .14;\n}\n",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Use scientific notation for non-zero numbers with an absolute
+    /// value below 10 raised to this exponent.
+    pub sci_below: Option<i32>,
+    /// Use scientific notation for numbers with an absolute value of
+    /// at least 10 raised to this exponent.
+    pub sci_from: Option<i32>,
+    /// If numbers between -1 and 1 should have a zero before the
+    /// decimal point.
+    ///
+    /// If None, the zero is written unless the output is compressed.
+    pub leading_zero: Option<bool>,
+    /// How to round the last decimal.
+    pub rounding: Rounding,
+}
+
+/// Rounding mode for the last decimal of a number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round halves up (away from zero), e.g. 0.125 is 0.13.
+    HalfUp,
+    /// Round halves to the nearest even digit, e.g. 0.125 is 0.12.
+    HalfEven,
+}
+
+impl NumberFormat {
+    /// Create the default number format.
+    pub const fn new() -> Self {
+        NumberFormat {
+            sci_below: None,
+            sci_from: None,
+            leading_zero: None,
+            rounding: Rounding::HalfUp,
+        }
+    }
+    /// True if the leading zero should be written, given if the
+    /// output is compressed.
+    pub(crate) fn leading_zero_for(&self, compressed: bool) -> bool {
+        self.leading_zero.unwrap_or(!compressed)
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::new()
+    }
+}
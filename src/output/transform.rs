@@ -5,41 +5,406 @@
 // https://users.rust-lang.org/t/using-an-option-mut-t-in-a-loop-clippy-complains/72481/2
 #![allow(clippy::needless_option_as_deref)]
 
-use super::cssbuf::{CssBuf, CssHead};
-use crate::css::{BodyItem, Comment, Import, Property, Rule, Selectors};
+use super::cssbuf::{CssHead, CssItems, ModuleCss};
+use super::Format;
+use crate::css;
+use crate::css::{
+    AtRule, AtRuleBodyItem, BodyItem, Comment, Import, Property, Rule,
+    Selectors,
+};
 use crate::error::{Error, Invalid};
-use crate::input::{Context, Loader, Parsed, SourceKind};
-use crate::sass::{get_global_module, Expose, Item, UseAs};
+use crate::input::{
+    Context, Loader, ModuleInit, Parsed, SourceFile, SourceKind,
+};
+use crate::sass::{
+    get_global_module, load_css_args, CallArgs, Config, Expose, Item, Mixin,
+    MixinDecl, MixinItem, Name, UseAs, Value,
+};
 use crate::value::ValueRange;
-use crate::ScopeRef;
-use std::io::Write;
+use crate::{ScopeRef, SourcePos};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Load a module, or get it if it is already loaded.
+///
+/// This is the common implementation of `@use`, `@forward` and the
+/// `meta.load-css` mixin.
+/// A module is only evaluated once, and its css is added to `buf`
+/// when it is evaluated.
+/// If `buf` is the output buffer, only the range of the output is
+/// recorded as the css of the module (or nothing, if the output is
+/// written as it is evaluated), otherwise a copy is kept.
+/// The `sourcefile` is unlocked when the module is loaded.
+fn load_module(
+    sourcefile: &SourceFile,
+    config: Config,
+    pos: &SourcePos,
+    head: &mut CssHead,
+    buf: &mut CssItems,
+    format: Format,
+    file_context: &mut Context<impl Loader>,
+) -> Result<ScopeRef, Error> {
+    let name = match sourcefile.source().imported {
+        SourceKind::Call(..) => Some(sourcefile.source().name()),
+        _ => None,
+    };
+    let module = head.load_module(
+        sourcefile.canonical_url(),
+        name,
+        &config,
+        pos,
+        |head| {
+            let module = ScopeRef::new_module(format, config.clone());
+            let parsed = file_context.parse(sourcefile)?;
+            if buf.is_output() {
+                head.flush(buf)?;
+                let start = head.start_module();
+                handle_parsed(
+                    &parsed,
+                    head,
+                    None,
+                    buf,
+                    module.clone(),
+                    file_context,
+                )?;
+                head.flush(buf)?;
+                Ok((module, head.end_module(start)))
+            } else {
+                let start = buf.len();
+                handle_parsed(
+                    &parsed,
+                    head,
+                    None,
+                    buf,
+                    module.clone(),
+                    file_context,
+                )?;
+                Ok((module, ModuleCss::Items(buf.items_from(start))))
+            }
+        },
+    )?;
+    file_context.unlock_loading(sourcefile);
+    Ok(module)
+}
+
+/// Load a custom built-in module, or get it if it is already loaded.
+///
+/// The module scope is created and `init` is called to define its
+/// members the first time the module is loaded.
+fn load_custom_module(
+    name: &str,
+    init: &ModuleInit,
+    config: Config,
+    pos: &SourcePos,
+    head: &mut CssHead,
+    format: Format,
+) -> Result<ScopeRef, Error> {
+    head.load_module(name, None, &config, pos, |_| {
+        let module = ScopeRef::new_module(format, config.clone());
+        init(&module).map_err(|e| match e {
+            Error::BadCall(..) | Error::Invalid(..) => e,
+            e => Error::BadCall(e.to_string(), pos.clone(), None),
+        })?;
+        Ok((module, ModuleCss::Items(vec![])))
+    })
+}
+
+/// Evaluate the `with` configuration of a `@use` rule.
+fn eval_config(
+    with: &[(Name, Value, bool, SourcePos)],
+    scope: &ScopeRef,
+    pos: &SourcePos,
+) -> Result<Config, Error> {
+    let mut values = BTreeMap::new();
+    for (name, value, _default, vpos) in with {
+        let value = value.do_evaluate(scope.clone(), true)?;
+        values.insert(name.clone(), (value, vpos.clone()));
+    }
+    Ok(Config::new(values, pos))
+}
+
+/// Get the configuration for a module loaded by `@forward`.
+///
+/// The configuration of the forwarding module (as seen through the
+/// `@forward` rule) is passed on, with the values of the `with`
+/// clause added.
+/// A `!default` value in the `with` clause is only used if the
+/// forwarding module is not configured with a non-null value for
+/// the same variable, and no such variable is defined.
+fn forward_config(
+    with: &[(Name, Value, bool, SourcePos)],
+    outer: &Config,
+    scope: &ScopeRef,
+    pos: &SourcePos,
+) -> Result<Config, Error> {
+    if with.is_empty() {
+        return Ok(outer.clone());
+    }
+    let mut values = outer.entries();
+    for (name, value, default, vpos) in with {
+        if *default {
+            match outer.take(name) {
+                Some(old) if !old.0.is_null() => {
+                    values.insert(name.clone(), old);
+                    continue;
+                }
+                _ => (),
+            }
+            // The variables of an importing file work as an implicit
+            // configuration.
+            if let Some(value) = scope.get_or_none(name) {
+                if !value.is_null() {
+                    values.insert(name.clone(), (value, vpos.clone()));
+                    continue;
+                }
+            }
+        }
+        let value = value.do_evaluate(scope.clone(), true)?;
+        values.insert(name.clone(), (value, vpos.clone()));
+    }
+    Ok(Config::new(values, pos))
+}
+
+/// Check the configuration after loading a module by `@forward`.
+///
+/// Values of the `outer` configuration that are passed on and used
+/// by the forwarded module are taken from the `outer` configuration.
+/// It is an error if a value of the `with` clause is not used.
+fn check_forwarded_config(
+    with: &[(Name, Value, bool, SourcePos)],
+    outer: &Config,
+    config: &Config,
+) -> Result<(), Error> {
+    if !with.is_empty() {
+        for name in outer.entries().keys() {
+            let overridden = with.iter().any(|(n, _, d, _)| n == name && !d);
+            if !overridden && !config.contains(name) {
+                outer.take(name);
+            }
+        }
+        let unused = config
+            .entries()
+            .into_iter()
+            .find(|(n, _)| with.iter().any(|(name, ..)| name == n));
+        if let Some((_, (_, pos))) = unused {
+            return Err(not_default(pos));
+        }
+    }
+    Ok(())
+}
+
+/// Check that no variable is configured twice in a `with` clause.
+fn check_config(
+    with: &[(Name, Value, bool, SourcePos)],
+) -> Result<(), Error> {
+    let mut seen = BTreeSet::new();
+    for (name, _, _, pos) in with {
+        if !seen.insert(name) {
+            return Err(Error::BadCall(
+                "The same variable may only be configured once.".into(),
+                pos.clone(),
+                None,
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn not_default(pos: SourcePos) -> Error {
+    Error::BadCall(
+        "This variable was not declared with !default in the @used module."
+            .into(),
+        pos,
+        None,
+    )
+}
+
+/// Include the css of a module, for the `meta.load-css` mixin.
+///
+/// The module is loaded like by `@use`, and its css is added in
+/// the current selector context, each time this is called.
+#[allow(clippy::too_many_arguments)]
+fn load_css(
+    url: &str,
+    config: Config,
+    pos: &SourcePos,
+    head: &mut CssHead,
+    rule: Option<&mut Rule>,
+    buf: &mut CssItems,
+    scope: &ScopeRef,
+    file_context: &mut Context<impl Loader>,
+) -> Result<(), Error> {
+    let sourcefile = file_context
+        .find_file(url, SourceKind::load_css(pos))?
+        .ok_or_else(|| {
+            Error::BadCall(
+                "Can't find stylesheet to import.".into(),
+                pos.clone(),
+                None,
+            )
+        })?;
+    load_module(
+        &sourcefile,
+        config.clone(),
+        pos,
+        head,
+        &mut CssItems::new(),
+        scope.get_format(),
+        file_context,
+    )?;
+    if let Some((name, pos)) = config.unused() {
+        return Err(Error::BadCall(
+            format!(
+                "${} was not declared with !default in the @used module.",
+                name
+            ),
+            pos,
+            None,
+        ));
+    }
+    let url = sourcefile.canonical_url();
+    let items = match head.module_css(url) {
+        Some(items) => items,
+        None => head.evaluate_again(url, |head, config| {
+            let module = ScopeRef::new_module(scope.get_format(), config);
+            let parsed = file_context.parse(&sourcefile)?;
+            let mut buf = CssItems::new();
+            handle_parsed(
+                &parsed,
+                head,
+                None,
+                &mut buf,
+                module,
+                file_context,
+            )?;
+            Ok(buf.into_items())
+        })?,
+    };
+    let selectors = scope.get_selectors();
+    let mut rule = rule;
+    let mut first = true;
+    for item in items {
+        match item {
+            css::Item::Separator => buf.do_separate(),
+            css::Item::Rule(r) if !selectors.is_root() => {
+                let mut nested = r;
+                nested.selectors = nested.selectors.inside(selectors);
+                buf.push_rule(nested);
+            }
+            css::Item::AtRule(r) if !selectors.is_root() => {
+                buf.push(r.nested_in(selectors));
+            }
+            css::Item::Comment(c) if rule.is_some() => {
+                if let Some(rule) = rule.as_deref_mut() {
+                    rule.push(BodyItem::Comment(c));
+                }
+            }
+            item => {
+                if first && rule.is_none() {
+                    buf.do_separate();
+                }
+                buf.push(item);
+            }
+        }
+        first = false;
+    }
+    Ok(())
+}
 
 pub fn handle_parsed(
-    items: Parsed,
+    items: &Parsed,
     head: &mut CssHead,
     rule: Option<&mut Rule>,
-    buf: &mut CssBuf,
+    buf: &mut CssItems,
     scope: ScopeRef,
     file_context: &mut Context<impl Loader>,
 ) -> Result<(), Error> {
     match items {
         Parsed::Scss(items) => {
-            handle_body(&items, head, rule, buf, scope, file_context)
+            handle_body(items, head, rule, buf, scope, file_context)
         }
         Parsed::Css(items) => {
             for item in items {
-                item.write(buf)?;
+                buf.push(item.clone());
+            }
+            head.flush(buf)
+        }
+    }
+}
+
+/// Handle the items emitted by a mixin implemented in rust.
+fn handle_mixin_items(
+    items: Vec<MixinItem>,
+    head: &mut CssHead,
+    rule: Option<&mut Rule>,
+    buf: &mut CssItems,
+    scope: ScopeRef,
+    file_context: &mut Context<impl Loader>,
+    pos: &SourcePos,
+) -> Result<(), Error> {
+    let mut rule = rule;
+    for item in items {
+        match item {
+            MixinItem::Body(BodyItem::Comment(c)) if rule.is_none() => {
+                buf.push(c);
+            }
+            MixinItem::Body(item) => {
+                if let Some(rule) = rule.as_deref_mut() {
+                    rule.push(item);
+                } else {
+                    return Err(Error::S(
+                        "Global property not allowed".into(),
+                    ));
+                }
+            }
+            MixinItem::Rule(selectors, body) => {
+                if rule.is_none() {
+                    buf.do_separate();
+                }
+                let selectors = selectors.inside(scope.get_selectors());
+                let mut rule =
+                    Rule::new(selectors.clone()).with_pos(pos.clone());
+                let mut sub = CssItems::new_as(buf);
+                handle_mixin_items(
+                    body,
+                    head,
+                    Some(&mut rule),
+                    &mut sub,
+                    ScopeRef::sub_selectors(scope.clone(), selectors),
+                    file_context,
+                    pos,
+                )?;
+                buf.push_rule(rule);
+                buf.join(sub);
+            }
+            MixinItem::Content => {
+                let content = Item::Content(CallArgs::default(), pos.clone());
+                handle_item(
+                    &content,
+                    head,
+                    rule.as_deref_mut(),
+                    buf,
+                    scope.clone(),
+                    file_context,
+                )?;
             }
-            Ok(())
         }
     }
+    Ok(())
+}
+
+/// Report an error from the body of a mixin at the call `pos`.
+fn mixin_error(e: Error, name: &str, pos: &SourcePos) -> Error {
+    match e {
+        Error::Invalid(err, _) => err.at(pos.clone()),
+        e => Error::BadCall(e.to_string(), pos.in_call(name), None),
+    }
 }
 
 fn handle_body(
     items: &[Item],
     head: &mut CssHead,
     rule: Option<&mut Rule>,
-    buf: &mut CssBuf,
+    buf: &mut CssItems,
     scope: ScopeRef,
     file_context: &mut Context<impl Loader>,
 ) -> Result<(), Error> {
@@ -53,6 +418,7 @@ fn handle_body(
             scope.clone(),
             file_context,
         )?;
+        head.flush(buf)?;
     }
     Ok(())
 }
@@ -61,13 +427,14 @@ fn handle_item(
     item: &Item,
     head: &mut CssHead,
     rule: Option<&mut Rule>,
-    buf: &mut CssBuf,
+    buf: &mut CssItems,
     scope: ScopeRef,
     file_context: &mut Context<impl Loader>,
 ) -> Result<(), Error> {
     let format = scope.get_format();
     match item {
         Item::Use(ref name, ref as_n, ref with, ref pos) => {
+            check_config(with)?;
             let name = name.evaluate(scope.clone())?.take_value();
             let module = if let Some(module) = get_global_module(&name) {
                 if !with.is_empty() {
@@ -78,41 +445,36 @@ fn handle_item(
                     ));
                 }
                 module
+            } else if let Some(init) = file_context.custom_module(&name) {
+                let config = eval_config(with, &scope, pos)?;
+                let module = load_custom_module(
+                    &name,
+                    init,
+                    config.clone(),
+                    pos,
+                    head,
+                    format,
+                )?;
+                if let Some((_, pos)) = config.unused() {
+                    return Err(not_default(pos));
+                }
+                module
             } else if let Some(sourcefile) =
                 file_context.find_file(&name, SourceKind::Use(pos.clone()))?
             {
-                let module = head.load_module(
-                    sourcefile.path(),
-                    |head| {
-                        let module = ScopeRef::new_global(format);
-                        for (name, value, default) in with {
-                            let default = if *default {
-                                scope.get_or_none(name)
-                            } else {
-                                None
-                            };
-                            let value = default.ok_or(()).or_else(|()| {
-                                value.do_evaluate(scope.clone(), true)
-                            })?;
-                            if module.get_or_none(name).is_none() {
-                                module.define(name.clone(), value)?;
-                            } else {
-                                return Err(Error::error(
-                                    "The same variable may only be configured once.",
-                                ));
-                            }
-                        }
-                        handle_parsed(
-                            sourcefile.parse()?,
-                            head,
-                            None,
-                            buf,
-                            module.clone(),
-                            file_context,
-                        )?;
-                        Ok(module)
-                    })?;
-                file_context.unlock_loading(&sourcefile);
+                let config = eval_config(with, &scope, pos)?;
+                let module = load_module(
+                    &sourcefile,
+                    config.clone(),
+                    pos,
+                    head,
+                    buf,
+                    format,
+                    file_context,
+                )?;
+                if let Some((_, pos)) = config.unused() {
+                    return Err(not_default(pos));
+                }
                 module
             } else {
                 return Err(Error::BadCall(
@@ -124,6 +486,7 @@ fn handle_item(
             scope.do_use(module, &name, as_n, &Expose::All)?;
         }
         Item::Forward(ref name, ref as_n, ref expose, ref with, ref pos) => {
+            check_config(with)?;
             let name = name.evaluate(scope.clone())?.take_value();
             let module = if let Some(module) = get_global_module(&name) {
                 if !with.is_empty() {
@@ -134,42 +497,35 @@ fn handle_item(
                     ));
                 }
                 module
+            } else if let Some(init) = file_context.custom_module(&name) {
+                let outer = scope.config().through_forward(as_n, expose);
+                let config = forward_config(with, &outer, &scope, pos)?;
+                let module = load_custom_module(
+                    &name,
+                    init,
+                    config.clone(),
+                    pos,
+                    head,
+                    format,
+                )?;
+                check_forwarded_config(with, &outer, &config)?;
+                module
             } else if let Some(sourcefile) = file_context
                 .find_file(&name, SourceKind::Forward(pos.clone()))?
             {
-                let module = head.load_module(
-                    sourcefile.path(),
-                    |head| {
-                        let module = ScopeRef::new_global(format);
-                        for (name, value, default) in with {
-                            let default = if *default {
-                                scope.get_or_none(name)
-                            } else {
-                                None
-                            };
-                            let value = default.ok_or(()).or_else(|()| {
-                                value.do_evaluate(scope.clone(), true)
-                            })?;
-                            if module.get_or_none(name).is_none() {
-                                module.define(name.clone(), value)?;
-                            } else {
-                                return Err(Error::error(
-                                    "The same variable may only be configured once.",
-                                ));
-                            }
-                        }
-                        handle_parsed(
-                            sourcefile.parse()?,
-                            head,
-                            None,
-                            buf,
-                            module.clone(),
-                            file_context,
-                        )?;
-                        Ok(module)
-                    });
-                file_context.unlock_loading(&sourcefile);
-                module?
+                let outer = scope.config().through_forward(as_n, expose);
+                let config = forward_config(with, &outer, &scope, pos)?;
+                let module = load_module(
+                    &sourcefile,
+                    config.clone(),
+                    pos,
+                    head,
+                    buf,
+                    format,
+                    file_context,
+                )?;
+                check_forwarded_config(with, &outer, &config)?;
+                module
             } else {
                 return Err(Error::S(format!("Module {} not found", name)));
             };
@@ -184,19 +540,20 @@ fn handle_item(
                     if let Some(sourcefile) = file_context
                         .find_file(x, SourceKind::Import(pos.clone()))?
                     {
-                        match sourcefile.parse()? {
+                        match &*file_context.parse(&sourcefile)? {
                             Parsed::Scss(items) => {
-                                let mut thead = CssHead::new();
-                                let module = ScopeRef::sub(scope.clone());
-                                handle_body(
-                                    &items,
-                                    &mut thead,
-                                    rule.as_deref_mut(),
-                                    buf,
-                                    module.clone(),
-                                    file_context,
-                                )?;
-                                head.merge_imports(thead);
+                                let module =
+                                    ScopeRef::sub_import(scope.clone());
+                                head.with_new_modules(|head| {
+                                    handle_body(
+                                        items,
+                                        head,
+                                        rule.as_deref_mut(),
+                                        buf,
+                                        module.clone(),
+                                        file_context,
+                                    )
+                                })?;
                                 scope.do_use(
                                     module,
                                     "",
@@ -206,7 +563,7 @@ fn handle_item(
                             }
                             Parsed::Css(items) => {
                                 for item in items {
-                                    item.write(buf)?;
+                                    buf.push(item.clone());
                                 }
                             }
                         }
@@ -233,18 +590,18 @@ fn handle_item(
                 {
                     rule.push(import.into());
                 } else if buf.is_root_level() {
-                    head.add_import(import);
+                    head.add_import(import, pos)?;
                 } else {
-                    import.write(buf)?;
+                    buf.push(import);
                 }
             }
         }
-        Item::AtRoot(ref selectors, ref body) => {
+        Item::AtRoot(ref selectors, ref body, _) => {
             let selectors = selectors
                 .eval(scope.clone())?
                 .with_backref(scope.get_selectors().one());
             let mut rule = Rule::new(selectors.clone());
-            let mut sub = CssBuf::new_as(buf);
+            let mut sub = CssItems::new_as(buf);
             handle_body(
                 body,
                 head,
@@ -253,29 +610,23 @@ fn handle_item(
                 ScopeRef::sub_selectors(scope, selectors),
                 file_context,
             )?;
-            rule.write(buf)?;
+            buf.push_rule(rule);
             buf.join(sub);
         }
         Item::AtRule {
             name,
             args,
             body,
-            pos: _,
+            pos,
         } => {
             buf.do_separate();
-            buf.do_indent_no_nl();
             let name = name.evaluate(scope.clone())?;
-            write!(buf, "@{}", name.value())?;
             let args = args.evaluate(scope.clone())?;
-            if !args.is_null() {
-                write!(buf, " {}", args.format(format))?;
-            }
-            if let Some(ref body) = *body {
-                buf.start_block();
+            let body = if let Some(ref body) = *body {
                 let selectors = scope.get_selectors().clone();
                 let has_selectors = !selectors.is_root();
                 let mut rule = Rule::new(selectors);
-                let mut sub = CssBuf::new_as(buf);
+                let mut sub = CssItems::new_nested();
                 handle_body(
                     body,
                     head,
@@ -284,18 +635,21 @@ fn handle_item(
                     ScopeRef::sub(scope),
                     file_context,
                 )?;
+                let mut items = Vec::<AtRuleBodyItem>::new();
                 if has_selectors {
-                    rule.write(buf)?;
-                } else {
-                    for item in &rule.body {
-                        item.write(buf)?;
+                    if !rule.body.is_empty() {
+                        items.push(rule.into());
                     }
+                } else {
+                    items.extend(rule.body.into_iter().map(Into::into));
                 };
-                buf.join(sub);
-                buf.end_block();
+                items.extend(sub.into_items().into_iter().map(Into::into));
+                Some(items)
             } else {
-                buf.add_one(";\n", ";");
-            }
+                None
+            };
+            let rule = AtRule::new(name.take_value(), args, body);
+            buf.push(rule.with_pos(pos.clone()));
         }
 
         Item::VariableDeclaration {
@@ -305,9 +659,18 @@ fn handle_item(
             global,
             ref pos,
         } => {
-            let val = val.do_evaluate(scope.clone(), true)?;
+            let configured = if *default {
+                scope.take_configured(name)
+            } else {
+                None
+            };
+            let default = *default && configured.is_none();
+            let val = match configured {
+                Some(val) => val,
+                None => val.do_evaluate(scope.clone(), true)?,
+            };
             scope
-                .set_variable(name.clone(), val, *default, *global)
+                .set_variable(name.clone(), val, default, *global)
                 .map_err(|e| e.at(pos.clone()))?;
         }
         Item::FunctionDeclaration(ref name, ref body) => {
@@ -331,50 +694,73 @@ fn handle_item(
             check_body(&body.body, BodyContext::Mixin)?;
             scope.define_mixin(name.into(), body.closure(&scope).into())
         }
-        Item::MixinCall(ref name, ref args, ref body, ref pos) => {
-            if let Some(mixin) = scope.get_mixin(&name.into()) {
-                let mixin = mixin.get(
-                    name,
-                    scope.clone(),
-                    args,
-                    pos,
-                    file_context,
-                )?;
+        Item::MixinCall(ref name, ref args, ref body, ref pos) => match scope
+            .get_mixin(&name.into())
+        {
+            Some(MixinDecl::LoadCss) => {
+                if let Some((url, config)) =
+                    load_css_args(scope.clone(), args, pos)?
+                {
+                    load_css(
+                        &url,
+                        config,
+                        pos,
+                        head,
+                        rule,
+                        buf,
+                        &scope,
+                        file_context,
+                    )?;
+                }
+            }
+            Some(MixinDecl::Builtin(mixin)) => {
+                let mscope =
+                    mixin.eval_args(name, scope.clone(), args, pos)?;
+                Mixin::define_content_in(&mscope, &scope, body);
+                mixin
+                    .emit(&mscope, body.is_some())
+                    .and_then(|items| {
+                        handle_mixin_items(
+                            items,
+                            head,
+                            rule,
+                            buf,
+                            mscope,
+                            file_context,
+                            pos,
+                        )
+                    })
+                    .map_err(|e| mixin_error(e, name, pos))?;
+            }
+            Some(MixinDecl::Sass(decl)) => {
+                let mixin =
+                    Mixin::for_call(decl, name, scope.clone(), args, pos)?;
                 mixin.define_content(&scope, body);
                 handle_parsed(
-                    mixin.body,
+                    &mixin.body,
                     head,
                     rule,
                     buf,
                     mixin.scope,
                     file_context,
                 )
-                .map_err(|e: Error| match e {
-                    Error::Invalid(err, _) => err.at(pos.clone()),
-                    e => {
-                        let pos = pos.in_call(name);
-                        Error::BadCall(e.to_string(), pos, None)
-                    }
-                })?;
-            } else {
+                .map_err(|e| mixin_error(e, name, pos))?;
+            }
+            Some(MixinDecl::NoBody) => (),
+            None => {
                 return Err(Error::BadCall(
                     "Undefined mixin.".into(),
                     pos.clone(),
                     None,
                 ));
             }
-        }
+        },
         Item::Content(args, pos) => {
-            if let Some(content) = scope.get_content() {
-                let mixin = content.get(
-                    "@content",
-                    scope,
-                    args,
-                    pos,
-                    file_context,
-                )?;
+            if let Some(Some(content)) = scope.get_content() {
+                let mixin =
+                    Mixin::for_call(content, "@content", scope, args, pos)?;
                 handle_parsed(
-                    mixin.body,
+                    &mixin.body,
                     head,
                     rule,
                     buf,
@@ -384,13 +770,13 @@ fn handle_item(
             }
         }
 
-        Item::IfStatement(ref cond, ref do_if, ref do_else) => {
+        Item::IfStatement(ref cond, ref do_if, ref do_else, _) => {
             let cond = cond.evaluate(scope.clone())?.is_true();
             let items = if cond { do_if } else { do_else };
             check_body(items, BodyContext::Control)?;
             handle_body(items, head, rule, buf, scope, file_context)?;
         }
-        Item::Each(ref names, ref values, ref body) => {
+        Item::Each(ref names, ref values, ref body, _) => {
             check_body(body, BodyContext::Control)?;
             let mut rule = rule;
             let pushed = scope.store_local_values(names);
@@ -413,6 +799,7 @@ fn handle_item(
             ref to,
             inclusive,
             ref body,
+            pos: _,
         } => {
             let range = ValueRange::new(
                 from.evaluate(scope.clone())?,
@@ -434,7 +821,7 @@ fn handle_item(
                 )?;
             }
         }
-        Item::While(ref cond, ref body) => {
+        Item::While(ref cond, ref body, _) => {
             check_body(body, BodyContext::Control)?;
             let mut rule = rule;
             let scope = ScopeRef::sub(scope);
@@ -450,10 +837,10 @@ fn handle_item(
             }
         }
 
-        Item::Debug(ref value) => {
+        Item::Debug(ref value, _) => {
             eprintln!("DEBUG: {}", value.evaluate(scope)?.format(format));
         }
-        Item::Warn(ref value) => {
+        Item::Warn(ref value, _) => {
             eprintln!("WARNING: {}", value.evaluate(scope)?.format(format));
         }
         Item::Error(ref value, ref pos) => {
@@ -463,15 +850,15 @@ fn handle_item(
             .at(pos.clone()));
         }
 
-        Item::Rule(ref selectors, ref body) => {
+        Item::Rule(ref selectors, ref body, ref pos) => {
             check_body(body, BodyContext::Rule)?;
             if rule.is_none() {
                 buf.do_separate();
             }
             let selectors =
                 selectors.eval(scope.clone())?.inside(scope.get_selectors());
-            let mut rule = Rule::new(selectors.clone());
-            let mut sub = CssBuf::new_as(buf);
+            let mut rule = Rule::new(selectors.clone()).with_pos(pos.clone());
+            let mut sub = CssItems::new_as(buf);
             handle_body(
                 body,
                 head,
@@ -480,21 +867,22 @@ fn handle_item(
                 ScopeRef::sub_selectors(scope, selectors),
                 file_context,
             )?;
-            rule.write(buf)?;
+            buf.push_rule(rule);
             buf.join(sub);
         }
-        Item::Property(ref name, ref value) => {
+        Item::Property(ref name, ref value, ref pos) => {
             if let Some(rule) = rule {
                 let v = value.evaluate(scope.clone())?;
                 if !v.is_null() {
                     let name = name.evaluate(scope)?;
-                    rule.push(Property::new(name.value().into(), v).into());
+                    let prop = Property::new(name.value().into(), v);
+                    rule.push(prop.with_pos(pos.clone()).into());
                 }
             } else {
                 return Err(Error::S("Global property not allowed".into()));
             }
         }
-        Item::CustomProperty(ref name, ref value) => {
+        Item::CustomProperty(ref name, ref value, _) => {
             if let Some(rule) = rule {
                 let v = value.evaluate(scope.clone())?;
                 if !v.is_null() {
@@ -510,7 +898,7 @@ fn handle_item(
                 ));
             }
         }
-        Item::NamespaceRule(ref name, ref value, ref body) => {
+        Item::NamespaceRule(ref name, ref value, ref body, _) => {
             if let Some(rule) = rule {
                 check_body(body, BodyContext::NsRule)?;
                 let value = value.evaluate(scope.clone())?;
@@ -521,7 +909,7 @@ fn handle_item(
                     );
                 }
                 let mut t = Rule::new(Selectors::root());
-                let mut sub = CssBuf::new(format);
+                let mut sub = CssItems::new();
                 handle_body(
                     body,
                     head,
@@ -549,18 +937,18 @@ fn handle_item(
                 ));
             }
         }
-        Item::Comment(ref c) => {
+        Item::Comment(ref c, _) => {
             if !format.is_compressed() {
                 let c = Comment::from(c.evaluate(scope)?.value());
                 if let Some(rule) = rule {
                     rule.push(c.into());
                 } else {
-                    c.write(buf);
+                    buf.push(c);
                 }
             }
         }
 
-        Item::None => (),
+        Item::SilentComment(..) | Item::None => (),
     }
     Ok(())
 }
@@ -6,7 +6,8 @@ mod values;
 pub(crate) use self::selectors::{selector, selector_part, selectors};
 
 use super::{util::opt_spacelike, PResult, Span};
-use crate::css::{Comment, Import, Item, Value};
+use crate::css::{AtRule, Comment, CssString, Import, Item, Value};
+use crate::SourcePos;
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag, tag_no_case};
 use nom::combinator::{
@@ -43,29 +44,38 @@ fn top_level_item(input: Span) -> PResult<Item> {
             if name == "import" {
                 into(import2)(input)
             } else {
+                let pos = SourcePos::from_to(rest, input).opt_back("@");
                 let (input, args) = map_res(media_args, |s| {
                     std::str::from_utf8(s.fragment())
                 })(input)?;
                 let (input, body) = preceded(
                     opt_spacelike,
                     alt((
-                        delimited(
-                            terminated(tag("{"), opt_spacelike),
-                            many0(terminated(
-                                alt((
-                                    into(comment),
-                                    into(preceded(tag("@import"), import2)),
-                                    into(rule::rule),
-                                    into(rule::property),
+                        map(
+                            delimited(
+                                terminated(tag("{"), opt_spacelike),
+                                many0(terminated(
+                                    alt((
+                                        into(comment),
+                                        into(preceded(
+                                            tag("@import"),
+                                            import2,
+                                        )),
+                                        into(rule::rule),
+                                        into(rule::property),
+                                    )),
+                                    opt_spacelike,
                                 )),
-                                opt_spacelike,
-                            )),
-                            tag("}"),
+                                tag("}"),
+                            ),
+                            Some,
                         ),
-                        map(tag(";"), |_| Vec::new()),
+                        map(tag(";"), |_| None),
                     )),
                 )(input)?;
-                Ok((input, Item::AtRule(name, args.trim().to_string(), body)))
+                let args = CssString::from(args.trim()).into();
+                let rule = AtRule::new(name, args, body).with_pos(pos);
+                Ok((input, rule.into()))
             }
         }
         _ => into(rule::rule)(input),
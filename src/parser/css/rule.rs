@@ -4,6 +4,7 @@ use super::super::util::opt_spacelike;
 use super::super::{PResult, Span};
 use super::{comment, import2, selectors, strings, values};
 use crate::css::{BodyItem, Property, Rule};
+use crate::SourcePos;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::combinator::{into, map, opt};
@@ -11,13 +12,19 @@ use nom::multi::many_till;
 use nom::sequence::{pair, preceded, terminated};
 
 pub fn rule(input: Span) -> PResult<Rule> {
+    let (end, selectors) = selectors(input)?;
+    let pos = SourcePos::from_to(input, end);
     map(
-        pair(
-            terminated(selectors, terminated(tag("{"), opt_spacelike)),
+        preceded(
+            terminated(tag("{"), opt_spacelike),
             many_till(terminated(body_item, opt_spacelike), tag("}")),
         ),
-        |(selectors, (body, _))| Rule { selectors, body },
-    )(input)
+        move |(body, _)| Rule {
+            selectors: selectors.clone(),
+            body,
+            pos: Some(pos.clone()),
+        },
+    )(end)
 }
 
 fn body_item(input: Span) -> PResult<BodyItem> {
@@ -29,13 +36,13 @@ fn body_item(input: Span) -> PResult<BodyItem> {
 }
 
 pub fn property(input: Span) -> PResult<Property> {
-    map(
-        pair(
-            terminated(property_name, terminated(tag(":"), opt_spacelike)),
-            terminated(values::any, opt(tag(";"))),
-        ),
-        |(name, val)| Property::new(name, val),
-    )(input)
+    let (end, (name, val)) = pair(
+        terminated(property_name, terminated(tag(":"), opt_spacelike)),
+        values::any,
+    )(input)?;
+    let pos = SourcePos::from_to(input, end);
+    let (rest, _) = opt(tag(";"))(end)?;
+    Ok((rest, Property::new(name, val).with_pos(pos)))
 }
 
 fn property_name(input: Span) -> PResult<String> {
@@ -1,6 +1,6 @@
 //! The `calc` function is special.  A css function that is partially evaluated in sass.
 //! This should apply to `min`, `max` and `clamp` as well.
-use super::util::{opt_spacelike, spacelike2};
+use super::util::{opt_spacelike, positioned, spacelike2};
 use super::value::{function_call, number, special_function, variable};
 use super::{ignore_comments, PResult, SourcePos, Span};
 use crate::sass::{CallArgs, Value};
@@ -8,7 +8,7 @@ use crate::value::Operator;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::character::complete::multispace0;
-use nom::combinator::{into, map, not, peek, value};
+use nom::combinator::{map, not, peek, value};
 use nom::sequence::{delimited, preceded, terminated, tuple};
 
 pub fn css_function(input: Span) -> PResult<Value> {
@@ -47,7 +47,8 @@ fn sum_expression(input: Span) -> PResult<Value> {
         )),
     ))(rest)
     {
-        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2));
+        let pos = SourcePos::from_to(input, nrest);
+        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2), pos);
         rest = nrest;
     }
     Ok((rest, v))
@@ -67,7 +68,8 @@ fn term(input: Span) -> PResult<Value> {
     ))(rest)
     {
         rest = nrest;
-        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2));
+        let pos = SourcePos::from_to(input, nrest);
+        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2), pos);
     }
     Ok((rest, v))
 }
@@ -75,24 +77,24 @@ fn term(input: Span) -> PResult<Value> {
 fn single_value(input: Span) -> PResult<Value> {
     alt((
         paren,
-        value(Value::True, tag("true")),
-        value(Value::False, tag("false")),
-        value(Value::HereSelector, tag("&")),
-        into(number),
+        positioned(tag("true"), |_, pos| Value::True(pos)),
+        positioned(tag("false"), |_, pos| Value::False(pos)),
+        positioned(tag("&"), |_, pos| Value::HereSelector(pos)),
+        positioned(number, Value::Numeric),
         variable,
-        value(Value::Null, tag("null")),
+        positioned(tag("null"), |_, pos| Value::Null(pos)),
         special_function,
         function_call,
     ))(input)
 }
 
 fn paren(input: Span) -> PResult<Value> {
-    map(
+    positioned(
         delimited(
             terminated(tag("("), opt_spacelike),
             sum_expression,
             preceded(opt_spacelike, tag(")")),
         ),
-        |inner| Value::Paren(Box::new(inner), false),
+        |inner, pos| Value::Paren(Box::new(inner), false, pos),
     )(input)
 }
@@ -36,7 +36,8 @@ pub fn import2(input: Span) -> PResult<Item> {
         ),
         |(import, args, end)| {
             let pos = SourcePos::from_to(input, end);
-            Item::Import(import, args.unwrap_or(Value::Null), pos)
+            let args = args.unwrap_or_else(|| Value::Null(pos.clone()));
+            Item::Import(import, args, pos)
         },
     )(input)
 }
@@ -47,19 +48,21 @@ pub fn use2(input: Span) -> PResult<Item> {
             terminated(name, opt_spacelike), // the name is "use"
             tuple((
                 terminated(quoted_sass_string, opt_spacelike),
+                opt(preceded(terminated(tag("as"), opt_spacelike), as_arg)),
                 opt(preceded(
                     terminated(tag("with"), opt_spacelike),
                     with_arg,
                 )),
+                // Also accept `as` after `with`.
                 opt(preceded(terminated(tag("as"), opt_spacelike), as_arg)),
                 position,
             )),
             semi_or_end,
         ),
-        |(s, w, n, end)| {
+        |(s, n, w, n2, end)| {
             Item::Use(
                 s,
-                n.unwrap_or(UseAs::KeepName),
+                n.or(n2).unwrap_or(UseAs::KeepName),
                 w.unwrap_or_default(),
                 SourcePos::from_to(input, end).opt_back("@"),
             )
@@ -152,27 +155,34 @@ fn as_arg(input: Span) -> PResult<UseAs> {
     )(input)
 }
 
-fn with_arg(input: Span) -> PResult<Vec<(Name, Value, bool)>> {
+fn with_arg(input: Span) -> PResult<Vec<(Name, Value, bool, SourcePos)>> {
     delimited(
         terminated(tag("("), opt_spacelike),
-        separated_list0(
-            comma,
-            tuple((
-                delimited(
-                    tag("$"),
-                    map(name, Name::from),
-                    delimited(opt_spacelike, tag(":"), opt_spacelike),
-                ),
-                terminated(space_list, opt_spacelike),
-                map(opt(terminated(tag("!default"), opt_spacelike)), |o| {
-                    o.is_some()
-                }),
-            )),
-        ),
+        separated_list0(comma, with_var),
         delimited(opt(comma), tag(")"), opt_spacelike),
     )(input)
 }
 
+fn with_var(input: Span) -> PResult<(Name, Value, bool, SourcePos)> {
+    let (rest, (name, value, end)) = tuple((
+        delimited(
+            tag("$"),
+            map(name, Name::from),
+            delimited(opt_spacelike, tag(":"), opt_spacelike),
+        ),
+        space_list,
+        position,
+    ))(input)?;
+    let (rest, default) = delimited(
+        opt_spacelike,
+        map(opt(terminated(tag("!default"), opt_spacelike)), |o| {
+            o.is_some()
+        }),
+        opt_spacelike,
+    )(rest)?;
+    Ok((rest, (name, value, default, SourcePos::from_to(input, end))))
+}
+
 fn quoted_sass_string(input: Span) -> PResult<SassString> {
     alt((sass_string_dq, sass_string_sq))(input)
 }
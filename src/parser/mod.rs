@@ -12,6 +12,8 @@ mod css_function;
 mod error;
 pub mod formalargs;
 mod imports;
+#[cfg(test)]
+mod nopos;
 mod pos;
 pub mod selectors;
 pub(crate) mod strings;
@@ -28,8 +30,8 @@ use self::strings::{
     custom_value, name, sass_string, sass_string_dq, sass_string_sq,
 };
 use self::util::{
-    comment2, ignore_comments, ignore_space, opt_spacelike, semi_or_end,
-    spacelike,
+    comment2, ignore_comments, ignore_space, opt_space, opt_spacelike,
+    positioned, semi_or_end, spacelike,
 };
 use self::value::{
     dictionary, function_call, single_value, value_expression,
@@ -45,7 +47,7 @@ use nom::branch::alt;
 use nom::bytes::complete::{is_a, is_not, tag};
 use nom::character::complete::one_of;
 use nom::combinator::{
-    all_consuming, map, map_res, opt, peek, value, verify,
+    all_consuming, cond, map, map_res, opt, peek, value, verify,
 };
 use nom::multi::{
     fold_many0, many0, many_till, separated_list0, separated_list1,
@@ -53,6 +55,8 @@ use nom::multi::{
 use nom::sequence::{delimited, pair, preceded, terminated};
 use nom::IResult;
 use nom_locate::LocatedSpan;
+#[cfg(test)]
+use nopos::{mock_pos, NoPos};
 use std::str::{from_utf8, Utf8Error};
 
 pub type Span<'a> = LocatedSpan<&'a [u8], &'a SourceName>;
@@ -85,7 +89,10 @@ pub fn parse_value_data(data: &[u8]) -> Result<Value, Error> {
 #[test]
 fn test_parse_value_data_1() -> Result<(), Error> {
     let v = parse_value_data(b"17em")?;
-    assert_eq!(Value::Numeric(Numeric::new(17, Unit::Em)), v);
+    assert_eq!(
+        Value::Numeric(Numeric::new(17, Unit::Em), mock_pos()),
+        v.no_pos()
+    );
     Ok(())
 }
 
@@ -101,51 +108,77 @@ pub(crate) fn sassfile(input: Span) -> PResult<Vec<Item>> {
         opt(tag("\u{feff}".as_bytes())),
         map(
             many_till(
-                preceded(opt_spacelike, top_level_item),
-                all_consuming(opt_spacelike),
+                preceded(opt_space, top_level_item),
+                all_consuming(opt_space),
             ),
             |(v, _eof)| v,
         ),
     )(input)
 }
 
-fn top_level_item(input: Span) -> PResult<Item> {
-    let (input, tag) = alt((tag("$"), tag("/*"), tag("@"), tag("")))(input)?;
+fn top_level_item(input0: Span) -> PResult<Item> {
+    let (input, tag) =
+        alt((tag("$"), tag("/*"), tag("//"), tag("@"), tag("")))(input0)?;
     match *tag.fragment() {
         b"$" => variable_declaration2(input),
-        b"/*" => comment_item(input),
+        b"/*" => comment_item(input0),
+        b"//" => silent_comment_item(input0),
         b"@" => at_rule2(input),
         b"" => alt((variable_declaration_mod, rule))(input),
         _ => unreachable!(),
     }
 }
 
+/// A `/* ... */` comment, including the start tag.
 fn comment_item(input: Span) -> PResult<Item> {
-    map(comment2, Item::Comment)(input)
+    let (rest, comment) = preceded(tag("/*"), comment2)(input)?;
+    let pos = SourcePos::from_to(input, rest);
+    Ok((rest, Item::Comment(comment, pos)))
+}
+
+/// A `// ...` comment, including the start tag.
+fn silent_comment_item(input: Span) -> PResult<Item> {
+    let (rest, text) = preceded(
+        tag("//"),
+        map_res(opt(is_not("\r\n")), |text: Option<Span>| {
+            text.map_or(Ok(""), input_to_str)
+        }),
+    )(input)?;
+    let pos = SourcePos::from_to(input, rest);
+    Ok((rest, Item::SilentComment(text.into(), pos)))
 }
 
 fn rule(input: Span) -> PResult<Item> {
-    map(pair(rule_start, body_block2), |(selectors, body)| {
-        Item::Rule(selectors, body)
+    map(pair(rule_start, body_block2), |((selectors, pos), body)| {
+        Item::Rule(selectors, body, pos)
     })(input)
 }
 
-fn rule_start(input: Span) -> PResult<Selectors> {
-    terminated(selectors, terminated(opt(is_a(", \t\r\n")), tag("{")))(input)
+fn rule_start(input: Span) -> PResult<(Selectors, SourcePos)> {
+    let (end, selectors) = selectors(input)?;
+    let pos = SourcePos::from_to(input, end);
+    let (rest, _) = terminated(opt(is_a(", \t\r\n")), tag("{"))(end)?;
+    Ok((rest, (selectors, pos)))
 }
 
 fn body_item(input: Span) -> PResult<Item> {
-    let (rest, tag) =
-        alt((tag("$"), tag("/*"), tag(";"), tag("@"), tag("--"), tag("")))(
-            input,
-        )?;
+    let (rest, tag) = alt((
+        tag("$"),
+        tag("/*"),
+        tag("//"),
+        tag(";"),
+        tag("@"),
+        tag("--"),
+        tag(""),
+    ))(input)?;
     match *tag.fragment() {
         b"$" => variable_declaration2(rest),
-        b"/*" => comment_item(rest),
+        b"/*" => comment_item(input),
+        b"//" => silent_comment_item(input),
         b";" => Ok((rest, Item::None)),
         b"@" => at_rule2(rest),
         b"--" => {
-            let result = custom_property(rest);
+            let result = custom_property(input);
             if result.is_err() {
                 // Note use of `input` rather than `rest` here.
                 if let Ok((rest, rule)) = rule(input) {
@@ -155,9 +188,9 @@ fn body_item(input: Span) -> PResult<Item> {
             result
         }
         b"" => match rule_start(rest) {
-            Ok((rest, selectors)) => {
+            Ok((rest, (selectors, pos))) => {
                 let (rest, body) = body_block2(rest)?;
-                Ok((rest, Item::Rule(selectors, body)))
+                Ok((rest, Item::Rule(selectors, body, pos)))
             }
             Err(_) => property_or_namespace_rule(rest),
         },
@@ -166,17 +199,15 @@ fn body_item(input: Span) -> PResult<Item> {
 }
 
 /// What follows the `@at-root` tag.
-fn at_root2(input: Span) -> PResult<Item> {
-    preceded(
-        opt_spacelike,
-        map(
-            pair(
-                map(opt(selectors), |s| s.unwrap_or_else(Selectors::root)),
-                body_block,
-            ),
-            |(selectors, body)| Item::AtRoot(selectors, body),
-        ),
-    )(input)
+///
+/// The `start` is the `at-root` name, for the source position.
+fn at_root2<'a>(start: Span, input: Span<'a>) -> PResult<'a, Item> {
+    let (input, _) = opt_spacelike(input)?;
+    let (end, selectors) = opt(selectors)(input)?;
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (rest, body) = body_block(end)?;
+    let selectors = selectors.unwrap_or_else(Selectors::root);
+    Ok((rest, Item::AtRoot(selectors, body, pos)))
 }
 
 /// What follows the `@include` tag.
@@ -215,28 +246,34 @@ fn mixin_call2(input: Span) -> PResult<Item> {
 fn at_rule2(input0: Span) -> PResult<Item> {
     let (input, name) = terminated(name, opt_spacelike)(input0)?;
     match name.as_ref() {
-        "at-root" => at_root2(input),
+        "at-root" => at_root2(input0, input),
         "charset" => charset2(input),
         "content" => content_stmt2(input),
-        "debug" => map(expression_argument, Item::Debug)(input),
-        "each" => each_loop2(input),
+        "debug" => {
+            let (rest, (v, pos)) = expression_argument(input0, input)?;
+            Ok((rest, Item::Debug(v, pos)))
+        }
+        "each" => each_loop2(input0, input),
         "error" => {
             let (end, v) = value_expression(input)?;
             let (rest, _) = opt(tag(";"))(end)?;
             let pos = SourcePos::from_to(input0, end).opt_back("@");
             Ok((rest, Item::Error(v, pos)))
         }
-        "for" => for_loop2(input),
+        "for" => for_loop2(input0, input),
         "forward" => forward2(input0),
         "function" => function_declaration2(input),
-        "if" => if_statement2(input),
+        "if" => if_statement2(input0, input),
         "import" => import2(input),
         "include" => mixin_call2(input),
         "mixin" => mixin_declaration2(input),
         "return" => return_stmt2(input0, input),
         "use" => use2(input0),
-        "warn" => map(expression_argument, Item::Warn)(input),
-        "while" => while_loop2(input),
+        "warn" => {
+            let (rest, (v, pos)) = expression_argument(input0, input)?;
+            Ok((rest, Item::Warn(v, pos)))
+        }
+        "while" => while_loop2(input0, input),
         _ => {
             let (input, name) = sass_string(input0)?;
             let pos = SourcePos::from_to(input0, input).opt_back("@");
@@ -249,7 +286,7 @@ fn at_rule2(input0: Span) -> PResult<Item> {
                 input,
                 Item::AtRule {
                     name,
-                    args: args.unwrap_or(Value::Null),
+                    args: args.unwrap_or_else(|| Value::Null(pos.clone())),
                     body,
                     pos,
                 },
@@ -258,8 +295,17 @@ fn at_rule2(input0: Span) -> PResult<Item> {
     }
 }
 
-fn expression_argument(input: Span) -> PResult<Value> {
-    terminated(value_expression, opt(tag(";")))(input)
+/// The value of an `@debug` or `@warn` directive.
+///
+/// The `start` is the directive name, for the source position.
+fn expression_argument<'a>(
+    start: Span,
+    input: Span<'a>,
+) -> PResult<'a, (Value, SourcePos)> {
+    let (end, v) = value_expression(input)?;
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (rest, _) = opt(tag(";"))(end)?;
+    Ok((rest, (v, pos)))
 }
 
 fn charset2(input: Span) -> PResult<Item> {
@@ -282,49 +328,55 @@ fn charset2(input: Span) -> PResult<Item> {
 }
 
 fn media_args(input: Span) -> PResult<Value> {
-    let (input, args) = separated_list0(
+    let (rest, args) = separated_list0(
         preceded(tag(","), opt_spacelike),
-        map(
-            many0(preceded(
-                opt(ignore_space),
+        media_args_space_list,
+    )(input)?;
+    Ok((
+        rest,
+        if args.len() == 1 {
+            args.into_iter().next().unwrap()
+        } else {
+            let pos = SourcePos::from_to(input, rest);
+            Value::List(args, Some(ListSeparator::Comma), false, pos)
+        },
+    ))
+}
+
+fn media_args_space_list(input: Span) -> PResult<Value> {
+    let (rest, args) = many0(preceded(
+        opt(ignore_space),
+        alt((
+            terminated(
                 alt((
-                    terminated(
-                        alt((
-                            function_call,
-                            dictionary,
-                            map(
-                                delimited(tag("("), media_args, tag(")")),
-                                |v| Value::Paren(Box::new(v), true),
-                            ),
-                            map(sass_string, Value::Literal),
-                            map(sass_string_dq, Value::Literal),
-                            map(sass_string_sq, Value::Literal),
-                        )),
-                        alt((
-                            value((), all_consuming(tag(""))),
-                            value((), peek(one_of(") \r\n\t{,;"))),
-                        )),
+                    function_call,
+                    dictionary,
+                    positioned(
+                        delimited(tag("("), media_args, tag(")")),
+                        |v, pos| Value::Paren(Box::new(v), true, pos),
                     ),
-                    map(map_res(is_not("#()\"'{};, "), input_to_str), |s| {
-                        Value::Literal(s.trim_end().into())
-                    }),
+                    positioned(sass_string, Value::Literal),
+                    positioned(sass_string_dq, Value::Literal),
+                    positioned(sass_string_sq, Value::Literal),
                 )),
-            )),
-            |args| {
-                if args.len() == 1 {
-                    args.into_iter().next().unwrap()
-                } else {
-                    Value::List(args, Some(ListSeparator::Space), false)
-                }
-            },
-        ),
-    )(input)?;
+                alt((
+                    value((), all_consuming(tag(""))),
+                    value((), peek(one_of(") \r\n\t{,;"))),
+                )),
+            ),
+            positioned(
+                map_res(is_not("#()\"'{};, "), input_to_str),
+                |s, pos| Value::Literal(s.trim_end().into(), pos),
+            ),
+        )),
+    ))(input)?;
     Ok((
-        input,
+        rest,
         if args.len() == 1 {
             args.into_iter().next().unwrap()
         } else {
-            Value::List(args, Some(ListSeparator::Comma), false)
+            let pos = SourcePos::from_to(input, rest);
+            Value::List(args, Some(ListSeparator::Space), false, pos)
         },
     ))
 }
@@ -343,57 +395,65 @@ fn test_media_args_2() {
 
 #[cfg(test)] // TODO: Or remove this?
 fn if_statement(input: Span) -> PResult<Item> {
-    preceded(tag("@if "), if_statement2)(input)
+    let (rest, _) = tag("@if ")(input)?;
+    if_statement2(input, rest)
 }
 
 fn if_statement_inner(input: Span) -> PResult<Item> {
-    preceded(
-        terminated(verify(name, |n: &String| n == "if"), opt_spacelike),
-        if_statement2,
-    )(input)
+    let (rest, _) = terminated(
+        verify(name, |n: &String| n == "if"),
+        opt_spacelike,
+    )(input)?;
+    if_statement2(input, rest)
 }
 
-fn if_statement2(input: Span) -> PResult<Item> {
-    let (input, cond) = terminated(value_expression, opt_spacelike)(input)?;
-    let (input, body) = body_block(input)?;
-    let (input2, word) = opt(delimited(
-        preceded(opt_spacelike, tag("@")),
-        name,
-        opt_spacelike,
-    ))(input)?;
+/// What follows an `@if` (or `@elseif` or `@else if`).
+///
+/// The `start` is the `if` word, for the source position.
+fn if_statement2<'a>(start: Span, input: Span<'a>) -> PResult<'a, Item> {
+    let (end, cond) = value_expression(input)?;
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (input, body) = preceded(opt_spacelike, body_block)(end)?;
+    let (at, _) = opt_spacelike(input)?;
+    let (input2, word) = opt(delimited(tag("@"), name, opt_spacelike))(at)?;
     match word.as_ref().map(|w| w.as_ref()) {
         Some("else") => {
             let (input2, else_body) = alt((
                 body_block,
                 map(if_statement_inner, |s| vec![s]),
             ))(input2)?;
-            Ok((input2, Item::IfStatement(cond, body, else_body)))
+            Ok((input2, Item::IfStatement(cond, body, else_body, pos)))
         }
         Some("elseif") => {
-            let (input2, else_body) = if_statement2(input2)?;
-            Ok((input2, Item::IfStatement(cond, body, vec![else_body])))
+            let (word, _) = tag("@")(at)?;
+            let (input2, else_body) = if_statement2(word, input2)?;
+            Ok((input2, Item::IfStatement(cond, body, vec![else_body], pos)))
         }
-        _ => Ok((input, Item::IfStatement(cond, body, vec![]))),
+        _ => Ok((input, Item::IfStatement(cond, body, vec![], pos))),
     }
 }
 
 /// The part of an each look that follows the `@each`.
-fn each_loop2(input: Span) -> PResult<Item> {
+///
+/// The `start` is the `each` word, for the source position.
+fn each_loop2<'a>(start: Span, input: Span<'a>) -> PResult<'a, Item> {
     let (input, names) = separated_list1(
         delimited(opt_spacelike, tag(","), opt_spacelike),
         map(preceded(tag("$"), name), Name::from),
     )(input)?;
-    let (input, values) = delimited(
+    let (end, values) = preceded(
         delimited(spacelike, tag("in"), spacelike),
         value_expression,
-        opt_spacelike,
     )(input)?;
-    let (input, body) = body_block(input)?;
-    Ok((input, Item::Each(names, values, body)))
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (input, body) = preceded(opt_spacelike, body_block)(end)?;
+    Ok((input, Item::Each(names, values, body, pos)))
 }
 
 /// A for loop after the initial `@for`.
-fn for_loop2(input: Span) -> PResult<Item> {
+///
+/// The `start` is the `for` word, for the source position.
+fn for_loop2<'a>(start: Span, input: Span<'a>) -> PResult<'a, Item> {
     let (input, name) = delimited(tag("$"), name, spacelike)(input)?;
     let (input, from) = delimited(
         terminated(tag("from"), spacelike),
@@ -404,8 +464,9 @@ fn for_loop2(input: Span) -> PResult<Item> {
         alt((value(true, tag("through")), value(false, tag("to")))),
         spacelike,
     )(input)?;
-    let (input, to) = terminated(single_value, opt_spacelike)(input)?;
-    let (input, body) = body_block(input)?;
+    let (end, to) = single_value(input)?;
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (input, body) = preceded(opt_spacelike, body_block)(end)?;
     Ok((
         input,
         Item::For {
@@ -414,14 +475,19 @@ fn for_loop2(input: Span) -> PResult<Item> {
             to: Box::new(to),
             inclusive,
             body,
+            pos,
         },
     ))
 }
 
-fn while_loop2(input: Span) -> PResult<Item> {
-    let (input, cond) = terminated(value_expression, opt_spacelike)(input)?;
-    let (input, body) = body_block(input)?;
-    Ok((input, Item::While(cond, body)))
+/// A while loop after the initial `@while`.
+///
+/// The `start` is the `while` word, for the source position.
+fn while_loop2<'a>(start: Span, input: Span<'a>) -> PResult<'a, Item> {
+    let (end, cond) = value_expression(input)?;
+    let pos = SourcePos::from_to(start, end).opt_back("@");
+    let (input, body) = preceded(opt_spacelike, body_block)(end)?;
+    Ok((input, Item::While(cond, body, pos)))
 }
 
 fn mixin_declaration2(input: Span) -> PResult<Item> {
@@ -464,16 +530,19 @@ fn content_stmt2(input: Span) -> PResult<Item> {
     Ok((rest, Item::Content(args.unwrap_or_default(), pos)))
 }
 
+/// A custom property, including the initial `--`.
 fn custom_property(input: Span) -> PResult<Item> {
-    let (rest, name) = terminated(opt(sass_string), tag(":"))(input)?;
+    let (rest, name) =
+        delimited(tag("--"), opt(sass_string), tag(":"))(input)?;
     let mut name = name.unwrap_or_else(|| SassString::from(""));
     name.prepend("--");
-    let (rest, value) =
-        terminated(custom_value, alt((tag(";"), peek(tag("}")))))(rest)?;
-    Ok((rest, Item::CustomProperty(name, value)))
+    let (end, value) = custom_value(rest)?;
+    let pos = SourcePos::from_to(input, end);
+    let (rest, _) = alt((tag(";"), peek(tag("}"))))(end)?;
+    Ok((rest, Item::CustomProperty(name, value, pos)))
 }
 
-fn property_or_namespace_rule(input: Span) -> PResult<Item> {
+fn property_or_namespace_rule(start: Span) -> PResult<Item> {
     let (input, name) = terminated(
         alt((
             map(preceded(tag("*"), sass_string), |mut s| {
@@ -483,10 +552,11 @@ fn property_or_namespace_rule(input: Span) -> PResult<Item> {
             sass_string,
         )),
         delimited(ignore_comments, tag(":"), ignore_comments),
-    )(input)?;
+    )(start)?;
 
-    let (input, val) =
-        opt(terminated(value_expression, opt_spacelike))(input)?;
+    let (end, val) = opt(value_expression)(input)?;
+    let pos = SourcePos::from_to(start, end);
+    let (input, _) = cond(val.is_some(), opt_spacelike)(end)?;
 
     let (input, next) = if val.is_some() {
         alt((tag("{"), tag(";"), tag("")))(input)?
@@ -500,9 +570,9 @@ fn property_or_namespace_rule(input: Span) -> PResult<Item> {
         b"" => (input, None),
         _ => (input, None), // error?
     };
-    let (input, _) = opt_spacelike(input)?;
+    let (input, _) = opt_space(input)?;
 
-    Ok((input, ns_or_prop_item(name, val, body)))
+    Ok((input, ns_or_prop_item(name, val, body, pos)))
 }
 
 use crate::sass::SassString;
@@ -510,11 +580,13 @@ fn ns_or_prop_item(
     name: SassString,
     value: Option<Value>,
     body: Option<Vec<Item>>,
+    pos: SourcePos,
 ) -> Item {
     if let Some(body) = body {
-        Item::NamespaceRule(name, value.unwrap_or(Value::Null), body)
+        let value = value.unwrap_or_else(|| Value::Null(pos.clone()));
+        Item::NamespaceRule(name, value, body, pos)
     } else if let Some(value) = value {
-        Item::Property(name, value)
+        Item::Property(name, value, pos)
     } else {
         unreachable!()
     }
@@ -526,10 +598,10 @@ fn body_block(input: Span) -> PResult<Vec<Item>> {
 
 fn body_block2(input: Span) -> PResult<Vec<Item>> {
     let (input, (v, _end)) = preceded(
-        opt_spacelike,
+        opt_space,
         many_till(
-            terminated(body_item, opt_spacelike),
-            terminated(terminated(tag("}"), opt_spacelike), opt(tag(";"))),
+            terminated(body_item, opt_space),
+            terminated(terminated(tag("}"), opt_space), opt(tag(";"))),
         ),
     )(input)?;
     Ok((input, v))
@@ -601,56 +673,86 @@ fn input_to_string(s: Span) -> Result<String, Utf8Error> {
 
 #[cfg(test)]
 fn percentage(v: i64) -> Value {
-    Value::Numeric(Numeric::new(v, Unit::Percent))
+    Value::Numeric(Numeric::new(v, Unit::Percent), mock_pos())
 }
 
 #[cfg(test)]
 fn string(v: &str) -> Value {
-    Value::Literal(v.into())
+    Value::Literal(v.into(), mock_pos())
 }
 
 #[test]
 fn if_with_no_else() {
-    assert_eq!(
-        check_parse!(if_statement, b"@if true { p { border: solid; } }\n"),
-        Item::IfStatement(
-            Value::True,
-            vec![Item::Rule(
-                selectors(code_span(b"p")).unwrap().1,
-                vec![Item::Property("border".into(), string("solid"))],
-            )],
-            vec![],
-        ),
-    )
+    match check_parse!(if_statement, b"@if true { p { border: solid; } }\n") {
+        Item::IfStatement(Value::True(_), do_if, do_else, _) => {
+            match &do_if[..] {
+                [Item::Rule(sel, body, _)] => {
+                    assert_eq!(
+                        sel.clone().no_pos(),
+                        selectors(code_span(b"p")).unwrap().1.no_pos()
+                    );
+                    match &body[..] {
+                        [Item::Property(name, value, _)] => {
+                            assert_eq!(
+                                (name, &value.clone().no_pos()),
+                                (&"border".into(), &string("solid"))
+                            )
+                        }
+                        body => panic!("Unexpected body {:?}", body),
+                    }
+                }
+                items => panic!("Unexpected items {:?}", items),
+            }
+            assert_eq!(do_else, vec![]);
+        }
+        item => panic!("Unexpected item {:?}", item),
+    }
 }
 
 #[test]
 fn test_simple_property() {
-    assert_eq!(
-        check_parse!(property_or_namespace_rule, b"color: red;\n"),
-        Item::Property(
-            "color".into(),
-            Value::Color(Rgba::from_rgb(255, 0, 0), Some("red".into())),
-        ),
-    )
+    match check_parse!(property_or_namespace_rule, b"color: red;\n") {
+        Item::Property(name, value, pos) => {
+            assert_eq!(
+                (name, value.no_pos()),
+                (
+                    "color".into(),
+                    Value::Color(
+                        Rgba::from_rgb(255, 0, 0),
+                        Some("red".into()),
+                        mock_pos(),
+                    ),
+                )
+            );
+            assert_eq!(
+                format!("{:?}", pos),
+                "{\"[color: red];\", (rsass):1 root stylesheet}"
+            );
+        }
+        item => panic!("Unexpected item {:?}", item),
+    }
 }
 
 #[test]
 fn test_property_2() {
-    assert_eq!(
-        check_parse!(
-            property_or_namespace_rule,
-            b"background-position: 90% 50%;\n"
-        ),
-        Item::Property(
-            "background-position".into(),
-            Value::List(
-                vec![percentage(90), percentage(50)],
-                Some(ListSeparator::Space),
-                false,
-            ),
+    match check_parse!(
+        property_or_namespace_rule,
+        b"background-position: 90% 50%;\n"
+    ) {
+        Item::Property(name, value, _) => assert_eq!(
+            (name, value.no_pos()),
+            (
+                "background-position".into(),
+                Value::List(
+                    vec![percentage(90), percentage(50)],
+                    Some(ListSeparator::Space),
+                    false,
+                    mock_pos(),
+                ),
+            )
         ),
-    )
+        item => panic!("Unexpected item {:?}", item),
+    }
 }
 
 #[test]
@@ -664,7 +766,7 @@ fn test_variable_declaration_simple() {
             pos: _,
         } => {
             assert_eq!(
-                (name, val, default, global),
+                (name, val.no_pos(), default, global),
                 ("foo".into(), string("bar"), false, false)
             )
         }
@@ -683,13 +785,14 @@ fn test_variable_declaration_global() {
             pos: _,
         } => {
             assert_eq!(
-                (name, val, default, global),
+                (name, val.no_pos(), default, global),
                 (
                     "y".into(),
                     Value::List(
                         vec![string("some"), string("value")],
                         Some(ListSeparator::Space),
                         false,
+                        mock_pos(),
                     ),
                     false,
                     true,
@@ -711,7 +814,7 @@ fn test_variable_declaration_default() {
             pos: _,
         } => {
             assert_eq!(
-                (name, val, default, global),
+                (name, val.no_pos(), default, global),
                 ("y".into(), string("value"), true, false,)
             )
         }
@@ -0,0 +1,57 @@
+//! Ignoring source positions when comparing parse results in tests.
+use super::SourcePos;
+use crate::sass::visit::{walk_selector_mut, VisitMut};
+use crate::sass::{CallArgs, Selector, Selectors, Value};
+
+/// Replace all source positions in a parsed tree with the same mock
+/// position, and remove selector positions.
+///
+/// This makes a parsed tree comparable to a tree built in a test.
+pub trait NoPos: Sized {
+    fn no_pos(self) -> Self;
+}
+
+struct Unpos;
+
+impl VisitMut for Unpos {
+    fn visit_selector_mut(&mut self, selector: &mut Selector) {
+        selector.pos = None;
+        walk_selector_mut(self, selector)
+    }
+    fn visit_pos_mut(&mut self, pos: &mut SourcePos) {
+        *pos = mock_pos();
+    }
+}
+
+/// The position used by [`NoPos`].
+pub fn mock_pos() -> SourcePos {
+    SourcePos::mock_value("")
+}
+
+impl NoPos for Value {
+    fn no_pos(mut self) -> Self {
+        Unpos.visit_value_mut(&mut self);
+        self
+    }
+}
+
+impl NoPos for CallArgs {
+    fn no_pos(mut self) -> Self {
+        Unpos.visit_call_args_mut(&mut self);
+        self
+    }
+}
+
+impl NoPos for Selector {
+    fn no_pos(mut self) -> Self {
+        Unpos.visit_selector_mut(&mut self);
+        self
+    }
+}
+
+impl NoPos for Selectors {
+    fn no_pos(mut self) -> Self {
+        Unpos.visit_selectors_mut(&mut self);
+        self
+    }
+}
@@ -45,6 +45,11 @@ impl SourcePos {
         }
     }
 
+    /// Make this position refer to `file`, keeping the line and column.
+    pub(crate) fn set_file(&mut self, file: &SourceName) {
+        Arc::make_mut(&mut self.p).file = file.clone();
+    }
+
     pub(crate) fn mock_function(
         name: &Name,
         args: &FormalArgs,
@@ -59,6 +64,18 @@ impl SourcePos {
     ) -> Self {
         SourcePos::mock_impl(name, "@mixin", args, module)
     }
+    /// A position for a value created in rust code, rather than parsed.
+    pub(crate) fn mock_value(text: &str) -> Self {
+        SourcePos {
+            p: Arc::new(SourcePosImpl {
+                line: text.into(),
+                line_no: 1,
+                line_pos: 1,
+                length: std::cmp::max(1, text.chars().count()),
+                file: SourceName::root("(rsass)"),
+            }),
+        }
+    }
     fn mock_impl(
         name: &Name,
         kind: &str,
@@ -175,9 +192,25 @@ impl SourcePos {
         Ok(())
     }
 
-    pub(crate) fn line_no(&self) -> u32 {
+    /// The line number of this position (starting at 1).
+    pub fn line_no(&self) -> u32 {
         self.p.line_no
     }
+    /// The position on the line (in characters, starting at 1).
+    pub fn line_pos(&self) -> usize {
+        self.p.line_pos
+    }
+    /// The length of this position (in characters).
+    ///
+    /// Note that the length may extend past the end of the line, for
+    /// a position spanning more than one line.
+    pub fn length(&self) -> usize {
+        self.p.length
+    }
+    /// The text of the (first) source line containing this position.
+    pub fn line(&self) -> &str {
+        &self.p.line
+    }
 
     /// If self is preceded (on same line) by `s`, include `s` in self.
     pub(crate) fn opt_back(mut self, s: &str) -> Self {
@@ -222,7 +255,8 @@ impl SourcePos {
     pub(crate) fn same_file_as(&self, other: &Self) -> bool {
         self.file_url() == other.file_url()
     }
-    pub(crate) fn file_url(&self) -> &str {
+    /// The url of the file containing this position.
+    pub fn file_url(&self) -> &str {
         self.p.file.name()
     }
 }
@@ -1,6 +1,6 @@
 use super::strings::{sass_string, sass_string_dq, sass_string_sq};
 use super::util::{ignore_comments, opt_spacelike, spacelike2};
-use super::{input_to_string, PResult, Span};
+use super::{input_to_string, PResult, SourcePos, Span};
 use crate::sass::{Selector, SelectorPart, Selectors};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -18,11 +18,12 @@ pub fn selectors(input: Span) -> PResult<Selectors> {
 }
 
 pub fn selector(input: Span) -> PResult<Selector> {
-    let (input, mut s) = many1(selector_part)(input)?;
+    let (rest, mut s) = many1(selector_part)(input)?;
     if s.last() == Some(&SelectorPart::Descendant) {
         s.pop();
     }
-    Ok((input, Selector::new(s)))
+    let pos = SourcePos::from_to(input, rest);
+    Ok((rest, Selector::new(s).with_pos(pos)))
 }
 
 pub(crate) fn selector_part(input: Span) -> PResult<SelectorPart> {
@@ -119,6 +120,7 @@ pub(crate) fn selector_part(input: Span) -> PResult<SelectorPart> {
 
 #[cfg(test)]
 mod test {
+    use super::super::nopos::NoPos;
     use super::*;
     use crate::sass::{SassString, StringPart};
     use crate::value::Quotes;
@@ -126,14 +128,14 @@ mod test {
     #[test]
     fn simple_selector() {
         assert_eq!(
-            check_parse!(selector, b"foo "),
+            check_parse!(selector, b"foo ").no_pos(),
             Selector::new(vec![SelectorPart::Simple("foo".into())]),
         )
     }
     #[test]
     fn escaped_simple_selector() {
         assert_eq!(
-            check_parse!(selector, b"\\E9m "),
+            check_parse!(selector, b"\\E9m ").no_pos(),
             Selector::new(vec![SelectorPart::Simple("ém".into())]),
         )
     }
@@ -141,7 +143,7 @@ mod test {
     #[test]
     fn selector2() {
         assert_eq!(
-            check_parse!(selector, b"foo bar "),
+            check_parse!(selector, b"foo bar ").no_pos(),
             Selector::new(vec![
                 SelectorPart::Simple("foo".into()),
                 SelectorPart::Descendant,
@@ -153,7 +155,7 @@ mod test {
     #[test]
     fn child_selector() {
         assert_eq!(
-            check_parse!(selector, b"foo > bar "),
+            check_parse!(selector, b"foo > bar ").no_pos(),
             Selector::new(vec![
                 SelectorPart::Simple("foo".into()),
                 SelectorPart::RelOp(b'>'),
@@ -165,7 +167,7 @@ mod test {
     #[test]
     fn foo1_selector() {
         assert_eq!(
-            check_parse!(selector, b"[data-icon='test-1'] "),
+            check_parse!(selector, b"[data-icon='test-1'] ").no_pos(),
             Selector::new(vec![SelectorPart::Attribute {
                 name: "data-icon".into(),
                 op: "=".into(),
@@ -181,7 +183,7 @@ mod test {
     #[test]
     fn pseudo_selector() {
         assert_eq!(
-            check_parse!(selector, b":before "),
+            check_parse!(selector, b":before ").no_pos(),
             Selector::new(vec![SelectorPart::Pseudo {
                 name: "before".into(),
                 arg: None,
@@ -191,7 +193,7 @@ mod test {
     #[test]
     fn pseudo_on_simple_selector() {
         assert_eq!(
-            check_parse!(selector, b"figure:before "),
+            check_parse!(selector, b"figure:before ").no_pos(),
             Selector::new(vec![
                 SelectorPart::Simple("figure".into()),
                 SelectorPart::Pseudo {
@@ -205,7 +207,7 @@ mod test {
     #[test]
     fn selectors_simple() {
         assert_eq!(
-            check_parse!(selectors, b"foo, bar "),
+            check_parse!(selectors, b"foo, bar ").no_pos(),
             Selectors::new(vec![
                 Selector::new(vec![SelectorPart::Simple("foo".into())]),
                 Selector::new(vec![SelectorPart::Simple("bar".into())]),
@@ -1,13 +1,29 @@
-use super::{PResult, Span};
+use super::{PResult, SourcePos, Span};
 use crate::sass::{SassString, StringPart};
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag};
-use nom::character::complete::multispace1;
+use nom::character::complete::{multispace0, multispace1};
 use nom::combinator::{all_consuming, map, map_res, not, opt, peek};
 use nom::multi::{fold_many0, fold_many1, many0};
 use nom::sequence::{preceded, terminated};
 use std::str::from_utf8;
 
+/// Apply `f`, and make a result from its output and the source
+/// position of the input it matched.
+pub fn positioned<'a, O, R, F, M>(
+    mut f: F,
+    make: M,
+) -> impl FnMut(Span<'a>) -> PResult<'a, R>
+where
+    F: FnMut(Span<'a>) -> PResult<'a, O>,
+    M: Fn(O, SourcePos) -> R,
+{
+    move |input| {
+        let (rest, out) = f(input)?;
+        Ok((rest, make(out, SourcePos::from_to(input, rest))))
+    }
+}
+
 pub fn semi_or_end(input: Span) -> PResult<()> {
     terminated(
         opt_spacelike,
@@ -25,6 +41,11 @@ pub fn spacelike2(input: Span) -> PResult<()> {
     terminated(spacelike, ignore_comments)(input)
 }
 
+/// Optional whitespace, but not comments.
+pub fn opt_space(input: Span) -> PResult<()> {
+    map(multispace0, |_| ())(input)
+}
+
 pub fn opt_spacelike(input: Span) -> PResult<()> {
     fold_many0(alt((ignore_space, ignore_lcomment)), || (), |(), ()| ())(
         input,
@@ -5,7 +5,7 @@ use super::strings::{
     special_function_misc, special_url, var_name,
 };
 use super::unit::unit;
-use super::util::{ignore_comments, opt_spacelike, spacelike2};
+use super::util::{ignore_comments, opt_spacelike, positioned, spacelike2};
 use super::{input_to_string, sass_string, PResult, SourcePos, Span};
 use crate::sass::{SassString, Value};
 use crate::value::{ListSeparator, Number, Numeric, Operator, Rgba};
@@ -14,124 +14,138 @@ use nom::bytes::complete::{tag, tag_no_case};
 use nom::character::complete::{
     alphanumeric1, multispace0, multispace1, one_of,
 };
-use nom::combinator::{into, map, map_res, not, opt, peek, recognize, value};
-use nom::multi::{fold_many0, fold_many1, many0, many_m_n, separated_list1};
+use nom::combinator::{map, map_res, not, opt, peek, recognize, value};
+use nom::multi::{fold_many1, many0, many_m_n, separated_list1};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom_locate::position;
 use num_traits::Zero;
 use std::str::from_utf8;
 
 pub fn value_expression(input: Span) -> PResult<Value> {
-    let (input, result) = separated_list1(
+    let (end, result) = separated_list1(
         preceded(tag(","), ignore_comments),
         terminated(space_list, ignore_comments),
     )(input)?;
-    let (input, trail) =
-        many0(delimited(opt_spacelike, tag(","), opt_spacelike))(input)?;
+    let (rest, trail) =
+        many0(delimited(opt_spacelike, tag(","), opt_spacelike))(end)?;
     Ok((
-        input,
+        rest,
         if result.len() == 1 && trail.is_empty() {
             result.into_iter().next().unwrap()
         } else {
-            Value::List(result, Some(ListSeparator::Comma), false)
+            let pos = SourcePos::from_to(input, end);
+            Value::List(result, Some(ListSeparator::Comma), false, pos)
         },
     ))
 }
 
 pub fn space_list(input: Span) -> PResult<Value> {
-    let (input, first) = se_or_ext_string(input)?;
-    let (input, list) = fold_many0(
-        pair(recognize(ignore_comments), se_or_ext_string),
-        move || vec![first.clone()],
-        |mut list: Vec<Value>, (s, item)| {
-            match (list.last_mut(), *s.fragment(), &item) {
-                (
-                    Some(Value::Literal(ref mut s1)),
-                    b"",
-                    Value::Literal(ref s2),
-                ) if s1.is_unquoted() && s2.is_unquoted() => {
-                    s1.append(s2);
-                }
-                _ => {
-                    list.push(item);
-                }
+    let (mut rest, first) = se_or_ext_string(input)?;
+    let mut last_start = input;
+    let mut list = vec![first];
+    while let Ok((nrest, (s, (start, item)))) = pair(
+        recognize(ignore_comments),
+        pair(position, se_or_ext_string),
+    )(rest)
+    {
+        match (list.last_mut(), *s.fragment(), &item) {
+            (
+                Some(Value::Literal(ref mut s1, ref mut pos)),
+                b"",
+                Value::Literal(ref s2, _),
+            ) if s1.is_unquoted() && s2.is_unquoted() => {
+                s1.append(s2);
+                *pos = SourcePos::from_to(last_start, nrest);
             }
-            list
-        },
-    )(input)?;
+            _ => {
+                list.push(item);
+                last_start = start;
+            }
+        }
+        rest = nrest;
+    }
     Ok((
-        input,
+        rest,
         if list.len() == 1 {
             list.into_iter().next().unwrap()
         } else {
-            Value::List(list, Some(ListSeparator::Space), false)
+            let pos = SourcePos::from_to(input, rest);
+            Value::List(list, Some(ListSeparator::Space), false, pos)
         },
     ))
 }
 
 pub fn simple_space_list(input: Span) -> PResult<Value> {
-    let (input, first) = single_expression(input)?;
-    let (input, list) = fold_many0(
-        preceded(spacelike2, single_expression),
-        move || vec![first.clone()],
-        |mut list, item| {
-            list.push(item);
-            list
-        },
-    )(input)?;
+    let (mut rest, first) = single_expression(input)?;
+    let mut list = vec![first];
+    while let Ok((nrest, item)) =
+        preceded(spacelike2, single_expression)(rest)
+    {
+        list.push(item);
+        rest = nrest;
+    }
     Ok((
-        input,
+        rest,
         if list.len() == 1 {
             list.into_iter().next().unwrap()
         } else {
-            Value::List(list, Some(ListSeparator::Space), false)
+            let pos = SourcePos::from_to(input, rest);
+            Value::List(list, Some(ListSeparator::Space), false, pos)
         },
     ))
 }
 
 fn se_or_ext_string(input: Span) -> PResult<Value> {
-    alt((single_expression, map(sass_string_ext, Value::Literal)))(input)
+    alt((
+        single_expression,
+        positioned(sass_string_ext, Value::Literal),
+    ))(input)
 }
 
 fn single_expression(input: Span) -> PResult<Value> {
-    let (input, a) = logic_expression(input)?;
-    fold_many0(
-        pair(
-            delimited(
-                multispace0,
-                alt((
-                    value(Operator::And, tag("and")),
-                    value(Operator::Or, tag("or")),
-                )),
-                multispace1,
-            ),
-            single_expression,
+    let (mut rest, mut a) = logic_expression(input)?;
+    while let Ok((nrest, (op, b))) = pair(
+        delimited(
+            multispace0,
+            alt((
+                value(Operator::And, tag("and")),
+                value(Operator::Or, tag("or")),
+            )),
+            multispace1,
         ),
-        move || a.clone(),
-        |a, (op, b)| Value::BinOp(Box::new(a), false, op, false, Box::new(b)),
-    )(input)
+        single_expression,
+    )(rest)
+    {
+        let pos = SourcePos::from_to(input, nrest);
+        a = Value::BinOp(Box::new(a), false, op, false, Box::new(b), pos);
+        rest = nrest;
+    }
+    Ok((rest, a))
 }
 
 fn logic_expression(input: Span) -> PResult<Value> {
-    let (input, a) = sum_expression(input)?;
-    fold_many0(
-        pair(
-            delimited(
-                multispace0,
-                alt((
-                    value(Operator::Equal, tag("==")),
-                    value(Operator::NotEqual, tag("!=")),
-                    value(Operator::GreaterE, tag(">=")),
-                    value(Operator::Greater, tag(">")),
-                    value(Operator::LesserE, tag("<=")),
-                    value(Operator::Lesser, tag("<")),
-                )),
-                multispace0,
-            ),
-            sum_expression,
+    let (mut rest, mut a) = sum_expression(input)?;
+    while let Ok((nrest, (op, b))) = pair(
+        delimited(
+            multispace0,
+            alt((
+                value(Operator::Equal, tag("==")),
+                value(Operator::NotEqual, tag("!=")),
+                value(Operator::GreaterE, tag(">=")),
+                value(Operator::Greater, tag(">")),
+                value(Operator::LesserE, tag("<=")),
+                value(Operator::Lesser, tag("<")),
+            )),
+            multispace0,
         ),
-        move || a.clone(),
-        |a, (op, b)| Value::BinOp(Box::new(a), false, op, false, Box::new(b)),
-    )(input)
+        sum_expression,
+    )(rest)
+    {
+        let pos = SourcePos::from_to(input, nrest);
+        a = Value::BinOp(Box::new(a), false, op, false, Box::new(b), pos);
+        rest = nrest;
+    }
+    Ok((rest, a))
 }
 
 fn sum_expression(input: Span) -> PResult<Value> {
@@ -157,7 +171,8 @@ fn sum_expression(input: Span) -> PResult<Value> {
         )),
     ))(rest)
     {
-        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2));
+        let pos = SourcePos::from_to(input, nrest);
+        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2), pos);
         rest = nrest;
     }
     Ok((rest, v))
@@ -177,36 +192,49 @@ fn term_value(input: Span) -> PResult<Value> {
     ))(rest)
     {
         rest = nrest;
-        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2));
+        let pos = SourcePos::from_to(input, nrest);
+        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2), pos);
     }
     Ok((rest, v))
 }
 
 pub fn single_value(input: Span) -> PResult<Value> {
     if let Ok((input0, _p)) = preceded(tag("("), opt_spacelike)(input) {
-        if let Ok((input, first_key)) = simple_space_list(input0) {
-            let (input, value) = if let Ok((mut input, first_val)) =
-                preceded(colon, space_list)(input)
+        if let Ok((rest, first_key)) = simple_space_list(input0) {
+            let (rest, items) = if let Ok((mut rest, first_val)) =
+                preceded(colon, space_list)(rest)
             {
                 let mut items = vec![(first_key, first_val)];
-                while let Ok((rest, (key, val))) = pair(
+                while let Ok((nrest, (key, val))) = pair(
                     preceded(comma, simple_space_list),
                     preceded(colon, space_list),
-                )(input)
+                )(rest)
                 {
                     items.push((key, val));
-                    input = rest;
+                    rest = nrest;
                 }
-                let (input, _) = opt(comma)(input)?;
-                (input, Value::Map(items))
+                let (rest, _) = opt(comma)(rest)?;
+                (rest, Ok(items))
             } else {
-                (input, Value::Paren(Box::new(first_key), false))
+                (rest, Err(first_key))
             };
-            if let Ok((input, _)) = end_paren(input) {
-                return Ok((input, value));
+            if let Ok((end, _)) = end_paren(rest) {
+                let pos = SourcePos::from_to(input, end);
+                let value = match items {
+                    Ok(items) => Value::Map(items, pos),
+                    Err(value) => Value::Paren(Box::new(value), false, pos),
+                };
+                return Ok((end, value));
             }
         }
-        terminated(fallback_in_paren, end_paren)(input0)
+        let (end, inner) =
+            terminated(opt(value_expression), end_paren)(input0)?;
+        let pos = SourcePos::from_to(input, end);
+        let value = match inner {
+            Some(inner) => Value::Paren(Box::new(inner), false, pos),
+            None => Value::List(vec![], None, false, pos),
+        };
+        Ok((end, value))
     } else {
         simple_value(input)
     }
@@ -220,13 +248,6 @@ fn colon(input: Span) -> PResult<Span> {
     delimited(opt_spacelike, tag(":"), opt_spacelike)(input)
 }
 
-fn fallback_in_paren(input: Span) -> PResult<Value> {
-    alt((
-        map(value_expression, |v| Value::Paren(Box::new(v), false)),
-        value(Value::List(vec![], None, false), tag("")),
-    ))(input)
-}
-
 fn end_paren(input: Span) -> PResult<Span> {
     preceded(opt_spacelike, tag(")"))(input)
 }
@@ -234,31 +255,33 @@ fn end_paren(input: Span) -> PResult<Span> {
 fn simple_value(input: Span) -> PResult<Value> {
     alt((
         bang,
-        value(Value::True, tag("true")),
-        value(Value::False, tag("false")),
-        value(Value::HereSelector, tag("&")),
+        positioned(tag("true"), |_, pos| Value::True(pos)),
+        positioned(tag("false"), |_, pos| Value::False(pos)),
+        positioned(tag("&"), |_, pos| Value::HereSelector(pos)),
         unicode_range,
         bracket_list,
-        into(number),
+        positioned(number, Value::Numeric),
         variable,
         hex_color,
-        value(Value::Null, tag("null")),
-        map(special_url, Value::Literal),
+        positioned(tag("null"), |_, pos| Value::Null(pos)),
+        positioned(special_url, Value::Literal),
         special_function,
         // Really ugly special case ... sorry.
-        value(Value::Literal("-null".into()), tag("-null")),
-        map(var_name, Value::Literal),
+        positioned(tag("-null"), |_, pos| {
+            Value::Literal("-null".into(), pos)
+        }),
+        positioned(var_name, Value::Literal),
         unary_op,
         function_call,
         // And a bunch of string variants
-        map(sass_string, literal_or_color),
-        map(sass_string_dq, Value::Literal),
-        map(sass_string_sq, Value::Literal),
+        positioned(sass_string, literal_or_color),
+        positioned(sass_string_dq, Value::Literal),
+        positioned(sass_string_sq, Value::Literal),
     ))(input)
 }
 
 fn bang(input: Span) -> PResult<Value> {
-    map(
+    positioned(
         map_res(
             preceded(
                 pair(tag("!"), opt_spacelike),
@@ -282,21 +305,25 @@ fn unicode_range(input: Span) -> PResult<Value> {
     Ok((
         rest,
         // The unwrap should be ok, as only ascii is matched.
-        Value::UnicodeRange(from_utf8(matched).unwrap().to_string()),
+        Value::UnicodeRange(
+            from_utf8(matched).unwrap().to_string(),
+            SourcePos::from_to(input, rest),
+        ),
     ))
 }
 
 fn bracket_list(input: Span) -> PResult<Value> {
-    let (input, content) =
+    let (rest, content) =
         delimited(tag("["), opt(value_expression), tag("]"))(input)?;
+    let pos = SourcePos::from_to(input, rest);
     Ok((
-        input,
+        rest,
         match content {
-            Some(Value::List(list, sep, false)) => {
-                Value::List(list, sep, true)
+            Some(Value::List(list, sep, false, _)) => {
+                Value::List(list, sep, true, pos)
             }
-            Some(single) => Value::List(vec![single], None, true),
-            None => Value::List(vec![], None, true),
+            Some(single) => Value::List(vec![single], None, true, pos),
+            None => Value::List(vec![], None, true, pos),
         },
     ))
 }
@@ -377,7 +404,7 @@ pub fn variable(input: Span) -> PResult<Value> {
     Ok((rest, Value::Variable(name.into(), pos)))
 }
 
-fn hex_color(input: Span) -> PResult<Value> {
+pub fn hex_color(input: Span) -> PResult<Value> {
     let (rest, (r, g, b, a)) = delimited(
         tag("#"),
         alt((
@@ -387,21 +414,22 @@ fn hex_color(input: Span) -> PResult<Value> {
         peek(map(not(alphanumeric1), |_| ())),
     )(input)?;
 
+    let pos = SourcePos::from_to(input, rest);
     if let Some(a) = a {
         let rgba = Rgba::from_rgba(r, g, b, a);
-        Ok((rest, Value::Color(rgba, None)))
+        Ok((rest, Value::Color(rgba, None, pos)))
     } else {
         let rgba = Rgba::from_rgb(r, g, b);
         let length = input.fragment().len() - rest.fragment().len();
         // Unwrap should be ok as only ascii is matched.
         let raw =
             from_utf8(&input.fragment()[0..length]).unwrap().to_string();
-        Ok((rest, Value::Color(rgba, Some(raw))))
+        Ok((rest, Value::Color(rgba, Some(raw), pos)))
     }
 }
 
 pub fn unary_op(input: Span) -> PResult<Value> {
-    map(
+    positioned(
         pair(
             terminated(
                 alt((
@@ -414,13 +442,16 @@ pub fn unary_op(input: Span) -> PResult<Value> {
             ),
             single_value,
         ),
-        |(op, v)| Value::UnaryOp(op, Box::new(v)),
+        |(op, v), pos| Value::UnaryOp(op, Box::new(v), pos),
     )(input)
 }
 
 pub fn special_function(input: Span) -> PResult<Value> {
     // Either a nice semantic css function or a fallback with interpolation.
-    alt((css_function, map(special_function_misc, Value::Literal)))(input)
+    alt((
+        css_function,
+        positioned(special_function_misc, Value::Literal),
+    ))(input)
 }
 
 pub fn function_call(input: Span) -> PResult<Value> {
@@ -429,13 +460,13 @@ pub fn function_call(input: Span) -> PResult<Value> {
     Ok((rest, Value::Call(name, args, pos)))
 }
 
-fn literal_or_color(s: SassString) -> Value {
+fn literal_or_color(s: SassString, pos: SourcePos) -> Value {
     if let Some(val) = s.single_raw() {
         if let Some(rgba) = Rgba::from_name(val) {
-            return Value::Color(rgba, Some(val.to_string()));
+            return Value::Color(rgba, Some(val.to_string()), pos);
         }
     }
-    Value::Literal(s)
+    Value::Literal(s, pos)
 }
 
 fn hexchar1(input: Span) -> PResult<u8> {
@@ -451,15 +482,17 @@ fn hexchar_raw(input: Span) -> PResult<u8> {
 }
 
 pub fn dictionary(input: Span) -> PResult<Value> {
-    delimited(
+    let (rest, items) = delimited(
         preceded(tag("("), opt_spacelike),
-        dictionary_inner,
+        dictionary_items,
         terminated(opt_spacelike, tag(")")),
-    )(input)
+    )(input)?;
+    let pos = SourcePos::from_to(input, rest);
+    Ok((rest, Value::Map(items, pos)))
 }
 
-pub fn dictionary_inner(input: Span) -> PResult<Value> {
-    let (input, items) = terminated(
+fn dictionary_items(input: Span) -> PResult<Vec<(Value, Value)>> {
+    terminated(
         separated_list1(
             delimited(opt_spacelike, tag(","), opt_spacelike),
             pair(
@@ -471,12 +504,12 @@ pub fn dictionary_inner(input: Span) -> PResult<Value> {
             ),
         ),
         opt(delimited(opt_spacelike, tag(","), opt_spacelike)),
-    )(input)?;
-    Ok((input, Value::Map(items.into_iter().collect())))
+    )(input)
 }
 
 #[cfg(test)]
 mod test {
+    use super::super::nopos::{mock_pos, NoPos};
     use super::super::{code_span, parse_value_data};
     use super::*;
     use crate::sass::CallArgs;
@@ -522,14 +555,14 @@ mod test {
 
     #[test]
     fn simple_value_literal() {
-        check_expr("rad;", Literal("rad".into()))
+        check_expr("rad;", lit("rad"))
     }
 
     #[test]
     fn simple_value_literal_color() {
         check_expr(
             "red;",
-            Color(Rgba::from_rgb(255, 0, 0), Some("red".into())),
+            Color(Rgba::from_rgb(255, 0, 0), Some("red".into()), mock_pos()),
         )
     }
 
@@ -546,7 +579,7 @@ mod test {
 
     #[test]
     fn paren_literal() {
-        check_expr("(rad);", Paren(Box::new(Literal("rad".into())), false))
+        check_expr("(rad);", Paren(Box::new(lit("rad")), false, mock_pos()))
     }
 
     #[test]
@@ -555,11 +588,13 @@ mod test {
             "(rod bloe);",
             Paren(
                 Box::new(List(
-                    vec![Literal("rod".into()), Literal("bloe".into())],
+                    vec![lit("rod"), lit("bloe")],
                     Some(ListSeparator::Space),
                     false,
+                    mock_pos(),
                 )),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -570,11 +605,13 @@ mod test {
             "(rod, bloe);",
             Paren(
                 Box::new(List(
-                    vec![Literal("rod".into()), Literal("bloe".into())],
+                    vec![lit("rod"), lit("bloe")],
                     Some(ListSeparator::Comma),
                     false,
+                    mock_pos(),
                 )),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -584,9 +621,10 @@ mod test {
         check_expr(
             "rod, bloe;",
             List(
-                vec![Literal("rod".into()), Literal("bloe".into())],
+                vec![lit("rod"), lit("bloe")],
                 Some(ListSeparator::Comma),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -597,11 +635,13 @@ mod test {
             "(rod, bloe, );",
             Paren(
                 Box::new(List(
-                    vec![Literal("rod".into()), Literal("bloe".into())],
+                    vec![lit("rod"), lit("bloe")],
                     Some(ListSeparator::Comma),
                     false,
+                    mock_pos(),
                 )),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -611,9 +651,10 @@ mod test {
         check_expr(
             "rod, bloe, ;",
             List(
-                vec![Literal("rod".into()), Literal("bloe".into())],
+                vec![lit("rod"), lit("bloe")],
                 Some(ListSeparator::Comma),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -633,7 +674,8 @@ mod test {
             Ok((
                 "foo".into(),
                 CallArgs::new(vec![(None, Value::scalar(17))], false)
-                    .unwrap(),
+                    .unwrap()
+                    .no_pos(),
                 ";".as_bytes(),
             )),
         );
@@ -646,7 +688,7 @@ mod test {
         let (rest, value) = value_expression(code_span(expr.as_bytes()))
             .map_err(|e| e.to_string())?;
         if let Value::Call(name, args, _) = value {
-            Ok((name, args, rest.fragment()))
+            Ok((name, args.no_pos(), rest.fragment()))
         } else {
             Err(format!("Not a call parse result: {:?} {:?}", value, rest))
         }
@@ -664,12 +706,14 @@ mod test {
                         Operator::Div,
                         false,
                         Box::new(Value::scalar(10)),
+                        mock_pos(),
                     ),
                     Value::scalar(2),
                     Value::scalar(3),
                 ],
                 Some(ListSeparator::Space),
                 false,
+                mock_pos(),
             ),
         )
     }
@@ -685,11 +729,13 @@ mod test {
                     Operator::Div,
                     false,
                     Box::new(Value::scalar(5)),
+                    mock_pos(),
                 )),
                 false,
                 Operator::Div,
                 false,
                 Box::new(Value::scalar(3)),
+                mock_pos(),
             ),
         )
     }
@@ -698,7 +744,11 @@ mod test {
     fn color_short() {
         check_expr(
             "#AbC;",
-            Color(Rgba::from_rgb(0xaa, 0xbb, 0xcc), Some("#AbC".into())),
+            Color(
+                Rgba::from_rgb(0xaa, 0xbb, 0xcc),
+                Some("#AbC".into()),
+                mock_pos(),
+            ),
         )
     }
 
@@ -706,7 +756,11 @@ mod test {
     fn color_long() {
         check_expr(
             "#AaBbCc;",
-            Color(Rgba::from_rgb(0xaa, 0xbb, 0xcc), Some("#AaBbCc".into())),
+            Color(
+                Rgba::from_rgb(0xaa, 0xbb, 0xcc),
+                Some("#AaBbCc".into()),
+                mock_pos(),
+            ),
         )
     }
 
@@ -715,9 +769,10 @@ mod test {
         check_expr(
             "[foo bar];",
             List(
-                vec![Literal("foo".into()), Literal("bar".into())],
+                vec![lit("foo"), lit("bar")],
                 Some(ListSeparator::Space),
                 true,
+                mock_pos(),
             ),
         )
     }
@@ -727,39 +782,45 @@ mod test {
         check_expr(
             "[foo, bar];",
             List(
-                vec![Literal("foo".into()), Literal("bar".into())],
+                vec![lit("foo"), lit("bar")],
                 Some(ListSeparator::Comma),
                 true,
+                mock_pos(),
             ),
         )
     }
 
     #[test]
     fn parse_bracket_empty_array() {
-        check_expr("[];", List(vec![], None, true))
+        check_expr("[];", List(vec![], None, true, mock_pos()))
     }
 
     #[test]
     fn map_nq() {
         check_expr(
             "(foo: bar, baz: 17);",
-            Map(vec![
-                (Literal("foo".into()), Literal("bar".into())),
-                (Literal("baz".into()), Value::scalar(17)),
-            ]
-            .into_iter()
-            .collect()),
+            Map(
+                vec![
+                    (lit("foo"), lit("bar")),
+                    (lit("baz"), Value::scalar(17)),
+                ],
+                mock_pos(),
+            ),
         )
     }
 
     fn check_expr(expr: &str, value: Value) {
         assert_eq!(
             value_expression(code_span(expr.as_bytes()))
-                .map(|(rest, value)| (*rest.fragment(), value)),
-            Ok((&b";"[..], value)),
+                .map(|(rest, value)| (*rest.fragment(), value.no_pos())),
+            Ok((&b";"[..], value.no_pos())),
         )
     }
 
+    fn lit(s: &str) -> Value {
+        Literal(s.into(), mock_pos())
+    }
+
     #[test]
     fn parse_extended_literal() -> Result<(), crate::Error> {
         assert_eq!(
@@ -10,9 +10,9 @@ use std::default::Default;
 /// If the optional name is None, the argument is positional.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd)]
 pub struct CallArgs {
-    positional: Vec<Value>,
+    pub(crate) positional: Vec<Value>,
     // Ordered for formattig.
-    named: OrderMap<Name, Value>,
+    pub(crate) named: OrderMap<Name, Value>,
     trailing_comma: bool,
 }
 
@@ -193,7 +193,7 @@ impl CallArgs {
 }
 
 fn is_splat(arg: &Value) -> Option<&[Value]> {
-    if let Value::List(list, sep, false) = arg {
+    if let Value::List(list, sep, false, _) = arg {
         if let Some((Value::Literal(v, ..), splat)) = list.split_last() {
             if v.is_unquoted()
                 && v.single_raw() == Some("...")
@@ -0,0 +1,141 @@
+use super::{Expose, Name, UseAs};
+use crate::css;
+use crate::SourcePos;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// The configuration of a module, from a `with` clause.
+///
+/// A configured value is used (and removed from the configuration)
+/// by a `!default` variable declaration at the top level of the
+/// module.
+/// A module that is `@forward`-ed from a configured module gets a
+/// view of the same configuration, adjusted by the prefix and
+/// `show` / `hide` of the `@forward` rule, so values used through
+/// the view are removed from the original configuration as well.
+#[derive(Clone, Default)]
+pub(crate) struct Config {
+    values: Arc<Mutex<BTreeMap<Name, (css::Value, SourcePos)>>>,
+    /// The prefix and exposed names of each `@forward` this
+    /// configuration is seen through, outermost first.
+    view: Vec<(Name, Expose)>,
+    /// The position of the `with` clause the configuration comes from.
+    origin: Option<SourcePos>,
+}
+
+impl Config {
+    /// Create a configuration from the values of a `with` clause.
+    ///
+    /// An empty set of values is an unconfigured module.
+    pub fn new(
+        values: BTreeMap<Name, (css::Value, SourcePos)>,
+        origin: &SourcePos,
+    ) -> Self {
+        let origin = Some(origin.clone()).filter(|_| !values.is_empty());
+        Config {
+            values: Arc::new(Mutex::new(values)),
+            view: Vec::new(),
+            origin,
+        }
+    }
+
+    /// The position of the `with` clause this configuration comes
+    /// from, if any.
+    ///
+    /// Loading an already loaded module is an error if this differs
+    /// from the origin of the configuration it was loaded with.
+    pub fn origin(&self) -> Option<&SourcePos> {
+        self.origin.as_ref()
+    }
+
+    /// Check if there are no values in this configuration.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Check if there is a value for `name` in this configuration.
+    pub fn contains(&self, name: &Name) -> bool {
+        self.outer_name(name).map_or(false, |name| {
+            self.values.lock().unwrap().contains_key(&name)
+        })
+    }
+
+    /// Take the value for `name` from this configuration.
+    pub fn take(&self, name: &Name) -> Option<(css::Value, SourcePos)> {
+        let name = self.outer_name(name)?;
+        self.values.lock().unwrap().remove(&name)
+    }
+
+    /// Get all the values of this configuration, by local name.
+    pub fn entries(&self) -> BTreeMap<Name, (css::Value, SourcePos)> {
+        self.values
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((self.inner_name(name)?, value.clone()))
+            })
+            .collect()
+    }
+
+    /// The configuration as seen through a `@forward` rule.
+    pub fn through_forward(&self, as_n: &UseAs, expose: &Expose) -> Self {
+        if self.is_empty() {
+            return Config::default();
+        }
+        let prefix = match as_n {
+            UseAs::Prefix(prefix) => prefix.into(),
+            _ => Name::from_static(""),
+        };
+        let mut view = self.view.clone();
+        view.push((prefix, expose.clone()));
+        Config {
+            values: self.values.clone(),
+            view,
+            origin: self.origin.clone(),
+        }
+    }
+
+    /// Get a copy of this configuration, that does not share its
+    /// values with this one.
+    pub fn copy(&self) -> Self {
+        let values = self.values.lock().unwrap().clone();
+        Config {
+            values: Arc::new(Mutex::new(values)),
+            view: self.view.clone(),
+            origin: self.origin.clone(),
+        }
+    }
+
+    /// Get the name and position of a value that is not used.
+    pub fn unused(&self) -> Option<(Name, SourcePos)> {
+        self.entries()
+            .into_iter()
+            .next()
+            .map(|(name, (_, pos))| (name, pos))
+    }
+
+    /// The name in the original configuration for a local name.
+    fn outer_name(&self, name: &Name) -> Option<Name> {
+        let mut name = name.clone();
+        for (prefix, expose) in self.view.iter().rev() {
+            if !expose.allow_var(&name) {
+                return None;
+            }
+            name = format!("{}{}", prefix, name).into();
+        }
+        Some(name)
+    }
+
+    /// The local name for a name in the original configuration.
+    fn inner_name(&self, name: &Name) -> Option<Name> {
+        let mut name = name.clone();
+        for (prefix, expose) in &self.view {
+            name = name.as_ref().strip_prefix(prefix.as_ref())?.into();
+            if !expose.allow_var(&name) {
+                return None;
+            }
+        }
+        Some(name)
+    }
+}
@@ -7,7 +7,10 @@ use std::fmt;
 /// The arguments are ordered (so they have a position).
 /// Each argument also has a name and may have a default value.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
-pub struct FormalArgs(Vec<(Name, Option<Value>)>, Option<Name>);
+pub struct FormalArgs(
+    pub(crate) Vec<(Name, Option<Value>)>,
+    pub(crate) Option<Name>,
+);
 
 impl FormalArgs {
     /// Create a new FormalArgs.
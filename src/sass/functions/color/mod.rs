@@ -61,7 +61,7 @@ pub fn expose(m: &Scope, global: &mut FunctionMap) {
 fn get_color(s: &Scope, name: &'static str) -> Result<Color, Error> {
     get_checked(s, Name::from_static(name), check_color)
 }
-fn check_color(v: Value) -> Result<Color, String> {
+pub(super) fn check_color(v: Value) -> Result<Color, String> {
     match v {
         Value::Color(col, _) => Ok(col),
         v => Err(is_not(&v, "a color")),
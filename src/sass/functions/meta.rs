@@ -76,7 +76,7 @@ pub fn create_module() -> Scope {
     });
     def!(f, content_exists(), |s| {
         if let Some(content) = call_scope(s).get_content() {
-            Ok((!content.is_no_body()).into())
+            Ok(content.is_some().into())
         } else {
             Err(Error::error(
                 "content-exists() may only be called within a mixin.",
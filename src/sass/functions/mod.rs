@@ -19,6 +19,9 @@ mod math;
 mod meta;
 mod selector;
 mod string;
+mod typed;
+
+pub use self::typed::{FromSassValue, IntoSassValue, TypedFunction};
 
 type BuiltinFn = dyn Fn(&ScopeRef) -> Result<Value, Error> + Send + Sync;
 
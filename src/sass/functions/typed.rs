@@ -0,0 +1,296 @@
+//! Conversions for functions implemented in rust with typed arguments.
+use super::color::check_color;
+use super::{check, Function};
+use crate::css::{CssString, Value, ValueMap};
+use crate::parser::parse_value_data;
+use crate::sass::{FormalArgs, Name};
+use crate::value::{Color, ListSeparator, Number, Numeric};
+use crate::{Error, Scope};
+use std::convert::TryInto;
+use std::sync::Arc;
+
+/// A type that can be taken as an argument of a rust function.
+///
+/// If a value can't be converted, the error is a message like
+/// `"foo" is not a number.`, which is reported as a bad argument.
+pub trait FromSassValue: Sized {
+    /// Convert a value to this type.
+    fn from_sass_value(value: Value) -> Result<Self, String>;
+
+    /// True if the argument may be omitted.
+    ///
+    /// An optional argument has the default value `null`.
+    fn optional() -> bool {
+        false
+    }
+}
+
+/// A type that can be returned from a rust function.
+pub trait IntoSassValue {
+    /// Convert this to a value.
+    fn into_sass_value(self) -> Value;
+}
+
+impl FromSassValue for Value {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        Ok(value)
+    }
+}
+impl IntoSassValue for Value {
+    fn into_sass_value(self) -> Value {
+        self
+    }
+}
+
+impl FromSassValue for Numeric {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        check::numeric(value)
+    }
+}
+impl IntoSassValue for Numeric {
+    fn into_sass_value(self) -> Value {
+        self.into()
+    }
+}
+
+/// A `Number` argument must be unitless.
+impl FromSassValue for Number {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        check::unitless(value)
+    }
+}
+impl IntoSassValue for Number {
+    fn into_sass_value(self) -> Value {
+        Numeric::scalar(self).into()
+    }
+}
+
+/// An `i64` argument must be an integer, but may have a unit.
+impl FromSassValue for i64 {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        check::int(value)
+    }
+}
+impl IntoSassValue for i64 {
+    fn into_sass_value(self) -> Value {
+        Value::scalar(self)
+    }
+}
+
+impl FromSassValue for Color {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        check_color(value)
+    }
+}
+impl IntoSassValue for Color {
+    fn into_sass_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl FromSassValue for CssString {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        check::string(value)
+    }
+}
+impl IntoSassValue for CssString {
+    fn into_sass_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl FromSassValue for String {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        Ok(check::string(value)?.take_value())
+    }
+}
+/// A `String` is returned as an unquoted string.
+impl IntoSassValue for String {
+    fn into_sass_value(self) -> Value {
+        self.into()
+    }
+}
+
+/// Any value can be a `bool` argument, since any value is truthy or
+/// falsy.
+impl FromSassValue for bool {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        Ok(value.is_true())
+    }
+}
+impl IntoSassValue for bool {
+    fn into_sass_value(self) -> Value {
+        self.into()
+    }
+}
+
+/// A `Vec` argument is the items of a list (or a single value).
+impl<T: FromSassValue> FromSassValue for Vec<T> {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        value
+            .iter_items()
+            .into_iter()
+            .map(T::from_sass_value)
+            .collect()
+    }
+}
+/// A `Vec` is returned as a comma-separated list.
+impl<T: IntoSassValue> IntoSassValue for Vec<T> {
+    fn into_sass_value(self) -> Value {
+        Value::List(
+            self.into_iter().map(T::into_sass_value).collect(),
+            Some(ListSeparator::Comma),
+            false,
+        )
+    }
+}
+
+/// An `Option` argument is optional, and `None` if null.
+impl<T: FromSassValue> FromSassValue for Option<T> {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_sass_value(value).map(Some),
+        }
+    }
+    fn optional() -> bool {
+        true
+    }
+}
+/// `None` is returned as null.
+impl<T: IntoSassValue> IntoSassValue for Option<T> {
+    fn into_sass_value(self) -> Value {
+        self.map_or(Value::Null, T::into_sass_value)
+    }
+}
+
+impl FromSassValue for ValueMap {
+    fn from_sass_value(value: Value) -> Result<Self, String> {
+        value.try_into()
+    }
+}
+impl IntoSassValue for ValueMap {
+    fn into_sass_value(self) -> Value {
+        Value::Map(self)
+    }
+}
+
+/// A rust function with typed arguments, that can be made into a
+/// [`Function`] with [`Function::typed`].
+///
+/// This is implemented for functions and closures of up to six
+/// arguments, where each argument implements [`FromSassValue`] and
+/// the function returns a `Result` of something that implements
+/// [`IntoSassValue`].
+/// The `Args` type parameter is a tuple of the argument types.
+pub trait TypedFunction<Args>: Send + Sync + 'static {
+    /// Check which arguments are optional.
+    fn optional() -> Vec<bool>;
+    /// Call the function with the arguments in `scope`.
+    fn call(&self, scope: &Scope, names: &[Name]) -> Result<Value, Error>;
+}
+
+fn get_arg<T: FromSassValue>(scope: &Scope, name: &Name) -> Result<T, Error> {
+    T::from_sass_value(scope.get(name)?)
+        .map_err(|e| Error::BadArgument(name.clone(), e))
+}
+
+macro_rules! typed_function {
+    ($($arg:ident),*) => {
+        impl<Func, Ret, $($arg),*> TypedFunction<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Result<Ret, Error> + Send + Sync + 'static,
+            Ret: IntoSassValue,
+            $($arg: FromSassValue,)*
+        {
+            fn optional() -> Vec<bool> {
+                vec![$($arg::optional()),*]
+            }
+            #[allow(unused_variables, unused_mut)]
+            fn call(
+                &self,
+                scope: &Scope,
+                names: &[Name],
+            ) -> Result<Value, Error> {
+                let mut names = names.iter();
+                let result = self($(get_arg::<$arg>(scope, names.next().unwrap())?),*)?;
+                Ok(result.into_sass_value())
+            }
+        }
+    };
+}
+
+typed_function!();
+typed_function!(A);
+typed_function!(A, B);
+typed_function!(A, B, C);
+typed_function!(A, B, C, D);
+typed_function!(A, B, C, D, E);
+typed_function!(A, B, C, D, E, F);
+
+impl Function {
+    /// Create a new `Function` from a rust function with typed
+    /// arguments.
+    ///
+    /// The formal arguments of the function are named by `args`,
+    /// and the arguments that are `Option`s may be omitted.
+    /// Each argument is converted with [`FromSassValue`], and an
+    /// argument that can't be converted is reported as a bad argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names in `args` does not match the
+    /// number of arguments of `body`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::MemoryContext;
+    /// # use rsass::sass::{Function, Name};
+    /// # use rsass::value::Numeric;
+    /// # use rsass::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// fn double(x: Numeric) -> Result<Numeric, Error> {
+    ///     Ok(Numeric::new(x.value * 2, x.unit))
+    /// }
+    /// let name = Name::from("double");
+    /// let mut context = MemoryContext::new();
+    /// context.get_scope().define_function(
+    ///     name.clone(),
+    ///     Function::typed(&name, &["x"], double),
+    /// );
+    /// context.insert("main.scss", "a { b: double(3px) }");
+    /// let main = context.load_root("main")?;
+    /// assert_eq!(context.transform(main)?, b"a {\n  b: 6px;\n}\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn typed<Args, F>(name: &Name, args: &[&str], body: F) -> Self
+    where
+        F: TypedFunction<Args>,
+    {
+        let optional = F::optional();
+        assert_eq!(
+            args.len(),
+            optional.len(),
+            "Function {} takes {} arguments",
+            name,
+            optional.len(),
+        );
+        let names: Vec<Name> = args.iter().map(|&arg| arg.into()).collect();
+        let null = parse_value_data(b"null").expect("null");
+        let formal = names
+            .iter()
+            .zip(optional)
+            .map(|(name, optional)| {
+                (name.clone(), Some(null.clone()).filter(|_| optional))
+            })
+            .collect();
+        Function::builtin(
+            "",
+            name,
+            FormalArgs::new(formal),
+            Arc::new(move |scope| body.call(scope, &names)),
+        )
+    }
+}
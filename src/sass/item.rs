@@ -4,6 +4,15 @@ use std::collections::BTreeSet;
 
 /// Every sass file is a sequence of sass items.
 /// Scoping items contains further sequences of items.
+///
+/// This is the syntax tree of a parsed sass file, as returned by
+/// [`SourceFile::parse`][crate::input::SourceFile::parse], before any
+/// evaluation.
+/// Each item (except [`Item::None`]) has a source position, available
+/// from [`Item::pos`], and comments (including `//` comments) between
+/// items are preserved.
+/// Each [`Value`] and each parsed [`Selector`][super::Selector] also
+/// has a position.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Item {
     /// An `@import` directive.
@@ -23,7 +32,9 @@ pub enum Item {
     },
 
     /// An `@at-root` directive.
-    AtRoot(Selectors, Vec<Item>),
+    ///
+    /// The source position is the directive and its selectors.
+    AtRoot(Selectors, Vec<Item>, SourcePos),
     /// A generic `@` directive.
     AtRule {
         /// The name of this directive
@@ -36,13 +47,15 @@ pub enum Item {
         pos: SourcePos,
     },
     /// An `@debug` directive.
-    Debug(Value),
+    Debug(Value, SourcePos),
     /// An `@warn` directive.
-    Warn(Value),
+    Warn(Value, SourcePos),
     /// An `@error` directive.
     Error(Value, SourcePos),
 
     /// A `@mixin` directive, declaring a mixin.
+    ///
+    /// The source position is the `decl` of the [`Callable`].
     MixinDeclaration(String, Callable),
     /// An `@include` directive, calling a mixin.
     MixinCall(String, CallArgs, Option<Callable>, SourcePos),
@@ -50,16 +63,20 @@ pub enum Item {
     Content(CallArgs, SourcePos),
 
     /// An `@function` declaration.
+    ///
+    /// The source position is the `decl` of the [`Callable`].
     FunctionDeclaration(String, Callable),
     /// An `@return` statement in a function declaration.
     Return(Value, SourcePos),
 
     /// An `@if` conditional directive.
-    IfStatement(Value, Vec<Item>, Vec<Item>),
+    ///
+    /// The source position is the directive and its condition.
+    IfStatement(Value, Vec<Item>, Vec<Item>, SourcePos),
     /// An `@each` loop directive.
     ///
     /// The value may be or evaluate to a list.
-    Each(Vec<Name>, Value, Vec<Item>),
+    Each(Vec<Name>, Value, Vec<Item>, SourcePos),
     /// An `@for` loop directive.
     For {
         /// The name of the iteration variable.
@@ -72,35 +89,90 @@ pub enum Item {
         inclusive: bool,
         /// The body of the loop.
         body: Vec<Item>,
+        /// The source location of this loop (excluding the body).
+        pos: SourcePos,
     },
     /// An `@while` loop directive.
-    While(Value, Vec<Item>),
+    While(Value, Vec<Item>, SourcePos),
 
     /// An `@use` directive.
-    Use(SassString, UseAs, Vec<(Name, Value, bool)>, SourcePos),
+    ///
+    /// Each configured variable has a name, a value, a flag for
+    /// `!default`, and the position of the name and value.
+    Use(
+        SassString,
+        UseAs,
+        Vec<(Name, Value, bool, SourcePos)>,
+        SourcePos,
+    ),
     /// An `@forward` directive.
+    ///
+    /// The configured variables are like for [`Item::Use`].
     Forward(
         SassString,
         UseAs,
         Expose,
-        Vec<(Name, Value, bool)>,
+        Vec<(Name, Value, bool, SourcePos)>,
         SourcePos,
     ),
 
     /// A sass rule; selectors followed by a block of items.
-    Rule(Selectors, Vec<Item>),
+    ///
+    /// The source position is the position of the selectors.
+    Rule(Selectors, Vec<Item>, SourcePos),
     /// A sass namespace rule; a name followed by a block of properties.
-    NamespaceRule(SassString, Value, Vec<Item>),
-    /// A sass property; a name and a value.
-    Property(SassString, Value),
+    NamespaceRule(SassString, Value, Vec<Item>, SourcePos),
+    /// A sass property; a name, a value, and a source position.
+    Property(SassString, Value, SourcePos),
     /// A custom property.
-    CustomProperty(SassString, SassString),
+    CustomProperty(SassString, SassString, SourcePos),
     /// A comment (that might be preserved for the output).
-    Comment(SassString),
+    Comment(SassString, SourcePos),
+    /// A silent (`//`) comment, never preserved for the output.
+    ///
+    /// The string is the text after the `//`.
+    SilentComment(String, SourcePos),
     /// Nothing
     None,
 }
 
+impl Item {
+    /// The source position of this item.
+    ///
+    /// Only [`Item::None`] has no position.
+    /// For items with a body, the position is the start of the item,
+    /// up to (but not including) the body.
+    pub fn pos(&self) -> Option<&SourcePos> {
+        match self {
+            Item::Import(_, _, pos)
+            | Item::VariableDeclaration { pos, .. }
+            | Item::AtRoot(_, _, pos)
+            | Item::AtRule { pos, .. }
+            | Item::Debug(_, pos)
+            | Item::Warn(_, pos)
+            | Item::Error(_, pos)
+            | Item::MixinCall(_, _, _, pos)
+            | Item::Content(_, pos)
+            | Item::Return(_, pos)
+            | Item::IfStatement(_, _, _, pos)
+            | Item::Each(_, _, _, pos)
+            | Item::For { pos, .. }
+            | Item::While(_, _, pos)
+            | Item::Use(_, _, _, pos)
+            | Item::Forward(_, _, _, _, pos)
+            | Item::Rule(_, _, pos)
+            | Item::NamespaceRule(_, _, _, pos)
+            | Item::Property(_, _, pos)
+            | Item::CustomProperty(_, _, pos)
+            | Item::Comment(_, pos)
+            | Item::SilentComment(_, pos) => Some(pos),
+            Item::MixinDeclaration(_, callable)
+            | Item::FunctionDeclaration(_, callable) => Some(&callable.decl),
+            Item::None => None,
+        }
+    }
+}
+
 /// How an `@forward`-ed module should be exposed.
 ///
 /// As directed by the `show` or `hide` keywords or their absense.
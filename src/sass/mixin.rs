@@ -1,21 +1,28 @@
 use super::functions::get_string;
-use super::{CallArgs, Callable, Closure, FormalArgs, Name, Value};
-use crate::css::{CssString, ValueToMapError};
-use crate::input::{Context, Loader, Parsed, SourceKind};
+use super::{CallArgs, Callable, Closure, Config, FormalArgs, Name};
+use crate::css::{self, CssString, ValueToMapError};
+use crate::input::Parsed;
 use crate::ordermap::OrderMap;
-use crate::parser::SourcePos;
+use crate::parser::{parse_value_data, SourcePos};
 use crate::{Error, Scope, ScopeRef};
+use std::collections::BTreeMap;
 use std::convert::TryInto;
+use std::sync::Arc;
+
+type BuiltinMixinFn =
+    dyn Fn(&ScopeRef, bool) -> Result<Vec<MixinItem>, Error> + Send + Sync;
 
 /// A declared mixin
 #[derive(Clone)]
 pub enum MixinDecl {
     /// an actual mixin
     Sass(Closure),
-    /// The body of a mixin call that does not have a body
+    /// A mixin that emits nothing
     NoBody,
     /// The special `load-css` mixin.
     LoadCss,
+    /// A mixin implemented in rust.
+    Builtin(BuiltinMixin),
 }
 
 impl From<Closure> for MixinDecl {
@@ -25,104 +32,146 @@ impl From<Closure> for MixinDecl {
 }
 
 impl MixinDecl {
-    pub(crate) fn get(
-        self,
+    /// Create a new mixin implemented in rust.
+    ///
+    /// The `body` is called with a scope containing the evaluated
+    /// arguments (and the selectors of the rule where the mixin is
+    /// included), and a flag that is true if the mixin is included
+    /// with a `@content` block.
+    /// It returns the items to emit where the mixin is included.
+    pub fn builtin(
+        module: &str,
+        name: &Name,
+        args: FormalArgs,
+        body: Arc<BuiltinMixinFn>,
+    ) -> Self {
+        let pos = SourcePos::mock_mixin(name, &args, module);
+        MixinDecl::Builtin(BuiltinMixin { args, pos, body })
+    }
+}
+
+/// A mixin implemented in rust.
+#[derive(Clone)]
+pub struct BuiltinMixin {
+    args: FormalArgs,
+    pos: SourcePos,
+    body: Arc<BuiltinMixinFn>,
+}
+
+impl BuiltinMixin {
+    /// Evaluate the arguments of a call to this mixin.
+    ///
+    /// Returns the scope of the call, with the selectors of `scope`.
+    pub(crate) fn eval_args(
+        &self,
         name: &str,
         scope: ScopeRef,
         call_args: &CallArgs,
         call_pos: &SourcePos,
-        file_context: &mut Context<impl Loader>,
-    ) -> Result<Mixin, Error> {
-        match self {
-            MixinDecl::Sass(decl) => {
-                let sel = scope.get_selectors().clone();
-                Ok(Mixin {
-                    scope: decl
-                        .body
-                        .args
-                        .evalcall(
-                            ScopeRef::sub_selectors(decl.scope.clone(), sel),
-                            call_args.evaluate(scope)?,
-                        )
-                        .map_err(|e| {
-                            e.decl_called(
-                                call_pos.in_call(name),
-                                decl.body.decl.clone(),
-                            )
-                        })?,
-                    body: Parsed::Scss(decl.body.body),
-                })
-            }
-            MixinDecl::NoBody => Ok(Mixin::empty(scope)),
-            MixinDecl::LoadCss => {
-                let fargs = FormalArgs::new(vec![
-                    (name!(url), None),
-                    (name!(with), Some(Value::Null)),
-                ]);
-                let pos = SourcePos::mock_mixin(
-                    &name!(load_css),
-                    &fargs,
-                    "sass:meta",
-                );
-                let call_pos2 = call_pos.clone();
-                let argscope = fargs
-                    .evalcall(
-                        scope.clone(),
-                        call_args.evaluate(scope.clone())?,
-                    )
-                    .map_err(|e| e.decl_called(call_pos2, pos))?;
-                let call_pos2 = call_pos.clone();
-                let url = get_string(&argscope, name!(url)).map_err(|e| {
-                    Error::BadCall(format!("{:?}", e), call_pos2, None)
-                })?;
-                let call_pos2 = call_pos.clone();
-                let with = get_opt_map(&argscope, name!(with))
-                    .map_err(|e| Error::BadCall(e, call_pos2, None))?;
+    ) -> Result<ScopeRef, Error> {
+        let sel = scope.get_selectors().clone();
+        self.args
+            .evalcall(
+                ScopeRef::sub_selectors(
+                    ScopeRef::new_global(scope.get_format()),
+                    sel,
+                ),
+                call_args.evaluate(scope)?,
+            )
+            .map_err(|e| {
+                e.decl_called(call_pos.in_call(name), self.pos.clone())
+            })
+    }
 
-                if url.value().starts_with("sass:") {
-                    if with.unwrap_or_default().is_empty() {
-                        return Ok(Mixin::empty(scope));
-                    } else {
-                        return Err(Error::BadCall(
-                            format!(
-                                "Built-in module {} can't be configured.",
-                                url.value()
-                            ),
-                            call_pos.clone(),
-                            None,
-                        ));
-                    }
-                }
-                let call_pos2 = call_pos.clone();
-                let source = file_context
-                    .find_file(url.value(), SourceKind::load_css(call_pos))?
-                    .ok_or_else(|| {
-                        Error::BadCall(
-                            "Can't find stylesheet to import.".into(),
-                            call_pos2,
-                            None,
-                        )
-                    })?;
+    /// Call the body of this mixin in a scope from `eval_args`.
+    pub(crate) fn emit(
+        &self,
+        scope: &ScopeRef,
+        has_content: bool,
+    ) -> Result<Vec<MixinItem>, Error> {
+        (self.body)(scope, has_content)
+    }
+}
 
-                let scope = ScopeRef::sub(scope);
-                if let Some(with) = with {
-                    for (key, value) in with.into_iter() {
-                        scope.define(key.into(), value)?;
-                    }
-                }
-                file_context.unlock_loading(&source);
-                Ok(Mixin {
-                    scope,
-                    body: source.parse()?,
-                })
-            }
-        }
+/// An item emitted by a mixin implemented in rust.
+#[derive(Clone, Debug)]
+pub enum MixinItem {
+    /// An item in the body of the current rule, such as a property.
+    Body(css::BodyItem),
+    /// A nested rule.
+    ///
+    /// The selectors are relative to the selectors where the mixin is
+    /// included, and may contain a back reference (`&`).
+    Rule(css::Selectors, Vec<MixinItem>),
+    /// The `@content` block of the mixin call, if any.
+    Content,
+}
+
+impl From<css::BodyItem> for MixinItem {
+    fn from(item: css::BodyItem) -> Self {
+        MixinItem::Body(item)
     }
-    pub(crate) fn is_no_body(&self) -> bool {
-        matches!(self, MixinDecl::NoBody)
+}
+impl From<css::Property> for MixinItem {
+    fn from(prop: css::Property) -> Self {
+        MixinItem::Body(prop.into())
     }
 }
 
+/// Evaluate the arguments of a call to the `meta.load-css` mixin.
+///
+/// Returns the url and the configuration, or `None` if the url
+/// refers to a built-in module, in which case there is nothing to
+/// load.
+pub(crate) fn load_css_args(
+    scope: ScopeRef,
+    call_args: &CallArgs,
+    call_pos: &SourcePos,
+) -> Result<Option<(String, Config)>, Error> {
+    let fargs = FormalArgs::new(vec![
+        (name!(url), None),
+        (name!(with), Some(parse_value_data(b"null").expect("null"))),
+    ]);
+    let pos = SourcePos::mock_mixin(&name!(load_css), &fargs, "sass:meta");
+    let argscope = fargs
+        .evalcall(scope.clone(), call_args.evaluate(scope)?)
+        .map_err(|e| e.decl_called(call_pos.clone(), pos))?;
+    let url = get_string(&argscope, name!(url)).map_err(|e| {
+        Error::BadCall(format!("{:?}", e), call_pos.clone(), None)
+    })?;
+    let with = get_opt_map(&argscope, name!(with))
+        .map_err(|e| Error::BadCall(e, call_pos.clone(), None))?
+        .unwrap_or_default();
+
+    if url.value().starts_with("sass:") {
+        return if with.is_empty() {
+            Ok(None)
+        } else {
+            Err(Error::BadCall(
+                format!(
+                    "Built-in module {} can't be configured.",
+                    url.value()
+                ),
+                call_pos.clone(),
+                None,
+            ))
+        };
+    }
+    let mut config = BTreeMap::new();
+    for (key, value) in with.into_iter() {
+        let name: Name = key.into();
+        if config.contains_key(&name) {
+            return Err(Error::BadCall(
+                format!("The variable ${} was configured twice.", name),
+                call_pos.clone(),
+                None,
+            ));
+        }
+        config.insert(name, (value, call_pos.clone()));
+    }
+    Ok(Some((url.value().into(), Config::new(config, call_pos))))
+}
+
 /// A mixin is a callable body of items.
 #[derive(Clone)]
 pub struct Mixin {
@@ -132,22 +181,53 @@ pub struct Mixin {
     pub body: Parsed,
 }
 
+/// The `@content` block of a mixin call, or `None` for a mixin call
+/// without a block.
+pub(crate) type Content = Option<Closure>;
+
 impl Mixin {
-    fn empty(scope: ScopeRef) -> Self {
-        Mixin {
-            scope,
-            body: Parsed::Css(vec![]),
-        }
+    /// Evaluate the arguments of a call to a mixin declared in sass.
+    pub(crate) fn for_call(
+        decl: Closure,
+        name: &str,
+        scope: ScopeRef,
+        call_args: &CallArgs,
+        call_pos: &SourcePos,
+    ) -> Result<Mixin, Error> {
+        let sel = scope.get_selectors().clone();
+        Ok(Mixin {
+            scope: decl
+                .body
+                .args
+                .evalcall(
+                    ScopeRef::sub_selectors(decl.scope.clone(), sel),
+                    call_args.evaluate(scope)?,
+                )
+                .map_err(|e| {
+                    e.decl_called(
+                        call_pos.in_call(name),
+                        decl.body.decl.clone(),
+                    )
+                })?,
+            body: Parsed::Scss(decl.body.body),
+        })
     }
     pub(crate) fn define_content(
         &self,
         scope: &ScopeRef,
         body: &Option<Callable>,
     ) {
-        self.scope.define_content(match body {
-            Some(body) => body.closure(scope).into(),
-            None => MixinDecl::NoBody,
-        })
+        Mixin::define_content_in(&self.scope, scope, body)
+    }
+    /// Define the `@content` of a mixin call in `mscope`.
+    ///
+    /// The `body` of the call is a closure in the calling `scope`.
+    pub(crate) fn define_content_in(
+        mscope: &ScopeRef,
+        scope: &ScopeRef,
+        body: &Option<Callable>,
+    ) {
+        mscope.define_content(body.as_ref().map(|body| body.closure(scope)))
     }
 }
 
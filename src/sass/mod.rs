@@ -12,6 +12,7 @@ macro_rules! name {
 
 mod call_args;
 mod callable;
+mod config;
 mod formal_args;
 mod functions;
 mod item;
@@ -20,13 +21,18 @@ mod name;
 mod selectors;
 mod string;
 mod value;
+pub mod visit;
 
 pub use self::call_args::CallArgs;
 pub use self::callable::{Call, Callable, Closure};
+pub(crate) use self::config::Config;
 pub use self::formal_args::{ArgsError, FormalArgs};
-pub use self::functions::{get_global_module, Function};
+pub use self::functions::{
+    get_global_module, FromSassValue, Function, IntoSassValue, TypedFunction,
+};
 pub use self::item::{Expose, Item, UseAs};
-pub use self::mixin::{Mixin, MixinDecl};
+pub(crate) use self::mixin::{load_css_args, Content};
+pub use self::mixin::{BuiltinMixin, Mixin, MixinDecl, MixinItem};
 pub use self::name::Name;
 pub use self::selectors::{Selector, SelectorPart, Selectors};
 pub use self::string::{SassString, StringPart};
@@ -8,13 +8,13 @@
 //! leafs of simple selectors in some future release.
 use crate::css;
 use crate::sass::SassString;
-use crate::{Error, ParseError, ScopeRef};
+use crate::{Error, ParseError, ScopeRef, SourcePos};
 
 /// A full set of selectors
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub struct Selectors {
     /// The actual selectors.
-    s: Vec<Selector>,
+    pub(crate) s: Vec<Selector>,
 }
 
 impl Selectors {
@@ -56,19 +56,34 @@ impl Selectors {
 /// A selector does not contain `,`.  If it does, it is a `Selectors`,
 /// where each of the parts separated by the comma is a `Selector`.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
-pub struct Selector(Vec<SelectorPart>);
+pub struct Selector {
+    pub(crate) parts: Vec<SelectorPart>,
+    pub(crate) pos: Option<SourcePos>,
+}
 
 impl Selector {
     /// Get the root (empty) selector.
     pub fn root() -> Self {
-        Selector(vec![])
+        Selector::new(vec![])
     }
     /// Create a new selector from parts.
-    pub fn new(s: Vec<SelectorPart>) -> Self {
-        Selector(s)
+    pub fn new(parts: Vec<SelectorPart>) -> Self {
+        Selector { parts, pos: None }
+    }
+    /// Set the source position of this selector.
+    pub fn with_pos(mut self, pos: SourcePos) -> Self {
+        self.pos = Some(pos);
+        self
+    }
+    /// The source position of this selector.
+    ///
+    /// Every parsed selector has a position, but the root selector
+    /// does not.
+    pub fn pos(&self) -> Option<&SourcePos> {
+        self.pos.as_ref()
     }
     fn eval(&self, scope: ScopeRef) -> Result<css::Selector, Error> {
-        self.0
+        self.parts
             .iter()
             .map(|sp| sp.eval(scope.clone()))
             .collect::<Result<_, _>>()
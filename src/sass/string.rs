@@ -11,7 +11,7 @@ use std::fmt::{self, Write};
 /// may occur.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub struct SassString {
-    parts: Vec<StringPart>,
+    pub(crate) parts: Vec<StringPart>,
     quotes: Quotes,
 }
 
@@ -8,59 +8,107 @@ use crate::ScopeRef;
 use num_traits::Zero;
 
 /// A sass value.
+///
+/// Each value has a position in the sass source, available from
+/// [`Value::pos`].
+/// For compound values, such as lists and binary operations, the
+/// position covers the entire value.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Value {
     /// A special kind of escape.  Only really used for !important.
-    Bang(String),
+    Bang(String, SourcePos),
     /// A call has a name and an argument (which may be multi).
     Call(SassString, CallArgs, SourcePos),
     /// A literal string value (quoted or not).
-    Literal(SassString),
+    Literal(SassString, SourcePos),
     /// A comma- or space separated list of values, with or without brackets.
-    List(Vec<Value>, Option<ListSeparator>, bool),
+    List(Vec<Value>, Option<ListSeparator>, bool, SourcePos),
     /// A Numeric value is a rational value with a Unit (which may be
     /// Unit::None) and flags.
-    Numeric(Numeric),
+    Numeric(Numeric, SourcePos),
     /// "(a/b) and a/b differs semantically.  Parens means the value
     /// should be evaluated numerically if possible, without parens /
     /// is not allways division.
     /// The boolean tells if the paren itself should be kept for output.
-    Paren(Box<Value>, bool),
+    Paren(Box<Value>, bool, SourcePos),
     /// A variable reference to be loaded when the value is evaluated.
     Variable(Name, SourcePos),
     /// Both a numerical and original string representation,
     /// since case and length should be preserved (#AbC vs #aabbcc).
-    Color(Rgba, Option<String>),
+    Color(Rgba, Option<String>, SourcePos),
     /// The null value.
-    Null,
+    Null(SourcePos),
     /// The true boolean value.
-    True,
+    True(SourcePos),
     /// The false boolean value.
-    False,
+    False(SourcePos),
     /// A binary operation, two operands and an operator.
     /// The boolean represents possible whitespace.
-    BinOp(Box<Value>, bool, Operator, bool, Box<Value>),
+    BinOp(Box<Value>, bool, Operator, bool, Box<Value>, SourcePos),
     /// A unary operator and its operand.
-    UnaryOp(Operator, Box<Value>),
+    UnaryOp(Operator, Box<Value>, SourcePos),
     /// A map in sass source is just a list of key/value parirs.
     /// Actual map behaviour comes after evaluating it.
-    Map(Vec<(Value, Value)>),
+    Map(Vec<(Value, Value)>, SourcePos),
     /// The magic value "&", exanding to the current selectors.
-    HereSelector,
+    HereSelector(SourcePos),
     /// A unicode range for font selections. U+NN, U+N?, U+NN-MM.
     /// The string is the entire value, including the "U+" tag.
-    UnicodeRange(String),
+    UnicodeRange(String, SourcePos),
 }
 
 impl Value {
     /// Create a new scalar value.
     pub fn scalar(v: impl Into<Number>) -> Self {
-        Value::Numeric(Numeric::scalar(v))
+        Numeric::scalar(v).into()
+    }
+
+    /// The source position of this value.
+    pub fn pos(&self) -> &SourcePos {
+        match self {
+            Value::Bang(_, pos)
+            | Value::Call(_, _, pos)
+            | Value::Literal(_, pos)
+            | Value::List(_, _, _, pos)
+            | Value::Numeric(_, pos)
+            | Value::Paren(_, _, pos)
+            | Value::Variable(_, pos)
+            | Value::Color(_, _, pos)
+            | Value::Null(pos)
+            | Value::True(pos)
+            | Value::False(pos)
+            | Value::BinOp(_, _, _, _, _, pos)
+            | Value::UnaryOp(_, _, pos)
+            | Value::Map(_, pos)
+            | Value::HereSelector(pos)
+            | Value::UnicodeRange(_, pos) => pos,
+        }
+    }
+
+    pub(crate) fn pos_mut(&mut self) -> &mut SourcePos {
+        match self {
+            Value::Bang(_, pos)
+            | Value::Call(_, _, pos)
+            | Value::Literal(_, pos)
+            | Value::List(_, _, _, pos)
+            | Value::Numeric(_, pos)
+            | Value::Paren(_, _, pos)
+            | Value::Variable(_, pos)
+            | Value::Color(_, _, pos)
+            | Value::Null(pos)
+            | Value::True(pos)
+            | Value::False(pos)
+            | Value::BinOp(_, _, _, _, _, pos)
+            | Value::UnaryOp(_, _, pos)
+            | Value::Map(_, pos)
+            | Value::HereSelector(pos)
+            | Value::UnicodeRange(_, pos) => pos,
+        }
     }
 
     /// All values other than `False` and `Null` should be considered true.
     pub fn is_true(&self) -> bool {
-        !matches!(self, Value::False | Value::Null)
+        !matches!(self, Value::False(_) | Value::Null(_))
     }
 
     /// Return true if this value is null.
@@ -69,8 +117,8 @@ impl Value {
     /// non-null values is also considered null.
     pub fn is_null(&self) -> bool {
         match *self {
-            Value::Null => true,
-            Value::List(ref list, _, false) => {
+            Value::Null(_) => true,
+            Value::List(ref list, _, false, _) => {
                 list.iter().all(|v| v.is_null())
             }
             _ => false,
@@ -89,9 +137,9 @@ impl Value {
         arithmetic: bool,
     ) -> Result<css::Value, Error> {
         Ok(match self {
-            Value::Bang(s) => css::Value::Bang(s.clone()),
-            Value::Literal(s) => s.evaluate(scope)?.into(),
-            Value::Paren(v, expl) => {
+            Value::Bang(s, _) => css::Value::Bang(s.clone()),
+            Value::Literal(s, _) => s.evaluate(scope)?.into(),
+            Value::Paren(v, expl, _) => {
                 let v = v.do_evaluate(scope, !expl)?;
                 if *expl
                     || v == css::Value::Null
@@ -103,14 +151,14 @@ impl Value {
                     v
                 }
             }
-            Value::Color(rgba, name) => {
+            Value::Color(rgba, name, _) => {
                 css::Value::Color(rgba.clone().into(), name.clone())
             }
             Value::Variable(name, pos) => scope
                 .get(name)
                 .map_err(|e| e.at(pos.clone()))?
                 .into_calculated(),
-            Value::List(v, s, b) => css::Value::List(
+            Value::List(v, s, b, _) => css::Value::List(
                 v.iter()
                     .map(|v| v.do_evaluate(scope.clone(), false))
                     .collect::<Result<_, _>>()?,
@@ -161,10 +209,10 @@ impl Value {
                 let name = name.evaluate(scope)?;
                 css::Value::Call(name.value().into(), call.args)
             }
-            Value::Numeric(num) => {
+            Value::Numeric(num, _) => {
                 css::Value::Numeric(num.clone(), arithmetic)
             }
-            Value::Map(m) => {
+            Value::Map(m, _) => {
                 let mut items = css::ValueMap::new();
                 for (k, v) in m.iter() {
                     let k = k.do_evaluate(scope.clone(), arithmetic)?;
@@ -175,10 +223,10 @@ impl Value {
                 }
                 css::Value::Map(items)
             }
-            Value::Null => css::Value::Null,
-            Value::True => css::Value::True,
-            Value::False => css::Value::False,
-            Value::BinOp(a, s1, op, s2, b) => {
+            Value::Null(_) => css::Value::Null,
+            Value::True(_) => css::Value::True,
+            Value::False(_) => css::Value::False,
+            Value::BinOp(a, s1, op, s2, b, _) => {
                 if *op == Operator::And {
                     let a = a.do_evaluate(scope.clone(), true)?;
                     if a.is_true() {
@@ -223,7 +271,7 @@ impl Value {
                     })
                 }
             }
-            Value::UnaryOp(op, v) => {
+            Value::UnaryOp(op, v, _) => {
                 let value = v.do_evaluate(scope, true)?;
                 match (op.clone(), value) {
                     (Operator::Not, css::Value::Numeric(v, _)) => {
@@ -243,8 +291,8 @@ impl Value {
                     (op, v) => css::Value::UnaryOp(op, Box::new(v)),
                 }
             }
-            Value::HereSelector => scope.get_selectors().clone().into(),
-            Value::UnicodeRange(s) => css::Value::UnicodeRange(s.clone()),
+            Value::HereSelector(_) => scope.get_selectors().clone().into(),
+            Value::UnicodeRange(s, _) => css::Value::UnicodeRange(s.clone()),
         })
     }
 
@@ -254,20 +302,20 @@ impl Value {
     pub fn inspect(&self, out: &mut std::fmt::Formatter) -> std::fmt::Result {
         use std::fmt::Display;
         match *self {
-            Value::Bang(ref s) => write!(out, "!{}", s),
-            Value::Literal(ref s) => {
+            Value::Bang(ref s, _) => write!(out, "!{}", s),
+            Value::Literal(ref s, _) => {
                 if let Some(s) = s.single_raw() {
                     out.write_str(s)
                 } else {
                     write!(out, "{:?}", s)
                 }
             }
-            Value::Paren(ref v, _expl) => {
+            Value::Paren(ref v, _expl, _) => {
                 out.write_str("(")?;
                 v.inspect(out)?;
                 out.write_str(")")
             }
-            Value::Color(ref rgba, ref name) => {
+            Value::Color(ref rgba, ref name, _) => {
                 if let Some(name) = name {
                     out.write_str(name)
                 } else {
@@ -279,7 +327,7 @@ impl Value {
             Value::Variable(ref name, ref _pos) => {
                 write!(out, "${}", name)
             }
-            Value::List(ref v, s, b) => {
+            Value::List(ref v, s, b, _) => {
                 if b {
                     out.write_str("(")?;
                 }
@@ -302,10 +350,10 @@ impl Value {
             Value::Call(ref name, ref args, ref _pos) => {
                 write!(out, "{}({:?})", name, args)
             }
-            Value::Numeric(ref num) => {
+            Value::Numeric(ref num, _) => {
                 num.format(Format::introspect()).fmt(out)
             }
-            Value::Map(ref m) => {
+            Value::Map(ref m, _) => {
                 out.write_str("(")?;
                 if let Some(((k, v), rest)) = m.split_first() {
                     k.inspect(out)?;
@@ -320,26 +368,29 @@ impl Value {
                 }
                 out.write_str(")")
             }
-            Value::Null => out.write_str("null"),
-            Value::True => out.write_str("true"),
-            Value::False => out.write_str("false"),
-            Value::BinOp(ref a, _, ref op, _, ref b) => {
+            Value::Null(_) => out.write_str("null"),
+            Value::True(_) => out.write_str("true"),
+            Value::False(_) => out.write_str("false"),
+            Value::BinOp(ref a, _, ref op, _, ref b, _) => {
                 a.inspect(out)?;
                 op.fmt(out)?;
                 b.inspect(out)
             }
-            Value::UnaryOp(ref op, ref v) => {
+            Value::UnaryOp(ref op, ref v, _) => {
                 op.fmt(out)?;
                 v.inspect(out)
             }
-            Value::HereSelector => out.write_str("&"),
-            Value::UnicodeRange(ref s) => s.fmt(out),
+            Value::HereSelector(_) => out.write_str("&"),
+            Value::UnicodeRange(ref s, _) => s.fmt(out),
         }
     }
 }
 
 impl From<Numeric> for Value {
     fn from(num: Numeric) -> Self {
-        Value::Numeric(num)
+        let pos = SourcePos::mock_value(
+            &num.format(Format::introspect()).to_string(),
+        );
+        Value::Numeric(num, pos)
     }
 }
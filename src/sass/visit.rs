@@ -0,0 +1,619 @@
+//! Traversal of the sass syntax tree.
+//!
+//! Implement [`Visit`] to inspect, or [`VisitMut`] to modify, a tree
+//! of parsed [`Item`]s.
+//! Each method of the traits has a default implementation that calls
+//! the corresponding `walk_` function, which visits the children of
+//! the node.
+//! So a visitor only needs to override the methods for the nodes it
+//! is interested in, and call the `walk_` function from the override
+//! if the children of that node should still be visited.
+//!
+//! # Example
+//!
+//! Find all uses of a variable:
+//!
+//! ```
+//! # use rsass::input::{Parsed, SourceFile, SourceName};
+//! use rsass::sass::visit::{walk_value, Visit};
+//! use rsass::sass::{Name, Value};
+//!
+//! struct FindVar(Name, Vec<u32>);
+//!
+//! impl Visit for FindVar {
+//!     fn visit_value(&mut self, value: &Value) {
+//!         match value {
+//!             Value::Variable(name, pos) if *name == self.0 => {
+//!                 self.1.push(pos.line_no())
+//!             }
+//!             value => walk_value(self, value),
+//!         }
+//!     }
+//! }
+//!
+//! let file = SourceFile::scss_bytes(
+//!     "$x: 2;\na {\n  b: 1 + $x;\n  @if $x > 1 { c: d }\n}\n",
+//!     SourceName::root("x.scss"),
+//! );
+//! let mut find = FindVar(Name::from("x"), vec![]);
+//! if let Parsed::Scss(items) = file.parse()? {
+//!     find.visit_items(&items);
+//! }
+//! assert_eq!(find.1, [3, 4]);
+//! # Ok::<(), rsass::Error>(())
+//! ```
+use super::{
+    CallArgs, Callable, Item, SassString, Selector, SelectorPart, Selectors,
+    StringPart, Value,
+};
+use crate::parser::SourcePos;
+
+/// A visitor of a sass syntax tree.
+///
+/// See the [module documentation](self).
+pub trait Visit {
+    /// Visit a sequence of items, e.g. a file or the body of a rule.
+    fn visit_items(&mut self, items: &[Item]) {
+        walk_items(self, items)
+    }
+    /// Visit an item.
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item)
+    }
+    /// Visit a value.
+    fn visit_value(&mut self, value: &Value) {
+        walk_value(self, value)
+    }
+    /// Visit a string that may contain interpolations.
+    fn visit_sass_string(&mut self, s: &SassString) {
+        walk_sass_string(self, s)
+    }
+    /// Visit the selectors of a rule (or `@at-root` directive).
+    fn visit_selectors(&mut self, selectors: &Selectors) {
+        walk_selectors(self, selectors)
+    }
+    /// Visit a single selector.
+    fn visit_selector(&mut self, selector: &Selector) {
+        walk_selector(self, selector)
+    }
+    /// Visit the arguments of a function or mixin call.
+    fn visit_call_args(&mut self, args: &CallArgs) {
+        walk_call_args(self, args)
+    }
+    /// Visit a mixin or function declaration.
+    ///
+    /// The default values of the formal arguments are visited before
+    /// the body.
+    fn visit_callable(&mut self, callable: &Callable) {
+        walk_callable(self, callable)
+    }
+    /// Visit the source position of a node.
+    ///
+    /// The walk functions call this for the node they walk, before
+    /// its children.
+    /// The default implementation does nothing.
+    fn visit_pos(&mut self, _pos: &SourcePos) {}
+}
+
+/// Visit each item in `items`.
+pub fn walk_items<V: Visit + ?Sized>(v: &mut V, items: &[Item]) {
+    for item in items {
+        v.visit_item(item);
+    }
+}
+
+/// Visit the position, values, strings, selectors and bodies of `item`.
+pub fn walk_item<V: Visit + ?Sized>(v: &mut V, item: &Item) {
+    match item {
+        Item::Import(names, args, pos) => {
+            v.visit_pos(pos);
+            for name in names {
+                v.visit_sass_string(name);
+            }
+            v.visit_value(args);
+        }
+        Item::VariableDeclaration { val, pos, .. } => {
+            v.visit_pos(pos);
+            v.visit_value(val);
+        }
+        Item::AtRoot(selectors, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_selectors(selectors);
+            v.visit_items(body);
+        }
+        Item::AtRule {
+            name,
+            args,
+            body,
+            pos,
+        } => {
+            v.visit_pos(pos);
+            v.visit_sass_string(name);
+            v.visit_value(args);
+            if let Some(body) = body {
+                v.visit_items(body);
+            }
+        }
+        Item::Debug(value, pos)
+        | Item::Warn(value, pos)
+        | Item::Error(value, pos)
+        | Item::Return(value, pos) => {
+            v.visit_pos(pos);
+            v.visit_value(value);
+        }
+        Item::MixinDeclaration(_, callable)
+        | Item::FunctionDeclaration(_, callable) => {
+            v.visit_callable(callable)
+        }
+        Item::MixinCall(_, args, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_call_args(args);
+            if let Some(body) = body {
+                v.visit_callable(body);
+            }
+        }
+        Item::Content(args, pos) => {
+            v.visit_pos(pos);
+            v.visit_call_args(args);
+        }
+        Item::IfStatement(cond, body, else_body, pos) => {
+            v.visit_pos(pos);
+            v.visit_value(cond);
+            v.visit_items(body);
+            v.visit_items(else_body);
+        }
+        Item::Each(_, values, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_value(values);
+            v.visit_items(body);
+        }
+        Item::For {
+            from,
+            to,
+            body,
+            pos,
+            ..
+        } => {
+            v.visit_pos(pos);
+            v.visit_value(from);
+            v.visit_value(to);
+            v.visit_items(body);
+        }
+        Item::While(cond, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_value(cond);
+            v.visit_items(body);
+        }
+        Item::Use(url, _, config, pos)
+        | Item::Forward(url, _, _, config, pos) => {
+            v.visit_pos(pos);
+            v.visit_sass_string(url);
+            for (_, value, _, pos) in config {
+                v.visit_pos(pos);
+                v.visit_value(value);
+            }
+        }
+        Item::Rule(selectors, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_selectors(selectors);
+            v.visit_items(body);
+        }
+        Item::NamespaceRule(name, value, body, pos) => {
+            v.visit_pos(pos);
+            v.visit_sass_string(name);
+            v.visit_value(value);
+            v.visit_items(body);
+        }
+        Item::Property(name, value, pos) => {
+            v.visit_pos(pos);
+            v.visit_sass_string(name);
+            v.visit_value(value);
+        }
+        Item::CustomProperty(name, value, pos) => {
+            v.visit_pos(pos);
+            v.visit_sass_string(name);
+            v.visit_sass_string(value);
+        }
+        Item::Comment(text, pos) => {
+            v.visit_pos(pos);
+            v.visit_sass_string(text);
+        }
+        Item::SilentComment(_, pos) => v.visit_pos(pos),
+        Item::None => (),
+    }
+}
+
+/// Visit the position, component values and strings of `value`.
+pub fn walk_value<V: Visit + ?Sized>(v: &mut V, value: &Value) {
+    v.visit_pos(value.pos());
+    match value {
+        Value::Call(name, args, _) => {
+            v.visit_sass_string(name);
+            v.visit_call_args(args);
+        }
+        Value::Literal(s, _) => v.visit_sass_string(s),
+        Value::List(items, ..) => {
+            for item in items {
+                v.visit_value(item);
+            }
+        }
+        Value::Paren(value, ..) | Value::UnaryOp(_, value, _) => {
+            v.visit_value(value)
+        }
+        Value::BinOp(a, _, _, _, b, _) => {
+            v.visit_value(a);
+            v.visit_value(b);
+        }
+        Value::Map(items, _) => {
+            for (key, value) in items {
+                v.visit_value(key);
+                v.visit_value(value);
+            }
+        }
+        Value::Bang(..)
+        | Value::Numeric(..)
+        | Value::Variable(..)
+        | Value::Color(..)
+        | Value::Null(_)
+        | Value::True(_)
+        | Value::False(_)
+        | Value::HereSelector(_)
+        | Value::UnicodeRange(..) => (),
+    }
+}
+
+/// Visit the interpolated values of `s`.
+pub fn walk_sass_string<V: Visit + ?Sized>(v: &mut V, s: &SassString) {
+    for part in &s.parts {
+        if let StringPart::Interpolation(value) = part {
+            v.visit_value(value);
+        }
+    }
+}
+
+/// Visit each selector of `selectors`.
+pub fn walk_selectors<V: Visit + ?Sized>(v: &mut V, selectors: &Selectors) {
+    for selector in &selectors.s {
+        v.visit_selector(selector);
+    }
+}
+
+/// Visit the position, strings and argument selectors of `selector`.
+pub fn walk_selector<V: Visit + ?Sized>(v: &mut V, selector: &Selector) {
+    if let Some(pos) = &selector.pos {
+        v.visit_pos(pos);
+    }
+    for part in &selector.parts {
+        match part {
+            SelectorPart::Simple(s) => v.visit_sass_string(s),
+            SelectorPart::Attribute { name, val, .. } => {
+                v.visit_sass_string(name);
+                v.visit_sass_string(val);
+            }
+            SelectorPart::PseudoElement { name, arg }
+            | SelectorPart::Pseudo { name, arg } => {
+                v.visit_sass_string(name);
+                if let Some(arg) = arg {
+                    v.visit_selectors(arg);
+                }
+            }
+            SelectorPart::Descendant
+            | SelectorPart::RelOp(_)
+            | SelectorPart::BackRef => (),
+        }
+    }
+}
+
+/// Visit the positional and then the named values of `args`.
+pub fn walk_call_args<V: Visit + ?Sized>(v: &mut V, args: &CallArgs) {
+    for value in &args.positional {
+        v.visit_value(value);
+    }
+    for (_, value) in args.named.iter() {
+        v.visit_value(value);
+    }
+}
+
+/// Visit the declaration, default argument values and body of `callable`.
+pub fn walk_callable<V: Visit + ?Sized>(v: &mut V, callable: &Callable) {
+    v.visit_pos(&callable.decl);
+    for (_, default) in &callable.args.0 {
+        if let Some(default) = default {
+            v.visit_value(default);
+        }
+    }
+    v.visit_items(&callable.body);
+}
+
+/// A visitor that can modify a sass syntax tree in place.
+///
+/// This is like [`Visit`], but with mutable references.
+/// To replace a node, assign to it in the corresponding method.
+/// To remove an item from a body, override
+/// [`visit_items_mut`][Self::visit_items_mut].
+///
+/// See also the [module documentation](self).
+pub trait VisitMut {
+    /// Visit a sequence of items, e.g. a file or the body of a rule.
+    fn visit_items_mut(&mut self, items: &mut Vec<Item>) {
+        walk_items_mut(self, items)
+    }
+    /// Visit an item.
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        walk_item_mut(self, item)
+    }
+    /// Visit a value.
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        walk_value_mut(self, value)
+    }
+    /// Visit a string that may contain interpolations.
+    fn visit_sass_string_mut(&mut self, s: &mut SassString) {
+        walk_sass_string_mut(self, s)
+    }
+    /// Visit the selectors of a rule (or `@at-root` directive).
+    fn visit_selectors_mut(&mut self, selectors: &mut Selectors) {
+        walk_selectors_mut(self, selectors)
+    }
+    /// Visit a single selector.
+    fn visit_selector_mut(&mut self, selector: &mut Selector) {
+        walk_selector_mut(self, selector)
+    }
+    /// Visit the arguments of a function or mixin call.
+    fn visit_call_args_mut(&mut self, args: &mut CallArgs) {
+        walk_call_args_mut(self, args)
+    }
+    /// Visit a mixin or function declaration.
+    fn visit_callable_mut(&mut self, callable: &mut Callable) {
+        walk_callable_mut(self, callable)
+    }
+    /// Visit the source position of a node.
+    fn visit_pos_mut(&mut self, _pos: &mut SourcePos) {}
+}
+
+/// Visit each item in `items`.
+pub fn walk_items_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    items: &mut Vec<Item>,
+) {
+    for item in items {
+        v.visit_item_mut(item);
+    }
+}
+
+/// Visit the position, values, strings, selectors and bodies of `item`.
+pub fn walk_item_mut<V: VisitMut + ?Sized>(v: &mut V, item: &mut Item) {
+    match item {
+        Item::Import(names, args, pos) => {
+            v.visit_pos_mut(pos);
+            for name in names {
+                v.visit_sass_string_mut(name);
+            }
+            v.visit_value_mut(args);
+        }
+        Item::VariableDeclaration { val, pos, .. } => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(val);
+        }
+        Item::AtRoot(selectors, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_selectors_mut(selectors);
+            v.visit_items_mut(body);
+        }
+        Item::AtRule {
+            name,
+            args,
+            body,
+            pos,
+        } => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(name);
+            v.visit_value_mut(args);
+            if let Some(body) = body {
+                v.visit_items_mut(body);
+            }
+        }
+        Item::Debug(value, pos)
+        | Item::Warn(value, pos)
+        | Item::Error(value, pos)
+        | Item::Return(value, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(value);
+        }
+        Item::MixinDeclaration(_, callable)
+        | Item::FunctionDeclaration(_, callable) => {
+            v.visit_callable_mut(callable)
+        }
+        Item::MixinCall(_, args, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_call_args_mut(args);
+            if let Some(body) = body {
+                v.visit_callable_mut(body);
+            }
+        }
+        Item::Content(args, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_call_args_mut(args);
+        }
+        Item::IfStatement(cond, body, else_body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(cond);
+            v.visit_items_mut(body);
+            v.visit_items_mut(else_body);
+        }
+        Item::Each(_, values, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(values);
+            v.visit_items_mut(body);
+        }
+        Item::For {
+            from,
+            to,
+            body,
+            pos,
+            ..
+        } => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(from);
+            v.visit_value_mut(to);
+            v.visit_items_mut(body);
+        }
+        Item::While(cond, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_value_mut(cond);
+            v.visit_items_mut(body);
+        }
+        Item::Use(url, _, config, pos)
+        | Item::Forward(url, _, _, config, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(url);
+            for (_, value, _, pos) in config {
+                v.visit_pos_mut(pos);
+                v.visit_value_mut(value);
+            }
+        }
+        Item::Rule(selectors, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_selectors_mut(selectors);
+            v.visit_items_mut(body);
+        }
+        Item::NamespaceRule(name, value, body, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(name);
+            v.visit_value_mut(value);
+            v.visit_items_mut(body);
+        }
+        Item::Property(name, value, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(name);
+            v.visit_value_mut(value);
+        }
+        Item::CustomProperty(name, value, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(name);
+            v.visit_sass_string_mut(value);
+        }
+        Item::Comment(text, pos) => {
+            v.visit_pos_mut(pos);
+            v.visit_sass_string_mut(text);
+        }
+        Item::SilentComment(_, pos) => v.visit_pos_mut(pos),
+        Item::None => (),
+    }
+}
+
+/// Visit the position, component values and strings of `value`.
+pub fn walk_value_mut<V: VisitMut + ?Sized>(v: &mut V, value: &mut Value) {
+    v.visit_pos_mut(value.pos_mut());
+    match value {
+        Value::Call(name, args, _) => {
+            v.visit_sass_string_mut(name);
+            v.visit_call_args_mut(args);
+        }
+        Value::Literal(s, _) => v.visit_sass_string_mut(s),
+        Value::List(items, ..) => {
+            for item in items {
+                v.visit_value_mut(item);
+            }
+        }
+        Value::Paren(value, ..) | Value::UnaryOp(_, value, _) => {
+            v.visit_value_mut(value)
+        }
+        Value::BinOp(a, _, _, _, b, _) => {
+            v.visit_value_mut(a);
+            v.visit_value_mut(b);
+        }
+        Value::Map(items, _) => {
+            for (key, value) in items {
+                v.visit_value_mut(key);
+                v.visit_value_mut(value);
+            }
+        }
+        Value::Bang(..)
+        | Value::Numeric(..)
+        | Value::Variable(..)
+        | Value::Color(..)
+        | Value::Null(_)
+        | Value::True(_)
+        | Value::False(_)
+        | Value::HereSelector(_)
+        | Value::UnicodeRange(..) => (),
+    }
+}
+
+/// Visit the interpolated values of `s`.
+pub fn walk_sass_string_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    s: &mut SassString,
+) {
+    for part in &mut s.parts {
+        if let StringPart::Interpolation(value) = part {
+            v.visit_value_mut(value);
+        }
+    }
+}
+
+/// Visit each selector of `selectors`.
+pub fn walk_selectors_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    selectors: &mut Selectors,
+) {
+    for selector in &mut selectors.s {
+        v.visit_selector_mut(selector);
+    }
+}
+
+/// Visit the position, strings and argument selectors of `selector`.
+pub fn walk_selector_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    selector: &mut Selector,
+) {
+    if let Some(pos) = &mut selector.pos {
+        v.visit_pos_mut(pos);
+    }
+    for part in &mut selector.parts {
+        match part {
+            SelectorPart::Simple(s) => v.visit_sass_string_mut(s),
+            SelectorPart::Attribute { name, val, .. } => {
+                v.visit_sass_string_mut(name);
+                v.visit_sass_string_mut(val);
+            }
+            SelectorPart::PseudoElement { name, arg }
+            | SelectorPart::Pseudo { name, arg } => {
+                v.visit_sass_string_mut(name);
+                if let Some(arg) = arg {
+                    v.visit_selectors_mut(arg);
+                }
+            }
+            SelectorPart::Descendant
+            | SelectorPart::RelOp(_)
+            | SelectorPart::BackRef => (),
+        }
+    }
+}
+
+/// Visit the positional and then the named values of `args`.
+pub fn walk_call_args_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    args: &mut CallArgs,
+) {
+    for value in &mut args.positional {
+        v.visit_value_mut(value);
+    }
+    for (_, value) in args.named.iter_mut() {
+        v.visit_value_mut(value);
+    }
+}
+
+/// Visit the declaration, default argument values and body of `callable`.
+pub fn walk_callable_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    callable: &mut Callable,
+) {
+    v.visit_pos_mut(&mut callable.decl);
+    for (_, default) in &mut callable.args.0 {
+        if let Some(default) = default {
+            v.visit_value_mut(default);
+        }
+    }
+    v.visit_items_mut(&mut callable.body);
+}
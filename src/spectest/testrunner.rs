@@ -90,10 +90,7 @@ impl TestRunner {
         }
     }
     pub fn set_precision(mut self, precision: usize) -> Self {
-        self.format = Format {
-            precision,
-            ..self.format
-        };
+        self.format = self.format.with_precision(precision);
         self
     }
     pub fn mock_file(mut self, name: &str, content: &str) -> Self {
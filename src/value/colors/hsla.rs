@@ -85,7 +85,25 @@ impl<'a> Display for Formatted<'a, Hsla> {
         // The byte-version of alpha is not used here.
         let hsla = self.value;
         let a = hsla.alpha;
-        if a >= one() {
+        if self.format.colors.modern_syntax {
+            write!(
+                out,
+                "hsl({}deg {}% {}%",
+                hsla.hue,
+                hsla.sat * 100,
+                hsla.lum * 100
+            )?;
+            if a < one() {
+                let a = Number::from(a);
+                let slash = if self.format.is_compressed() {
+                    "/"
+                } else {
+                    " / "
+                };
+                write!(out, "{}{}", slash, a.format(self.format))?;
+            }
+            out.write_str(")")
+        } else if a >= one() {
             write!(
                 out,
                 "hsl({}deg, {}%, {}%)",
@@ -158,7 +158,9 @@ impl<'a> Display for Formatted<'a, Color> {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
         match self.value {
             Color::Rgba(rgba) => rgba.format(self.format).fmt(out),
-            Color::Hsla(hsla) if hsla.hsla_format => {
+            Color::Hsla(hsla)
+                if hsla.hsla_format && self.format.colors.keep_source() =>
+            {
                 hsla.format(self.format).fmt(out)
             }
             any => any.to_rgba().format(self.format).fmt(out),
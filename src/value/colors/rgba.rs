@@ -1,7 +1,7 @@
 //! Color names from <https://www.w3.org/TR/css3-color/>
 #![allow(clippy::unreadable_literal)]
 use super::Rational;
-use crate::output::{Format, Formatted};
+use crate::output::{ColorPolicy, Format, Formatted};
 use crate::value::Number;
 use lazy_static::lazy_static;
 use num_traits::{one, zero, One, Signed, Zero};
@@ -478,15 +478,35 @@ impl<'a> Display for Formatted<'a, Rgba> {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
         // The byte-version of alpha is not used here.
         let rgba = self.value;
-        let (r, g, b, _a) = rgba.to_bytes();
-        let a = self.value.alpha;
-        if a >= Rational::one() {
-            // E.g. #ff00cc can be written #f0c in css.
-            // 0xff / 0x11 = 0xf.
-            let short = r % 0x11 == 0 && g % 0x11 == 0 && b % 0x11 == 0;
-            let hex_len = if short { 4 } else { 7 };
-            if self.format.is_compressed() {
-                if let Some(name) = rgba.name() {
+        let (r, g, b, a) = rgba.to_bytes();
+        let colors = self.format.colors;
+        let opaque = rgba.alpha >= Rational::one();
+        let name = rgba.name().filter(|_| colors.names);
+        match colors.policy_for(self.format.is_compressed()) {
+            ColorPolicy::Hex if opaque => write_long_hex(out, r, g, b),
+            ColorPolicy::Hex => {
+                write!(out, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+            }
+            ColorPolicy::RgbFunction => self.write_function(out),
+            ColorPolicy::Preserve if opaque => match rgba.source {
+                RgbFormat::LongHex => write_long_hex(out, r, g, b),
+                RgbFormat::ShortHex => {
+                    write!(out, "#{:x}{:x}{:x}", r / 0x11, g / 0x11, b / 0x11)
+                }
+                RgbFormat::Name => {
+                    if let Some(name) = name {
+                        return name.fmt(out);
+                    }
+                    write_long_hex(out, r, g, b)
+                }
+                RgbFormat::Rgb => self.write_function(out),
+            },
+            _ if opaque => {
+                // E.g. #ff00cc can be written #f0c in css.
+                // 0xff / 0x11 = 0xf.
+                let short = r % 0x11 == 0 && g % 0x11 == 0 && b % 0x11 == 0;
+                let hex_len = if short { 4 } else { 7 };
+                if let Some(name) = name {
                     if name.len() <= hex_len {
                         return name.fmt(out);
                     }
@@ -494,36 +514,39 @@ impl<'a> Display for Formatted<'a, Rgba> {
                 if short {
                     write!(out, "#{:x}{:x}{:x}", r / 0x11, g / 0x11, b / 0x11)
                 } else {
-                    write!(out, "#{:02x}{:02x}{:02x}", r, g, b)
+                    write_long_hex(out, r, g, b)
                 }
+            }
+            ColorPolicy::Shortest if colors.names && rgba.all_zero() => {
+                write!(out, "transparent")
+            }
+            _ => self.write_function(out),
+        }
+    }
+}
+
+impl<'a> Formatted<'a, Rgba> {
+    /// Write this color as a `rgb()` or `rgba()` function.
+    fn write_function(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        let rgba = self.value;
+        let (r, g, b, _a) = rgba.to_bytes();
+        let a = rgba.alpha;
+        let compressed = self.format.is_compressed();
+        if self.format.colors.modern_syntax {
+            write!(out, "rgb({} {} {}", r, g, b)?;
+            if a < Rational::one() {
+                let a = Number::from(a);
+                let slash = if compressed { "/" } else { " / " };
+                write!(out, "{}{}", slash, a.format(self.format))?;
+            }
+            out.write_str(")")
+        } else if a >= Rational::one() {
+            if compressed {
+                write!(out, "rgb({},{},{})", r, g, b)
             } else {
-                match rgba.source {
-                    RgbFormat::LongHex => {
-                        write!(out, "#{:02x}{:02x}{:02x}", r, g, b)
-                    }
-                    RgbFormat::ShortHex => {
-                        write!(
-                            out,
-                            "#{:x}{:x}{:x}",
-                            r / 0x11,
-                            g / 0x11,
-                            b / 0x11
-                        )
-                    }
-                    RgbFormat::Name => {
-                        if let Some(name) = rgba.name() {
-                            return name.fmt(out);
-                        }
-                        write!(out, "#{:02x}{:02x}{:02x}", r, g, b)
-                    }
-                    RgbFormat::Rgb => {
-                        write!(out, "rgb({}, {}, {})", r, g, b)
-                    }
-                }
+                write!(out, "rgb({}, {}, {})", r, g, b)
             }
-        } else if self.format.is_compressed() && rgba.all_zero() {
-            write!(out, "transparent")
-        } else if self.format.is_compressed() {
+        } else if compressed {
             // Note: libsass does not use the format for the alpha like this.
             let a = Number::from(a);
             write!(out, "rgba({},{},{},{})", r, g, b, a.format(self.format))
@@ -540,3 +563,12 @@ impl<'a> Display for Formatted<'a, Rgba> {
         }
     }
 }
+
+fn write_long_hex(
+    out: &mut fmt::Formatter,
+    r: u8,
+    g: u8,
+    b: u8,
+) -> fmt::Result {
+    write!(out, "#{:02x}{:02x}{:02x}", r, g, b)
+}
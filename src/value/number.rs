@@ -1,4 +1,4 @@
-use crate::output::{Format, Formatted};
+use crate::output::{Format, Formatted, Rounding};
 use num_bigint::BigInt;
 use num_integer::Integer;
 use num_rational::Ratio;
@@ -652,6 +652,9 @@ impl Zero for Number {
 
 impl<'a> fmt::Display for Formatted<'a, Number> {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(result) = self.write_scientific(out) {
+            return result;
+        }
         match self.value.value {
             NumValue::Rational(ref s) => do_fmt(out, s, self.format),
             NumValue::BigRational(ref s) => do_fmt(out, s, self.format),
@@ -683,7 +686,10 @@ impl<'a> fmt::Display for Formatted<'a, Number> {
                             }
                         }
                         if !frac.is_zero() {
-                            let end = (frac * 10.).round().abs() as u8;
+                            let end = round_float(
+                                (frac * 10.).abs(),
+                                self.format.numbers.rounding,
+                            ) as u8;
                             if end == 10 {
                                 loop {
                                     match dec.pop() {
@@ -721,8 +727,11 @@ impl<'a> fmt::Display for Formatted<'a, Number> {
                         out.write_char('-')?;
                     }
 
-                    let skip_zero = self.format.is_compressed();
-                    if !(whole.is_zero() && skip_zero && !dec.is_empty()) {
+                    let zero = self
+                        .format
+                        .numbers
+                        .leading_zero_for(self.format.is_compressed());
+                    if !(whole.is_zero() && !zero && !dec.is_empty()) {
                         write!(out, "{}", whole)?;
                     }
 
@@ -736,6 +745,116 @@ impl<'a> fmt::Display for Formatted<'a, Number> {
     }
 }
 
+impl<'a> Formatted<'a, Number> {
+    /// Write this number in scientific notation, if the format says so.
+    ///
+    /// Returns None if the number should be written normally.
+    fn write_scientific(
+        &self,
+        out: &mut fmt::Formatter,
+    ) -> Option<fmt::Result> {
+        let numbers = self.format.numbers;
+        if numbers.sci_below.is_none() && numbers.sci_from.is_none() {
+            return None;
+        }
+        let value = match &self.value.value {
+            NumValue::Rational(r) => Ratio::new_raw(
+                BigInt::from(*r.numer()),
+                BigInt::from(*r.denom()),
+            ),
+            NumValue::BigRational(r) => r.clone(),
+            NumValue::Float(f) => float_to_ratio(*f)?,
+        };
+        if value.is_zero() {
+            return None;
+        }
+        let ten = Ratio::from_integer(BigInt::from(10));
+        let mut mantissa = value.abs();
+        let mut exp = 0;
+        while mantissa >= ten {
+            mantissa /= &ten;
+            exp += 1;
+        }
+        while mantissa < Ratio::one() {
+            mantissa *= &ten;
+            exp -= 1;
+        }
+        if !(numbers.sci_below.map_or(false, |below| exp < below)
+            || numbers.sci_from.map_or(false, |from| exp >= from))
+        {
+            return None;
+        }
+        // Round before writing, since rounding may give a mantissa of 10.
+        let scale = ten.pow(self.format.precision as i32);
+        let mut mantissa = Ratio::from_integer(round_ratio(
+            mantissa * &scale,
+            numbers.rounding,
+        )) / scale;
+        if mantissa >= ten {
+            mantissa /= ten;
+            exp += 1;
+        }
+        if value.is_negative() {
+            mantissa = -mantissa;
+        }
+        Some(
+            do_fmt(out, &mantissa, self.format)
+                .and_then(|()| write!(out, "e{}", exp)),
+        )
+    }
+}
+
+/// Get the exact value of a finite float as a ratio.
+fn float_to_ratio(value: f64) -> Option<Ratio<BigInt>> {
+    if !value.is_finite() {
+        return None;
+    }
+    let (mantissa, exp, sign) = num_traits::Float::integer_decode(value);
+    let mantissa = BigInt::from(sign) * BigInt::from(mantissa);
+    Some(if exp >= 0 {
+        Ratio::from_integer(mantissa << exp as usize)
+    } else {
+        Ratio::new(mantissa, BigInt::one() << (-exp) as usize)
+    })
+}
+
+/// Round a non-negative ratio to an integer.
+fn round_ratio<N>(value: Ratio<N>, rounding: Rounding) -> N
+where
+    N: Clone + Integer,
+{
+    match rounding {
+        Rounding::HalfUp => value.round().to_integer(),
+        Rounding::HalfEven => {
+            let whole = value.trunc();
+            let rest = value - &whole;
+            let whole = whole.to_integer();
+            let half = Ratio::new(N::one(), N::one() + N::one());
+            if rest > half || (rest == half && whole.is_odd()) {
+                whole + N::one()
+            } else {
+                whole
+            }
+        }
+    }
+}
+
+/// Round a non-negative float to an integer.
+fn round_float(value: f64, rounding: Rounding) -> f64 {
+    match rounding {
+        Rounding::HalfUp => value.round(),
+        Rounding::HalfEven => {
+            let whole = value.trunc();
+            let rest = value - whole;
+            if rest > 0.5 || (rest == 0.5 && whole % 2. != 0.) {
+                whole + 1.
+            } else {
+                whole
+            }
+        }
+    }
+}
+
 fn do_fmt<N>(
     out: &mut fmt::Formatter,
     value: &Ratio<N>,
@@ -764,7 +883,8 @@ where
             }
         }
         if !frac.is_zero() {
-            let end = (frac * &ten).round().abs().to_integer();
+            let end =
+                round_ratio((frac * &ten).abs(), format.numbers.rounding);
             if end == ten {
                 loop {
                     match dec.pop() {
@@ -800,8 +920,8 @@ where
         out.write_char('-')?;
     }
 
-    let skip_zero = format.is_compressed();
-    if !(whole.is_zero() && skip_zero && !dec.is_empty()) {
+    let zero = format.numbers.leading_zero_for(format.is_compressed());
+    if !(whole.is_zero() && !zero && !dec.is_empty()) {
         write!(out, "{}", whole)?;
     }
 
@@ -886,6 +1006,7 @@ fn debug_int_value() {
          \n        value: Number 17 / 1,\
          \n        unit: UnitSet [],\
          \n    },\
+         \n    {\"[17]\", (rsass):1 root stylesheet},\
          \n)",
     );
 }
@@ -2,7 +2,9 @@
 use crate::css::{CssString, Selectors, Value};
 use crate::error::Invalid;
 use crate::output::Format;
-use crate::sass::{Expose, Function, Item, MixinDecl, Name, UseAs};
+use crate::sass::{
+    Config, Content, Expose, Function, Item, MixinDecl, Name, UseAs,
+};
 use crate::{Error, SourcePos};
 use arc_swap::ArcSwapOption;
 use lazy_static::lazy_static;
@@ -34,6 +36,24 @@ impl ScopeRef {
     pub fn sub(parent: ScopeRef) -> Self {
         Self::dynamic(Scope::sub(parent))
     }
+    /// Create a new global scope for a module with a configuration.
+    pub(crate) fn new_module(format: Format, config: Config) -> Self {
+        Self::dynamic(Scope {
+            config: Some(config),
+            ..Scope::new_global(format)
+        })
+    }
+    /// Create a new subscope for a file imported by `@import`.
+    ///
+    /// An import at the top level of a configured module shares the
+    /// configuration of the module.
+    pub(crate) fn sub_import(parent: ScopeRef) -> Self {
+        let config = parent.config.clone();
+        Self::dynamic(Scope {
+            config,
+            ..Scope::sub(parent)
+        })
+    }
     /// Create a new subscope of a given parent with selectors.
     pub fn sub_selectors(parent: ScopeRef, selectors: Selectors) -> Self {
         Self::dynamic(Scope::sub_selectors(parent, selectors))
@@ -62,14 +82,14 @@ impl ScopeRef {
     {
         for b in body {
             let result = match *b {
-                Item::IfStatement(ref cond, ref do_if, ref do_else) => {
+                Item::IfStatement(ref cond, ref do_if, ref do_else, _) => {
                     if cond.evaluate(self.clone())?.is_true() {
                         self.clone().eval_body(do_if)?
                     } else {
                         self.clone().eval_body(do_else)?
                     }
                 }
-                Item::Each(ref names, ref values, ref body) => {
+                Item::Each(ref names, ref values, ref body, _) => {
                     let s = self.clone();
                     for value in values.evaluate(s.clone())?.iter_items() {
                         s.define_multi(names, value)?;
@@ -85,6 +105,7 @@ impl ScopeRef {
                     ref to,
                     inclusive,
                     ref body,
+                    pos: _,
                 } => {
                     let range = crate::value::ValueRange::new(
                         from.evaluate(self.clone())?,
@@ -115,7 +136,7 @@ impl ScopeRef {
                 Item::Return(ref v, _) => {
                     Some(v.do_evaluate(self.clone(), true)?)
                 }
-                Item::While(ref cond, ref body) => {
+                Item::While(ref cond, ref body, _) => {
                     let scope = ScopeRef::sub(self.clone());
                     while cond.evaluate(scope.clone())?.is_true() {
                         if let Some(r) = scope.clone().eval_body(body)? {
@@ -124,7 +145,7 @@ impl ScopeRef {
                     }
                     None
                 }
-                Item::Warn(ref value) => {
+                Item::Warn(ref value, _) => {
                     eprintln!(
                         "WARNING: {}",
                         value
@@ -143,7 +164,7 @@ impl ScopeRef {
                     .at(pos.clone()));
                 }
                 Item::None => None,
-                Item::Comment(..) => None,
+                Item::Comment(..) | Item::SilentComment(..) => None,
                 ref x => {
                     return Err(Error::S(format!(
                         "Not implemented in function: {:?}",
@@ -158,7 +179,11 @@ impl ScopeRef {
         Ok(None)
     }
 
-    fn with_forwarded(self) -> Self {
+    /// Get this module scope with the members it forwards.
+    ///
+    /// The result is a scope containing the same variables,
+    /// functions and mixins as a `@use` of this module would see.
+    pub fn with_forwarded(self) -> Self {
         if let Some(forwarded) = self.opt_forward() {
             let merged = ScopeRef::new_global(self.get_format());
             merged.expose_star(&forwarded);
@@ -222,7 +247,9 @@ pub struct Scope {
     forward: Mutex<Option<ScopeRef>>,
     format: Format,
     /// The thing to use for `@content` in a mixin.
-    content: ArcSwapOption<MixinDecl>,
+    content: ArcSwapOption<Content>,
+    /// The configuration, for the top-level scope of a module.
+    config: Option<Config>,
 }
 
 impl Scope {
@@ -242,6 +269,7 @@ impl Scope {
             forward: Default::default(),
             format,
             content: None.into(),
+            config: None,
         }
     }
     /// Create a scope for a built-in module.
@@ -270,6 +298,7 @@ impl Scope {
             forward: Default::default(),
             format,
             content: None.into(),
+            config: None,
         }
     }
     /// Create a new subscope of a given parent with selectors.
@@ -285,6 +314,7 @@ impl Scope {
             forward: Default::default(),
             format,
             content: None.into(),
+            config: None,
         }
     }
 
@@ -316,6 +346,21 @@ impl Scope {
         self.set_variable(name, val, false, false)
     }
 
+    /// Define a `!default` variable.
+    ///
+    /// If this is the scope of a module that is configured with a
+    /// value for the variable, that value is used instead of `val`.
+    pub fn define_default(
+        &self,
+        name: Name,
+        val: Value,
+    ) -> Result<(), ScopeError> {
+        match self.take_configured(&name) {
+            Some(val) => self.set_variable(name, val, false, false),
+            None => self.set_variable(name, val, true, false),
+        }
+    }
+
     /// Define a variable with a value.
     ///
     /// The `$` sign is not included in `name`.
@@ -552,13 +597,13 @@ impl Scope {
             UseAs::Prefix(prefix) => {
                 for (name, function) in &*module.functions.lock().unwrap() {
                     let name = format!("{}{}", prefix, name).into();
-                    if expose.allow_var(&name) {
+                    if expose.allow_fun(&name) {
                         self.define_function(name, function.clone());
                     }
                 }
                 for (name, value) in &*module.variables.lock().unwrap() {
                     let name = format!("{}{}", prefix, name).into();
-                    if expose.allow_fun(&name) {
+                    if expose.allow_var(&name) {
                         self.define(name, value.clone())?;
                     }
                 }
@@ -599,6 +644,10 @@ impl Scope {
         }
         Value::Map(result)
     }
+    /// Get the names of the mixins of this scope.
+    pub fn mixin_names(&self) -> Vec<Name> {
+        self.mixins.lock().unwrap().keys().cloned().collect()
+    }
     /// Get the variables of this scope as a `Value::Map`.
     pub fn variables_map(&self) -> Value {
         use crate::css::ValueMap;
@@ -614,6 +663,100 @@ impl Scope {
         }
         Value::Map(result)
     }
+    /// Define variables from a serializable struct or map.
+    ///
+    /// Each field of the struct (or entry of the map) is defined as a
+    /// variable, with the field value converted by
+    /// [`css::to_value`][crate::css::to_value], so a nested struct
+    /// is defined as a map.
+    ///
+    /// This is only available if rsass is built with the `serde`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rsass::input::MemoryContext;
+    /// # use std::collections::BTreeMap;
+    /// # fn main() -> Result<(), rsass::Error> {
+    /// let mut sizes = BTreeMap::new();
+    /// sizes.insert("small", "4px");
+    /// sizes.insert("large", "2em");
+    /// let mut tokens = BTreeMap::new();
+    /// tokens.insert("sizes", sizes);
+    /// let mut context = MemoryContext::new();
+    /// context.get_scope().define_serialized(&tokens)?;
+    /// context.insert("main.scss", "a { b: map-get($sizes, large) }");
+    /// let main = context.load_root("main")?;
+    /// assert_eq!(context.transform(main)?, b"a {\n  b: 2em;\n}\n");
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn define_serialized<T: serde::Serialize + ?Sized>(
+        &self,
+        values: &T,
+    ) -> Result<(), Error> {
+        match crate::css::to_value(values)? {
+            Value::Map(map) => {
+                for (name, value) in map {
+                    let name = match name {
+                        Value::Literal(s) => s.take_value(),
+                        name => name.format(Format::introspect()).to_string(),
+                    };
+                    self.define(name.into(), value)?;
+                }
+                Ok(())
+            }
+            v => Err(Error::S(format!(
+                "Expected a struct or map of variables, got {}.",
+                v.type_name()
+            ))),
+        }
+    }
+    /// Get the variables of this scope as a deserializable struct.
+    ///
+    /// This is the reverse of
+    /// [`define_serialized`][Self::define_serialized].
+    /// As a `-` and a `_` is considered equal in sass variable names,
+    /// the names are given with `_`, to match the fields of a rust
+    /// struct.
+    /// Each value is converted by
+    /// [`css::from_value`][crate::css::from_value].
+    ///
+    /// This is only available if rsass is built with the `serde`
+    /// feature.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_variables<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<T, Error> {
+        use crate::css::ValueMap;
+        let map = self
+            .variables
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| *name != &Name::from_static("@scope_name@"))
+            .map(|(name, value)| (name.as_ref().into(), value.clone()))
+            .collect::<ValueMap>();
+        crate::css::from_value(Value::Map(map))
+    }
+    /// Get the configuration of the module this is the top-level
+    /// scope of.
+    ///
+    /// Other scopes have an empty configuration.
+    pub(crate) fn config(&self) -> Config {
+        self.config.clone().unwrap_or_default()
+    }
+    /// Take the configured value for a `!default` variable declaration.
+    ///
+    /// A configured null value is taken but not used.
+    pub(crate) fn take_configured(&self, name: &Name) -> Option<Value> {
+        if name.split_module().is_some() {
+            return None;
+        }
+        let (value, _pos) = self.config.as_ref()?.take(name)?;
+        Some(value).filter(|v| !v.is_null())
+    }
     /// Get the forward scope for this scope.
     ///
     /// Create a new one if necessary.
@@ -629,10 +772,10 @@ impl Scope {
         self.forward.lock().unwrap().clone()
     }
 
-    pub(crate) fn define_content(&self, body: MixinDecl) {
+    pub(crate) fn define_content(&self, body: Content) {
         self.content.store(Some(Arc::new(body)));
     }
-    pub(crate) fn get_content(&self) -> Option<MixinDecl> {
+    pub(crate) fn get_content(&self) -> Option<Content> {
         self.content
             .load_full()
             .map(|c| (*c).clone())
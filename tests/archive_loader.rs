@@ -0,0 +1,113 @@
+//! Tests for loading stylesheets from archives.
+//!
+//! The archives are created by the tests, in the cargo temporary
+//! directory for tests.
+#![cfg(feature = "archive")]
+use rsass::input::{ArchiveLoader, Context, Loader};
+use rsass::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+static THEME: &[(&str, &str)] = &[
+    (
+        "theme.scss",
+        "@use 'colors';\n@import 'reset';\na { b: colors.$c }",
+    ),
+    ("colors/_index.scss", "@forward 'palette';"),
+    ("colors/_palette.scss", "$c: red;"),
+    ("reset.css", "b { margin: 0 }"),
+    ("README.md", "Not a stylesheet."),
+];
+
+#[test]
+fn zip_archive() -> Result<(), Error> {
+    let loader = ArchiveLoader::for_archive(&write_zip("zip_theme.zip"))?;
+    check(loader, "theme")
+}
+
+#[test]
+fn tar_archive() -> Result<(), Error> {
+    let loader = ArchiveLoader::for_archive(&write_tar("tar_theme.tar"))?;
+    check(loader, "theme")
+}
+
+#[test]
+fn only_stylesheets() -> Result<(), Error> {
+    for path in [write_zip("only_css.zip"), write_tar("only_css.tar")] {
+        let loader = ArchiveLoader::for_archive(&path)?;
+        assert!(loader.find_file("reset.css")?.is_some());
+        assert!(loader.find_file("README.md")?.is_none());
+    }
+    Ok(())
+}
+
+#[test]
+fn mixed_with_directory() -> Result<(), Error> {
+    let dir = tmp("mixed");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.scss"), "@import 'theme';\nc { d: e }")
+        .unwrap();
+    std::fs::write(dir.join("_palette.scss"), "$c: blue;").unwrap();
+    let mut loader = ArchiveLoader::new();
+    loader.push_path(&dir)?;
+    loader.push_path(&write_zip("mixed_theme.zip"))?;
+    let mut context = Context::for_loader(loader);
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "b {\n  margin: 0;\n}\na {\n  b: red;\n}\n\nc {\n  d: e;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_archive_format() {
+    let mut loader = ArchiveLoader::new();
+    assert!(loader.push_archive("theme.rar".as_ref()).is_err());
+}
+
+#[test]
+fn missing_archive() {
+    assert!(ArchiveLoader::for_archive(&tmp("nonesuch.zip")).is_err());
+}
+
+fn check(loader: ArchiveLoader, name: &str) -> Result<(), Error> {
+    let mut context = Context::for_loader(loader);
+    let main = context.load_root(name)?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "b {\n  margin: 0;\n}\na {\n  b: red;\n}\n"
+    );
+    Ok(())
+}
+
+fn write_zip(name: &str) -> PathBuf {
+    let path = tmp(name);
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let options = zip::write::FileOptions::default();
+    for (name, data) in THEME {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(data.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+    path
+}
+
+fn write_tar(name: &str) -> PathBuf {
+    let path = tmp(name);
+    let mut tar = tar::Builder::new(std::fs::File::create(&path).unwrap());
+    for (name, data) in THEME {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, format!("./{}", name), data.as_bytes())
+            .unwrap();
+    }
+    tar.finish().unwrap();
+    path
+}
+
+fn tmp(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
@@ -0,0 +1,79 @@
+//! Tests that modules are identified by canonical url, independently
+//! of how the url is spelled.
+use rsass::input::{FsContext, MemoryContext};
+use rsass::Error;
+use std::path::Path;
+
+#[test]
+fn dot_segments_use_same_module() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context
+        .insert("main.scss", "@use 'lib/a';\n@use 'b';\nx { y: a.$v b.$v }");
+    context.insert("lib/_a.scss", "@use './../b';\n$v: b.$v;");
+    context.insert("_b.scss", "b { loaded: once }\n$v: 1;");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "b {\n  loaded: once;\n}\n\nx {\n  y: 1 1;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn dot_segments_in_root() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "a { b: c }");
+    let main = context.load_root("lib/./../main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: c;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn loop_through_dot_segments() {
+    let mut context = MemoryContext::new();
+    context.insert("a.scss", "@use 'sub/../b';");
+    context.insert("b.scss", "@use 'a';");
+    let main = context.load_root("a").unwrap();
+    assert_eq!(
+        context.transform(main).unwrap_err().to_string(),
+        "Error: Module loop: this module is already being loaded.\
+         \n  ,\
+         \n1 | @use 'a';\
+         \n  | ^^^^^^^^\
+         \n  '\
+         \n  b.scss 1:1  @use\
+         \n  a.scss 1:1  root stylesheet",
+    );
+}
+
+#[test]
+fn same_file_through_search_paths() -> Result<(), Error> {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("canonical");
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    std::fs::write(dir.join("lib/_x.scss"), "x { loaded: once }").unwrap();
+    std::fs::write(dir.join("main.scss"), "@use 'lib/x';\n@use 'x';")
+        .unwrap();
+    let (mut context, main) = FsContext::for_path(&dir.join("main.scss"))?;
+    context.push_path(&dir.join("lib"));
+    assert_eq!(context.transform(main)?, b"x {\n  loaded: once;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn loop_from_path() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("canonical_loop");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.scss"), "@use 'b';").unwrap();
+    std::fs::write(dir.join("_b.scss"), "@use 'a';").unwrap();
+    let (context, main) = FsContext::for_path(&dir.join("a.scss")).unwrap();
+    assert_eq!(
+        context.transform(main).unwrap_err().to_string(),
+        "Error: Module loop: this module is already being loaded.\
+         \n  ,\
+         \n1 | @use 'a';\
+         \n  | ^^^^^^^^\
+         \n  '\
+         \n  _b.scss 1:1  @use\
+         \n  a.scss 1:1   root stylesheet",
+    );
+}
@@ -0,0 +1,127 @@
+//! Tests for the color policy of the output format.
+use rsass::compile_scss;
+use rsass::output::{ColorFormat, ColorPolicy, Format, Style};
+
+const INPUT: &str = "a {
+  name: red;
+  short: #ABC;
+  long: #00ff00;
+  fn: rgb(1, 2, 3);
+  alpha: rgba(1, 2, 3, 0.5);
+  clear: transparent;
+  hsl: hsl(120, 50%, 50%);
+  calc: lighten(#000, 20%);
+}";
+
+#[test]
+fn auto_expanded() {
+    assert_eq!(
+        compile(ColorFormat::default(), Style::Expanded),
+        "a {\n  name: red;\n  short: #ABC;\n  long: #00ff00;\
+         \n  fn: rgb(1, 2, 3);\n  alpha: rgba(1, 2, 3, 0.5);\
+         \n  clear: transparent;\n  hsl: hsl(120deg, 50%, 50%);\
+         \n  calc: #333333;\n}\n"
+    );
+}
+
+#[test]
+fn auto_compressed() {
+    assert_eq!(
+        compile(ColorFormat::default(), Style::Compressed),
+        "a{name:red;short:#ABC;long:#00ff00;fn:#010203;\
+         alpha:rgba(1,2,3,.5);clear:transparent;\
+         hsl:hsl(120deg, 50%, 50%);calc:#333}\n"
+    );
+}
+
+#[test]
+fn preserve_compressed() {
+    assert_eq!(
+        compile(ColorFormat::new(ColorPolicy::Preserve), Style::Compressed),
+        "a{name:red;short:#ABC;long:#00ff00;fn:rgb(1,2,3);\
+         alpha:rgba(1,2,3,.5);clear:transparent;\
+         hsl:hsl(120deg, 50%, 50%);calc:#333333}\n"
+    );
+}
+
+#[test]
+fn shortest_expanded() {
+    assert_eq!(
+        compile(ColorFormat::new(ColorPolicy::Shortest), Style::Expanded),
+        "a {\n  name: red;\n  short: #abc;\n  long: lime;\
+         \n  fn: #010203;\n  alpha: rgba(1, 2, 3, 0.5);\
+         \n  clear: transparent;\n  hsl: #40bf40;\n  calc: #333;\n}\n"
+    );
+}
+
+#[test]
+fn hex() {
+    assert_eq!(
+        compile(ColorFormat::new(ColorPolicy::Hex), Style::Expanded),
+        "a {\n  name: #ff0000;\n  short: #aabbcc;\n  long: #00ff00;\
+         \n  fn: #010203;\n  alpha: #01020380;\n  clear: #00000000;\
+         \n  hsl: #40bf40;\n  calc: #333333;\n}\n"
+    );
+}
+
+#[test]
+fn rgb_function() {
+    assert_eq!(
+        compile(ColorFormat::new(ColorPolicy::RgbFunction), Style::Expanded),
+        "a {\n  name: rgb(255, 0, 0);\n  short: rgb(170, 187, 204);\
+         \n  long: rgb(0, 255, 0);\n  fn: rgb(1, 2, 3);\
+         \n  alpha: rgba(1, 2, 3, 0.5);\n  clear: rgba(0, 0, 0, 0);\
+         \n  hsl: rgb(64, 191, 64);\n  calc: rgb(51, 51, 51);\n}\n"
+    );
+}
+
+#[test]
+fn rgb_function_modern() {
+    assert_eq!(
+        compile(
+            ColorFormat::new(ColorPolicy::RgbFunction).modern(),
+            Style::Expanded
+        ),
+        "a {\n  name: rgb(255 0 0);\n  short: rgb(170 187 204);\
+         \n  long: rgb(0 255 0);\n  fn: rgb(1 2 3);\
+         \n  alpha: rgb(1 2 3 / 0.5);\n  clear: rgb(0 0 0 / 0);\
+         \n  hsl: rgb(64 191 64);\n  calc: rgb(51 51 51);\n}\n"
+    );
+}
+
+#[test]
+fn modern_compressed() {
+    assert_eq!(
+        compile(ColorFormat::default().modern(), Style::Compressed),
+        "a{name:red;short:#ABC;long:#00ff00;fn:#010203;\
+         alpha:rgb(1 2 3/.5);clear:transparent;\
+         hsl:hsl(120deg 50% 50%);calc:#333}\n"
+    );
+}
+
+#[test]
+fn no_names() {
+    assert_eq!(
+        compile(ColorFormat::default().without_names(), Style::Compressed),
+        "a{name:#f00;short:#ABC;long:#00ff00;fn:#010203;\
+         alpha:rgba(1,2,3,.5);clear:rgba(0,0,0,0);\
+         hsl:hsl(120deg, 50%, 50%);calc:#333}\n"
+    );
+}
+
+#[test]
+fn no_names_preserve() {
+    assert_eq!(
+        compile(ColorFormat::default().without_names(), Style::Expanded),
+        "a {\n  name: #ff0000;\n  short: #ABC;\n  long: #00ff00;\
+         \n  fn: rgb(1, 2, 3);\n  alpha: rgba(1, 2, 3, 0.5);\
+         \n  clear: rgba(0, 0, 0, 0);\n  hsl: hsl(120deg, 50%, 50%);\
+         \n  calc: #333333;\n}\n"
+    );
+}
+
+fn compile(colors: ColorFormat, style: Style) -> String {
+    let format = Format::default().with_style(style).with_colors(colors);
+    String::from_utf8(compile_scss(INPUT.as_bytes(), format).unwrap())
+        .unwrap()
+}
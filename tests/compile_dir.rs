@@ -0,0 +1,76 @@
+//! Tests for compiling a directory of stylesheets.
+use rsass::input::CompileDir;
+use rsass::output::{Format, Style};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::Path;
+
+#[test]
+fn entry_points_and_module() {
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_dir");
+    let src = Path::new("tests/compile_dir");
+    let compiled = CompileDir::new()
+        .with_format(Format::default().with_style(Style::Compressed))
+        .write_to(src, &out)
+        .unwrap();
+    let names = compiled.iter().map(|f| &f.name[..]).collect::<Vec<_>>();
+    assert_eq!(names, ["admin/panel.css", "main.css"]);
+    assert_eq!(
+        read_to_string(out.join("admin/panel.css")).unwrap(),
+        ".widget{margin:0}.panel{border-color:#336699}\n"
+    );
+    assert_eq!(
+        read_to_string(out.join("main.css")).unwrap(),
+        "body{color:#336699}\n"
+    );
+    let deps = compiled[0]
+        .dependencies
+        .iter()
+        .map(|path| path.strip_prefix(src.canonicalize().unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        deps,
+        [
+            Path::new("admin/panel.scss"),
+            Path::new("_colors.scss"),
+            Path::new("admin/_widgets.scss"),
+        ]
+    );
+    let module = read_to_string(out.with_extension("rs")).unwrap();
+    assert!(module.contains(
+        "pub const ADMIN_PANEL_CSS: &str = \"admin/panel.css\";\n"
+    ));
+    assert!(module.contains(&format!(
+        "pub const MAIN_HASH: &str = \"{}\";\n",
+        compiled[1].hash
+    )));
+    assert_eq!(compiled[1].hash.len(), 16);
+}
+
+#[test]
+fn same_constant_names() {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let src = tmp.join("compile_same_src");
+    create_dir_all(src.join("admin")).unwrap();
+    write(src.join("admin/main.scss"), "a { b: c }").unwrap();
+    write(src.join("admin-main.scss"), "d { e: f }").unwrap();
+    let out = tmp.join("compile_same");
+    match CompileDir::new().write_to(&src, &out) {
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Error: Entry points \"admin-main.scss\" and \
+             \"admin/main.scss\" would get the same constant names"
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn missing_dir() {
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_none");
+    match CompileDir::new().write_to(Path::new("tests/nonesuch"), &out) {
+        Err(e) => assert!(e
+            .to_string()
+            .starts_with("Error: Reading \"tests/nonesuch\" failed: ")),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
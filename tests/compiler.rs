@@ -0,0 +1,176 @@
+//! Tests for the thread-safe compiler.
+use rsass::input::{
+    Compiler, FsLoader, Imported, Importer, LoadError, MemoryLoader,
+    RoutingLoader,
+};
+use rsass::output::{Format, Style};
+use rsass::sass::Name;
+use rsass::value::{Numeric, Unit};
+use std::path::Path;
+
+fn loader() -> MemoryLoader {
+    let mut loader = MemoryLoader::new();
+    loader.insert("_theme.scss", "$gap: 4px !default;\n$n: 0;");
+    for i in 0..12 {
+        loader.insert(
+            format!("page{}.scss", i),
+            format!(
+                "@use 'theme' with ($gap: {}px);\n\
+                 .p{} {{ margin: theme.$gap; n: theme.$n }}",
+                i, i
+            ),
+        );
+    }
+    loader.insert("broken.scss", "a { b: $undefined }");
+    loader
+}
+
+#[test]
+fn send_and_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<Compiler<MemoryLoader>>();
+    check::<Compiler<FsLoader>>();
+    check::<Compiler<RoutingLoader>>();
+}
+
+#[test]
+fn many_in_order() {
+    let compiler = Compiler::new(loader())
+        .with_format(Format::default().with_style(Style::Compressed))
+        .with_threads(3);
+    let mut urls = (0..12).map(|i| format!("page{}", i)).collect::<Vec<_>>();
+    urls.insert(5, "broken".into());
+    let result = compiler.compile_many(urls);
+    assert_eq!(result.len(), 13);
+    for (i, css) in result.iter().filter_map(|r| r.as_ref().ok()).enumerate()
+    {
+        assert_eq!(
+            String::from_utf8(css.clone()).unwrap(),
+            format!(".p{}{{margin:{}px;n:0}}\n", i, i)
+        );
+    }
+    match &result[5] {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some("Error: Undefined variable.")
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn shared_between_threads() {
+    let compiler = Compiler::new(loader());
+    let threads = (0..4)
+        .map(|i| {
+            let compiler = compiler.clone();
+            std::thread::spawn(move || {
+                compiler.compile(&format!("page{}", i)).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    for (i, thread) in threads.into_iter().enumerate() {
+        assert_eq!(
+            String::from_utf8(thread.join().unwrap()).unwrap(),
+            format!(".p{} {{\n  margin: {}px;\n  n: 0;\n}}\n", i, i)
+        );
+    }
+}
+
+#[test]
+fn parse_cache() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compiler_cache");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.scss"), "a { b: 1 }").unwrap();
+    let mut loader = FsLoader::for_cwd();
+    loader.push_path(&dir);
+    let compiler = Compiler::new(loader);
+    assert_eq!(compiler.compile("main").unwrap(), b"a {\n  b: 1;\n}\n");
+    std::fs::write(dir.join("main.scss"), "a { b: 2 }").unwrap();
+    assert_eq!(compiler.compile("main").unwrap(), b"a {\n  b: 1;\n}\n");
+    compiler.clear_cache();
+    assert_eq!(compiler.compile("main").unwrap(), b"a {\n  b: 2;\n}\n");
+}
+
+#[test]
+fn error_trace_of_cached_file() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("_colors.scss", "@error \"No colors\";");
+    loader.insert("a.scss", "@use 'colors';");
+    loader.insert("b.scss", "@use 'colors';");
+    let compiler = Compiler::new(loader);
+    for entry in &["a", "b"] {
+        match compiler.compile(entry) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                format!(
+                    "Error: \"No colors\"\
+                     \n  ,\
+                     \n1 | @error \"No colors\";\
+                     \n  | ^^^^^^^^^^^^^^^^^^\
+                     \n  \'\
+                     \n  _colors.scss 1:1  @use\
+                     \n  {}.scss 1:1        root stylesheet",
+                    entry
+                )
+            ),
+            Ok(_) => panic!("Expected an error"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Tokens;
+
+impl Importer for Tokens {
+    fn import(
+        &self,
+        url: &str,
+        _base: Option<&str>,
+    ) -> Result<Option<Imported>, LoadError> {
+        if url == "tokens" {
+            Ok(Some(Imported::scss("gen:tokens.scss", "$c: red;")))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[test]
+fn importers_modules_and_globals() {
+    let mut loader = MemoryLoader::new();
+    for i in 0..6 {
+        loader.insert(
+            format!("page{}.scss", i),
+            format!(
+                "@use 'tokens';\n@use 'app:sizes';\n\
+                 .p{} {{ color: tokens.$c; gap: sizes.$gap; n: $n }}",
+                i
+            ),
+        );
+    }
+    let mut compiler = Compiler::new(loader)
+        .with_format(Format::default().with_style(Style::Compressed))
+        .with_importer(Tokens)
+        .with_global_scope(|scope| {
+            scope.define(
+                Name::from_static("n"),
+                Numeric::new(1, Unit::None).into(),
+            )?;
+            Ok(())
+        });
+    compiler.define_module("app:sizes", |module| {
+        module.define(
+            Name::from_static("gap"),
+            Numeric::new(4, Unit::Px).into(),
+        )?;
+        Ok(())
+    });
+    let urls = (0..6).map(|i| format!("page{}", i)).collect::<Vec<_>>();
+    for (i, css) in compiler.compile_many(urls).into_iter().enumerate() {
+        assert_eq!(
+            String::from_utf8(css.unwrap()).unwrap(),
+            format!(".p{}{{color:red;gap:4px;n:1}}\n", i)
+        );
+    }
+}
@@ -299,10 +299,9 @@ fn t50_wrapped_pseudo_selectors() {
 }
 
 fn check(input: &[u8], expected: &str) {
-    let format = Format {
-        style: Style::Compressed,
-        precision: 5,
-    };
+    let format = Format::default()
+        .with_style(Style::Compressed)
+        .with_precision(5);
     assert_eq!(
         String::from_utf8(compile_scss(input, format).unwrap()).unwrap(),
         expected
@@ -0,0 +1,90 @@
+//! Tests for getting a css tree rather than serialized css.
+use rsass::css::{AtRuleBodyItem, BodyItem, Item};
+use rsass::input::{FsContext, SourceFile, SourceName};
+use rsass::output::{write_css, Format, Style};
+use rsass::Error;
+
+#[test]
+fn nested_rules_are_flattened() -> Result<(), Error> {
+    let items = ast("a { b: c; d { e: f } }")?;
+    match &items[..] {
+        [Item::Rule(a), Item::Rule(ad)] => {
+            assert_eq!(a.selectors().to_string(), "a");
+            assert_eq!(ad.selectors().to_string(), "a d");
+            match ad.body() {
+                [BodyItem::Property(p)] => {
+                    assert_eq!(p.name(), "e");
+                    assert_eq!(
+                        p.value().format(Format::default()).to_string(),
+                        "f"
+                    );
+                }
+                body => panic!("Unexpected body {:?}", body),
+            }
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+    Ok(())
+}
+
+#[test]
+fn at_rule_body() -> Result<(), Error> {
+    let items = ast("a { b: c; @media screen { d: e } }")?;
+    match &items[..] {
+        [Item::Rule(_), Item::AtRule(media)] => {
+            assert_eq!(media.name(), "media");
+            assert_eq!(
+                media.args().format(Format::default()).to_string(),
+                "screen"
+            );
+            match media.body() {
+                Some([AtRuleBodyItem::Rule(rule)]) => {
+                    assert_eq!(rule.selectors().to_string(), "a");
+                }
+                body => panic!("Unexpected body {:?}", body),
+            }
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+    Ok(())
+}
+
+#[test]
+fn imports_are_hoisted() -> Result<(), Error> {
+    let items = ast("a { b: c }\n@import \"foo.css\";\n/* hello */")?;
+    match &items[..] {
+        [Item::Import(import), Item::Rule(_), Item::Comment(c)] => {
+            assert_eq!(import.name().value(), "foo.css");
+            assert_eq!(c.text(), " hello ");
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+    Ok(())
+}
+
+#[test]
+fn serialize_in_any_format() -> Result<(), Error> {
+    let items = ast("a { b: c }\n@media print { a { d: 1.123456 } }")?;
+    assert_eq!(
+        String::from_utf8(write_css(&items, Format::default())).unwrap(),
+        "a {\n  b: c;\n}\n\n@media print {\n  a {\n    d: 1.123456;\n  }\n}\n",
+    );
+    assert_eq!(
+        String::from_utf8(write_css(
+            &items,
+            Format::default()
+                .with_style(Style::Compressed)
+                .with_precision(2)
+        ))
+        .unwrap(),
+        "a{b:c}@media print{a{d:1.12}}\n",
+    );
+    Ok(())
+}
+
+fn ast(input: &str) -> Result<Vec<Item>, Error> {
+    FsContext::for_cwd().transform_to_ast(SourceFile::scss_bytes(
+        input.as_bytes(),
+        SourceName::root("-"),
+    ))
+}
@@ -0,0 +1,87 @@
+//! Tests for custom built-in modules defined on a context.
+use rsass::input::MemoryContext;
+use rsass::sass::{Function, Name};
+use rsass::value::{Numeric, Unit};
+use rsass::Error;
+
+fn tokens_context() -> MemoryContext {
+    let mut context = MemoryContext::new();
+    context.define_module("app:tokens", |module| {
+        module.define_default(
+            Name::from_static("gap"),
+            Numeric::new(4, Unit::Px).into(),
+        )?;
+        module.define(Name::from_static("brand"), "red".into())?;
+        let name = Name::from_static("double");
+        module.define_function(
+            name.clone(),
+            Function::typed(&name, &["x"], |x: Numeric| {
+                Ok(Numeric::new(x.value * 2, x.unit))
+            }),
+        );
+        Ok(())
+    });
+    context
+}
+
+fn render(context: &mut MemoryContext, main: &str) -> Result<String, Error> {
+    context.insert("main.scss", main);
+    let main = context.load_root("main")?;
+    let css = std::mem::take(context).transform(main)?;
+    Ok(String::from_utf8(css).unwrap())
+}
+
+#[test]
+fn use_with_namespace() -> Result<(), Error> {
+    let mut context = tokens_context();
+    assert_eq!(
+        render(
+            &mut context,
+            "@use 'app:tokens';\na { b: tokens.double(tokens.$gap) tokens.$brand }",
+        )?,
+        "a {\n  b: 8px red;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn configured() -> Result<(), Error> {
+    let mut context = tokens_context();
+    assert_eq!(
+        render(
+            &mut context,
+            "@use 'app:tokens' as t with ($gap: 1em);\na { b: t.$gap }",
+        )?,
+        "a {\n  b: 1em;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn forwarded_and_configured() -> Result<(), Error> {
+    let mut context = tokens_context();
+    context.insert("_lib.scss", "@forward 'app:tokens' as tok-*;");
+    assert_eq!(
+        render(
+            &mut context,
+            "@use 'lib' with ($tok-gap: 2em);\na { b: lib.$tok-gap lib.tok-double(3) }",
+        )?,
+        "a {\n  b: 2em 6;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn not_configurable() {
+    let mut context = tokens_context();
+    match render(&mut context, "@use 'app:tokens' with ($brand: blue);") {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some(
+                "Error: This variable was not declared with !default \
+                 in the @used module."
+            ),
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
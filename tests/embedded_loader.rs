@@ -0,0 +1,68 @@
+//! Tests for stylesheets embedded in the program.
+use rsass::input::{Context, EmbeddedLoader};
+use rsass::Error;
+use std::path::Path;
+
+static FILES: &[(&str, &[u8])] = &[
+    ("_a.scss", b"$v: 1;"),
+    ("b/_index.scss", b"$v: 2;"),
+    ("lib/_colors.scss", b"$c: red;"),
+    ("lib/theme.scss", b"@import 'colors';\na { b: $c }"),
+    ("main.scss", b"@use 'a';\n@use 'b';\nx { y: a.$v b.$v }"),
+    ("reset.css", b"b { margin: 0 }"),
+];
+
+#[test]
+fn partial_and_index() -> Result<(), Error> {
+    check("main", "x {\n  y: 1 2;\n}\n")
+}
+
+#[test]
+fn relative_to_importing_file() -> Result<(), Error> {
+    check("lib/theme", "a {\n  b: red;\n}\n")
+}
+
+#[test]
+fn plain_css() -> Result<(), Error> {
+    check("reset", "b {\n  margin: 0;\n}\n")
+}
+
+#[test]
+fn not_found() {
+    let mut context = Context::for_loader(EmbeddedLoader::new(FILES));
+    assert!(context.load_root("nonesuch").is_err());
+}
+
+#[test]
+fn write_table() {
+    let dir = Path::new("tests/node/node_modules/bootstrap");
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("bootstrap.rs");
+    EmbeddedLoader::write_table(dir, &out).unwrap();
+    let table = std::fs::read_to_string(&out).unwrap();
+    let names = table
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("(\""))
+        .map(|line| &line[..line.find('"').unwrap()])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "scss/_functions.scss",
+            "scss/_grid.scss",
+            "scss/bootstrap.scss"
+        ],
+    );
+    assert!(table.starts_with("&[\n"));
+    assert!(table.contains("include_bytes!("));
+    assert!(!table.contains("package.json"));
+}
+
+fn check(name: &str, expected: &str) -> Result<(), Error> {
+    let mut context = Context::for_loader(EmbeddedLoader::new(FILES));
+    let main = context.load_root(name)?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        expected
+    );
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Tests for `@forward` with a prefix and `show` or `hide`.
+use rsass::input::MemoryContext;
+use rsass::Error;
+
+fn colors() -> MemoryContext {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "_colors.scss",
+        "$primary: red;\n$secret: blue;\n\
+         @function secret() { @return green; }",
+    );
+    context
+}
+
+#[test]
+fn hide_variable() {
+    let mut context = colors();
+    context.insert("_lib.scss", "@forward 'colors' as c-* hide $c-secret;");
+    context.insert("main.scss", "@use 'lib';\na { b: lib.$c-secret }");
+    let main = context.load_root("main").unwrap();
+    match context.transform(main) {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some("Error: Undefined variable.")
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn hide_function_keeps_variable() -> Result<(), Error> {
+    let mut context = colors();
+    context.insert("_lib.scss", "@forward 'colors' as c-* hide c-secret;");
+    context.insert("main.scss", "@use 'lib';\na { b: lib.$c-secret }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: blue;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn show_variable() -> Result<(), Error> {
+    let mut context = colors();
+    context.insert("_lib.scss", "@forward 'colors' as c-* show $c-primary;");
+    context.insert("main.scss", "@use 'lib';\na { b: lib.$c-primary }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+    Ok(())
+}
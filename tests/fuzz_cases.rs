@@ -1,19 +1,20 @@
 use rsass::{compile_scss, compile_value, output};
 
-const FORMAT: output::Format = output::Format {
-    style: output::Style::Compressed,
-    precision: 5,
-};
+fn format() -> output::Format {
+    output::Format::default()
+        .with_style(output::Style::Compressed)
+        .with_precision(5)
+}
 
 #[test]
 fn bad_escape() {
-    assert!(compile_scss(b"\\d00000", FORMAT).is_err());
+    assert!(compile_scss(b"\\d00000", format()).is_err());
 }
 
 #[test]
 fn decimal_integer_overflow() {
     assert_eq!(
-        compile_value(b"2000000000000000000000000000000000000", FORMAT)
+        compile_value(b"2000000000000000000000000000000000000", format())
             .unwrap(),
         b"2000000000000000000000000000000000000".to_vec(),
     );
@@ -22,7 +23,7 @@ fn decimal_integer_overflow() {
 #[test]
 fn decimal_fraction_overflow() {
     assert_eq!(
-        compile_value(b"0.2000000000000000000000000000000000000", FORMAT)
+        compile_value(b"0.2000000000000000000000000000000000000", format())
             .unwrap(),
         b".2",
     );
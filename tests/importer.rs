@@ -0,0 +1,110 @@
+//! Tests for importers that provide stylesheets dynamically.
+use rsass::input::{
+    Imported, Importer, LoadError, MemoryContext, SourceFormat,
+};
+use rsass::Error;
+use std::sync::{Arc, Mutex};
+
+/// The urls and previous urls an importer is called with.
+type Calls = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+/// Generates a scss module of variables from a simple token list.
+#[derive(Debug)]
+struct Tokens {
+    calls: Calls,
+}
+
+impl Tokens {
+    fn new() -> Self {
+        Tokens {
+            calls: Default::default(),
+        }
+    }
+}
+
+impl Importer for Tokens {
+    fn import(
+        &self,
+        url: &str,
+        base: Option<&str>,
+    ) -> Result<Option<Imported>, LoadError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((url.into(), base.map(String::from)));
+        match url {
+            "tokens" | "design/tokens" => Ok(Some(Imported::scss(
+                "gen:tokens.scss",
+                "$primary: red;\na { tokens: loaded }",
+            ))),
+            "reset" => Ok(Some(Imported::new(
+                "gen:reset.css",
+                SourceFormat::Css,
+                "b { margin: 0 }",
+            ))),
+            "gen:helpers" | "helpers" => Ok(Some(Imported::scss(
+                "gen:helpers.scss",
+                "@use 'tokens';\n$x: tokens.$primary;",
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[test]
+fn explicit_format() -> Result<(), Error> {
+    let mut context = MemoryContext::new().with_importer(Tokens::new());
+    context.insert("main.scss", "@import 'reset';");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"b {\n  margin: 0;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn dedup_by_canonical_url() -> Result<(), Error> {
+    let mut context = MemoryContext::new().with_importer(Tokens::new());
+    context.insert(
+        "main.scss",
+        "@use 'tokens';\n@use 'other';\nc { d: tokens.$primary other.$p }",
+    );
+    context
+        .insert("_other.scss", "@use 'design/tokens';\n$p: tokens.$primary;");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        context.transform(main)?,
+        b"a {\n  tokens: loaded;\n}\n\nc {\n  d: red red;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn relative_to_canonical_url() -> Result<(), Error> {
+    let tokens = Tokens::new();
+    let calls = tokens.calls.clone();
+    let mut context = MemoryContext::new().with_importer(tokens);
+    context.insert("main.scss", "@use 'helpers';\nc { d: helpers.$x }");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        context.transform(main)?,
+        b"a {\n  tokens: loaded;\n}\n\nc {\n  d: red;\n}\n"
+    );
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            ("main".to_string(), None),
+            ("helpers".to_string(), Some("main.scss".to_string())),
+            ("tokens".to_string(), Some("gen:helpers.scss".to_string())),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn falls_back_to_loader() -> Result<(), Error> {
+    let mut context = MemoryContext::new().with_importer(Tokens::new());
+    context.insert("main.scss", "@use 'local';\nc { d: local.$l }");
+    context.insert("_local.scss", "$l: 1;");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"c {\n  d: 1;\n}\n");
+    Ok(())
+}
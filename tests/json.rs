@@ -0,0 +1,110 @@
+//! Tests for json output of compiled css.
+#![cfg(feature = "json")]
+use rsass::input::{FsContext, SourceFile, SourceName};
+use rsass::output::{to_json, Format, Style};
+use rsass::Error;
+use serde_json::{json, Value};
+
+#[test]
+fn at_rule_with_children() -> Result<(), Error> {
+    assert_eq!(
+        compile("a { @media print { b: c; d { e: f } } }", false)?,
+        json!([{
+            "type": "at-rule",
+            "name": "media",
+            "prelude": "print",
+            "children": [
+                {
+                    "type": "rule",
+                    "selectors": ["a"],
+                    "declarations": [
+                        {"type": "declaration", "name": "b", "value": "c"},
+                    ],
+                },
+                {
+                    "type": "rule",
+                    "selectors": ["a d"],
+                    "declarations": [
+                        {"type": "declaration", "name": "e", "value": "f"},
+                    ],
+                },
+            ],
+        }]),
+    );
+    Ok(())
+}
+
+#[test]
+fn bodyless_at_rule_and_import() -> Result<(), Error> {
+    assert_eq!(
+        compile("@import \"a.css\" screen;\n@foo bar;", false)?,
+        json!([
+            {"type": "import", "url": "a.css", "args": "screen"},
+            {"type": "at-rule", "name": "foo", "prelude": "bar"},
+        ]),
+    );
+    Ok(())
+}
+
+#[test]
+fn positions() -> Result<(), Error> {
+    assert_eq!(
+        compile("/* hi */\na {\n  b: c;\n}", true)?,
+        json!([
+            {"type": "comment", "text": " hi "},
+            {
+                "type": "rule",
+                "selectors": ["a"],
+                "declarations": [{
+                    "type": "declaration",
+                    "name": "b",
+                    "value": "c",
+                    "pos": {"file": "-", "line": 3, "column": 3},
+                }],
+                "pos": {"file": "-", "line": 2, "column": 1},
+            },
+        ]),
+    );
+    Ok(())
+}
+
+#[test]
+fn comments_not_declarations() -> Result<(), Error> {
+    assert_eq!(
+        compile("a { /* x */ b: c; /* y */ }", false)?,
+        json!([{
+            "type": "rule",
+            "selectors": ["a"],
+            "declarations": [
+                {"type": "declaration", "name": "b", "value": "c"},
+            ],
+            "comments": [
+                {"type": "comment", "text": " x "},
+                {"type": "comment", "text": " y "},
+            ],
+        }]),
+    );
+    Ok(())
+}
+
+#[test]
+fn values_in_format() -> Result<(), Error> {
+    let items = FsContext::for_cwd().transform_to_ast(
+        SourceFile::scss_bytes("a { b: (1/3) }", SourceName::root("-")),
+    )?;
+    let format = Format::default()
+        .with_style(Style::Compressed)
+        .with_precision(2);
+    assert_eq!(
+        to_json(&items, format, false)[0]["declarations"][0]["value"],
+        json!(".33"),
+    );
+    Ok(())
+}
+
+fn compile(input: &str, with_pos: bool) -> Result<Value, Error> {
+    let items = FsContext::for_cwd().transform_to_ast(
+        SourceFile::scss_bytes(input, SourceName::root("-")),
+    )?;
+    Ok(to_json(&items, Format::default(), with_pos))
+}
@@ -0,0 +1,72 @@
+//! Tests for `meta.load-css` loading modules.
+use rsass::input::MemoryContext;
+use rsass::Error;
+
+#[test]
+fn nested_in_rule() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\na { @include meta.load-css('other') }",
+    );
+    context.insert("_other.scss", "b { c: d }\n@media screen { e { f: g } }");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "a b {\n  c: d;\n}\n@media screen {\n  a e {\n    f: g;\n  }\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn evaluated_once() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\n@use 'other';\
+         \na { @include meta.load-css('other') }\
+         \nx { y: other.$count }",
+    );
+    context.insert(
+        "_other.scss",
+        "@use 'counter';\ncounter.$n: counter.$n + 1;\
+         \n$count: counter.$n;\nb { c: d }",
+    );
+    context.insert("_counter.scss", "$n: 0;");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "b {\n  c: d;\n}\n\na b {\n  c: d;\n}\n\nx {\n  y: 1;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn configured() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\n@include meta.load-css('other', $with: (a: 2))",
+    );
+    context.insert("_other.scss", "$a: 1 !default;\nb { c: $a }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"b {\n  c: 2;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn configure_loaded() {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\n@use 'other';\
+         \n@include meta.load-css('other', $with: (a: 2))",
+    );
+    context.insert("_other.scss", "$a: 1 !default;");
+    let main = context.load_root("main").unwrap();
+    match context.transform(main) {
+        Err(Error::AlreadyLoaded(..)) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
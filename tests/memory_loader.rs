@@ -0,0 +1,69 @@
+//! Tests for compiling stylesheets from memory.
+use rsass::input::{Context, LoadError, MemoryContext, MemoryLoader};
+use rsass::Error;
+
+#[test]
+fn partial_and_index() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@use 'a';\n@use 'b';\nx { y: a.$v b.$v }");
+    context.insert("_a.scss", "$v: 1;");
+    context.insert("b/_index.scss", "$v: 2;");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"x {\n  y: 1 2;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn relative_to_importing_file() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@import 'lib/theme';");
+    context.insert("lib/theme.scss", "@import 'colors';\na { b: $c }");
+    context.insert("lib/_colors.scss", "$c: red;");
+    let main = context.load_root("main.scss")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: red;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn plain_css() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@import 'reset';");
+    context.insert("reset.css", "a { margin: 0 }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  margin: 0;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn update_and_remove() -> Result<(), Error> {
+    let mut loader = MemoryLoader::new();
+    loader.insert("main.scss", "@use 'v';\na { b: v.$v }");
+    assert_eq!(loader.insert("_v.scss", "$v: 1;"), None);
+    assert_eq!(loader.insert("_v.scss", "$v: 2;"), Some(b"$v: 1;".to_vec()));
+    assert_eq!(loader.get("_v.scss"), Some(&b"$v: 2;"[..]));
+
+    let mut context = Context::for_loader(loader.clone());
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: 2;\n}\n");
+
+    assert_eq!(loader.remove("_v.scss"), Some(b"$v: 2;".to_vec()));
+    assert_eq!(loader.get("_v.scss"), None);
+    let mut context = Context::for_loader(loader);
+    let main = context.load_root("main")?;
+    assert!(context.transform(main).is_err());
+    Ok(())
+}
+
+#[test]
+fn root_not_found() {
+    let mut context = MemoryContext::new();
+    context.insert("_main.scss", "a { b: c }");
+    assert!(context.load_root("main").is_ok());
+    match context.load_root("other") {
+        Err(Error::Input(LoadError::NotFound(name))) => {
+            assert_eq!(name, "other")
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
@@ -0,0 +1,64 @@
+//! Tests for configuring modules through `@use` and `@forward` chains.
+use rsass::input::MemoryContext;
+use rsass::Error;
+
+fn tokens() -> MemoryContext {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "lib/_index.scss",
+        "@forward 'tokens' with ($x: 1 !default);",
+    );
+    context.insert("lib/_tokens.scss", "$x: 0 !default;\n$y: 0 !default;");
+    context
+}
+
+#[test]
+fn forward_default_used() -> Result<(), Error> {
+    let mut context = tokens();
+    context.insert("main.scss", "@use 'lib';\na { x: lib.$x }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  x: 1;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn forward_default_overridden() -> Result<(), Error> {
+    let mut context = tokens();
+    context.insert(
+        "main.scss",
+        "@use 'lib' with ($x: 2, $y: 3);\na { x: lib.$x lib.$y }",
+    );
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  x: 2 3;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn through_prefixed_forward() -> Result<(), Error> {
+    let mut context = tokens();
+    context.insert("_theme.scss", "@forward 'lib' as theme-*;");
+    context.insert(
+        "main.scss",
+        "@use 'theme' with ($theme-y: 4);\na { y: theme.$theme-y }",
+    );
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  y: 4;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn not_default() {
+    let mut context = tokens();
+    context.insert("main.scss", "@use 'lib' with ($z: 2);");
+    let main = context.load_root("main").unwrap();
+    match context.transform(main) {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some(
+                "Error: This variable was not declared with !default \
+                 in the @used module."
+            ),
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
@@ -0,0 +1,70 @@
+//! Tests for getting the resulting module of a transformation.
+use rsass::css::Value;
+use rsass::input::MemoryContext;
+use rsass::output::Format;
+use rsass::sass::{FromSassValue, Name};
+use rsass::Error;
+
+#[test]
+fn members_and_css() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "$breakpoints: (small: 576px, large: 992px);\n\
+         @function double($x) { @return 2 * $x; }\n\
+         @mixin hidden { display: none; }\n\
+         $breakpoints: map-merge($breakpoints, (huge: 1400px));\n\
+         a { b: c }",
+    );
+    let main = context.load_root("main")?;
+    let (css, module) = context.transform_to_module(main)?;
+    assert_eq!(String::from_utf8(css).unwrap(), "a {\n  b: c;\n}\n");
+    assert_eq!(
+        module
+            .get(&Name::from("breakpoints"))?
+            .format(Format::introspect())
+            .to_string(),
+        "(small: 576px, large: 992px, huge: 1400px)",
+    );
+    assert!(module.get_function(&Name::from("double"))?.is_some());
+    assert_eq!(module.mixin_names(), vec![Name::from("hidden")]);
+    Ok(())
+}
+
+#[test]
+fn forwarded_members() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "_colors.scss",
+        "$primary: #336699;\n$secret: 1;\n@mixin themed { color: $primary; }",
+    );
+    context.insert(
+        "main.scss",
+        "@forward 'colors' as color-* hide $color-secret;\n$count: 3;",
+    );
+    let main = context.load_root("main")?;
+    let (_css, module) = context.transform_to_module(main)?;
+    assert_eq!(
+        module
+            .variables_map()
+            .format(Format::introspect())
+            .to_string(),
+        "(\"color-primary\": #336699, \"count\": 3)",
+    );
+    assert_eq!(module.mixin_names(), vec![Name::from("color-themed")]);
+    let count = i64::from_sass_value(module.get(&Name::from("count"))?);
+    assert_eq!(count, Ok(3));
+    Ok(())
+}
+
+#[test]
+fn used_modules_not_included() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("_lib.scss", "$x: 1;");
+    context.insert("main.scss", "@use 'lib';\n$y: lib.$x + 1;");
+    let main = context.load_root("main")?;
+    let (_css, module) = context.transform_to_module(main)?;
+    assert_eq!(module.get_or_none(&Name::from("x")), None);
+    assert_eq!(module.get_or_none(&Name::from("y")), Some(Value::scalar(2)));
+    Ok(())
+}
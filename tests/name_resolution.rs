@@ -0,0 +1,82 @@
+//! Tests for the name resolution rules of `Context::find_file`.
+use rsass::input::{FsContext, MemoryContext};
+use rsass::Error;
+use std::path::Path;
+
+#[test]
+fn scss_before_css() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@use 'a';");
+    context.insert("a.scss", "a { from: scss }");
+    context.insert("a.css", "a { from: css }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  from: scss;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn file_before_index() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@use 'a';");
+    context.insert("a.css", "a { from: file }");
+    context.insert("a/_index.scss", "a { from: index }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  from: file;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn partial_with_suffix() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "@use 'lib/a.scss';");
+    context.insert("lib/_a.scss", "a { b: c }");
+    let main = context.load_root("main")?;
+    assert_eq!(context.transform(main)?, b"a {\n  b: c;\n}\n");
+    Ok(())
+}
+
+#[test]
+fn ambiguous_root() {
+    let mut context = MemoryContext::new();
+    context.insert("lib/main.scss", "a { b: c }");
+    context.insert("lib/_main.scss", "a { b: c }");
+    match context.load_root("lib/main") {
+        Err(err) => assert_eq!(
+            err.to_string(),
+            "Error: It's not clear which file to import. Found:\
+             \n  lib/_main.scss\
+             \n  lib/main.scss",
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn ambiguous_use_shows_canonical_paths() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ambiguous_use");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("main.scss"), "@use 'a';").unwrap();
+    std::fs::write(dir.join("_a.scss"), "a { b: c }").unwrap();
+    std::fs::write(dir.join("a.scss"), "a { b: c }").unwrap();
+    let dir = dir.canonicalize().unwrap();
+    let (context, main) =
+        FsContext::for_path(&dir.join("main.scss")).unwrap();
+    match context.transform(main) {
+        Err(err) => assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: It's not clear which file to import. Found:\
+                 \n  {}\
+                 \n  {}\
+                 \n  ,\
+                 \n1 | @use 'a';\
+                 \n  | ^^^^^^^^\
+                 \n  '\
+                 \n  main.scss 1:1  root stylesheet",
+                dir.join("_a.scss").display(),
+                dir.join("a.scss").display(),
+            ),
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
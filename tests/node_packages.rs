@@ -0,0 +1,86 @@
+//! Tests for loading sass from node packages.
+//!
+//! The packages are in `tests/node/node_modules`, and the importing
+//! files are in `tests/node/src`.
+#![cfg(feature = "node")]
+use rsass::input::{FsContext, NodePackageImporter, SourceFile, SourceName};
+use rsass::Error;
+
+#[test]
+fn sass_field_and_relative_import() -> Result<(), Error> {
+    check("@use 'pkg:bootstrap';", ".btn {\n  color: blue;\n}\n")
+}
+
+#[test]
+fn tilde_with_subpath() -> Result<(), Error> {
+    check(
+        "@import '~bootstrap/scss/grid';",
+        ".row {\n  display: flex;\n}\n",
+    )
+}
+
+#[test]
+fn exports_root_condition() -> Result<(), Error> {
+    check(
+        "@use 'pkg:@scope/tokens';\na { b: tokens.$brand }",
+        "a {\n  b: #123456;\n}\n",
+    )
+}
+
+#[test]
+fn exports_subpath() -> Result<(), Error> {
+    check(
+        "@use 'pkg:@scope/tokens/colors';\na { b: colors.$brand }",
+        "a {\n  b: #123456;\n}\n",
+    )
+}
+
+#[test]
+fn exports_pattern() -> Result<(), Error> {
+    check(
+        "@use 'pkg:@scope/tokens/themes/dark';\na { b: dark.$bg }",
+        "a {\n  b: black;\n}\n",
+    )
+}
+
+#[test]
+fn style_field() -> Result<(), Error> {
+    check("@import 'pkg:styled';", ".styled {\n  margin: 0;\n}\n")
+}
+
+#[test]
+fn index_without_manifest() -> Result<(), Error> {
+    check(
+        "@use 'pkg:plain';\na { b: plain.$plain }",
+        "a {\n  b: 1;\n}\n",
+    )
+}
+
+#[test]
+fn missing_package() {
+    assert!(check("@use 'pkg:nonesuch';", "").is_err());
+}
+
+#[test]
+fn from_file() -> Result<(), Error> {
+    let (context, main) =
+        FsContext::for_path("tests/node/src/main.scss".as_ref())?;
+    let context =
+        context.with_importer(NodePackageImporter::new("tests/node/src"));
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        ".row {\n  display: flex;\n}\n\n.plain {\n  value: 1;\n}\n"
+    );
+    Ok(())
+}
+
+fn check(input: &str, expected: &str) -> Result<(), Error> {
+    let context = FsContext::for_cwd()
+        .with_importer(NodePackageImporter::new("tests/node/src"));
+    let input = SourceFile::scss_bytes(input, SourceName::root("main.scss"));
+    assert_eq!(
+        String::from_utf8(context.transform(input)?).unwrap(),
+        expected
+    );
+    Ok(())
+}
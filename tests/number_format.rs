@@ -0,0 +1,151 @@
+//! Tests for the numeric options of the output format.
+use rsass::compile_scss;
+use rsass::output::{Format, NumberFormat, Rounding, Style};
+
+#[test]
+fn default_expanded() {
+    check(
+        Style::Expanded,
+        NumberFormat::default(),
+        b"a { b: 0.5; c: -0.25px; d: 1.125; e: 0.00001 }",
+        "a {\n  b: 0.5;\n  c: -0.25px;\n  d: 1.13;\n  e: 0;\n}\n",
+    )
+}
+
+#[test]
+fn default_compressed() {
+    check(
+        Style::Compressed,
+        NumberFormat::default(),
+        b"a { b: 0.5; c: -0.25px; d: 1.125; e: 0.00001 }",
+        "a{b:.5;c:-.25px;d:1.13;e:0}\n",
+    )
+}
+
+#[test]
+fn leading_zero_always() {
+    check(
+        Style::Compressed,
+        NumberFormat {
+            leading_zero: Some(true),
+            ..Default::default()
+        },
+        b"a { b: 0.5; c: -0.25px; d: 1.5 }",
+        "a{b:0.5;c:-0.25px;d:1.5}\n",
+    )
+}
+
+#[test]
+fn leading_zero_never() {
+    check(
+        Style::Expanded,
+        NumberFormat {
+            leading_zero: Some(false),
+            ..Default::default()
+        },
+        b"a { b: 0.5; c: -0.25px; d: 1.5; e: 0 }",
+        "a {\n  b: .5;\n  c: -.25px;\n  d: 1.5;\n  e: 0;\n}\n",
+    )
+}
+
+#[test]
+fn leading_zero_in_color() {
+    check(
+        Style::Compressed,
+        NumberFormat {
+            leading_zero: Some(true),
+            ..Default::default()
+        },
+        b"a { b: rgba(1, 2, 3, 0.5) }",
+        "a{b:rgba(1,2,3,0.5)}\n",
+    )
+}
+
+#[test]
+fn round_half_up() {
+    check(
+        Style::Compressed,
+        NumberFormat::default(),
+        b"a { b: 0.125; c: 0.135; d: -0.125; e: 0.995; f: 1.0049 }",
+        "a{b:.13;c:.14;d:-.13;e:1;f:1}\n",
+    )
+}
+
+#[test]
+fn round_half_even() {
+    check(
+        Style::Compressed,
+        NumberFormat {
+            rounding: Rounding::HalfEven,
+            ..Default::default()
+        },
+        b"a { b: 0.125; c: 0.135; d: -0.125; e: 0.995; f: 1.0049 }",
+        "a{b:.12;c:.14;d:-.12;e:1;f:1}\n",
+    )
+}
+
+#[test]
+fn round_half_even_float() {
+    check(
+        Style::Compressed,
+        NumberFormat {
+            rounding: Rounding::HalfEven,
+            ..Default::default()
+        },
+        b"@use 'sass:math';\n\
+          a {\n  b: math.div(1, 8) * math.sqrt(1);\n  \
+          c: 0.375 * math.sqrt(1);\n}",
+        "a{b:.12;c:.38}\n",
+    )
+}
+
+#[test]
+fn scientific_small() {
+    check(
+        Style::Compressed,
+        NumberFormat {
+            sci_below: Some(-3),
+            ..Default::default()
+        },
+        b"a { b: 0.0001; c: -0.000123456px; d: 0.01; e: 0 }",
+        "a{b:1e-4;c:-1.23e-4px;d:.01;e:0}\n",
+    )
+}
+
+#[test]
+fn scientific_large() {
+    check(
+        Style::Expanded,
+        NumberFormat {
+            sci_from: Some(6),
+            ..Default::default()
+        },
+        b"a { b: 999999; c: 1000000; d: 123456789px; e: 9999999.999 }",
+        "a {\n  b: 999999;\n  c: 1e6;\n  d: 1.23e8px;\n  e: 1e7;\n}\n",
+    )
+}
+
+#[test]
+fn scientific_half_even() {
+    check(
+        Style::Expanded,
+        NumberFormat {
+            sci_from: Some(3),
+            rounding: Rounding::HalfEven,
+            ..Default::default()
+        },
+        b"a { b: 1235000; c: 1245000; d: 1245001 }",
+        "a {\n  b: 1.24e6;\n  c: 1.24e6;\n  d: 1.25e6;\n}\n",
+    )
+}
+
+fn check(style: Style, numbers: NumberFormat, input: &[u8], expected: &str) {
+    let format = Format::default()
+        .with_style(style)
+        .with_precision(2)
+        .with_numbers(numbers);
+    assert_eq!(
+        String::from_utf8(compile_scss(input, format).unwrap()).unwrap(),
+        expected
+    );
+}
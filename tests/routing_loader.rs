@@ -0,0 +1,108 @@
+//! Tests for routing urls to different loaders.
+use rsass::input::{Context, MemoryLoader, RoutingLoader};
+use rsass::Error;
+
+#[test]
+fn schemes_and_relative() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route("pkg:", files(&[("tokens/_index.scss", "$t: tok;")]))
+        .with_route("theme:", files(&[("_dark.scss", "$d: dark;")]))
+        .with_fallback(files(&[
+            (
+                "main.scss",
+                "@use 'pkg:tokens';\n@use 'theme:dark';\n@use 'local';\n\
+                 a { b: tokens.$t dark.$d local.$l }",
+            ),
+            ("_local.scss", "$l: loc;"),
+        ]));
+    check(loader, "a {\n  b: tok dark loc;\n}\n")
+}
+
+#[test]
+fn relative_stays_in_package() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route(
+            "pkg:",
+            files(&[
+                ("lib/_index.scss", "@use 'colors';\n$x: colors.$c;"),
+                ("lib/_colors.scss", "$c: pkg;"),
+            ]),
+        )
+        .with_fallback(files(&[
+            ("main.scss", "@use 'pkg:lib';\na { b: lib.$x }"),
+            ("lib/_colors.scss", "$c: local;"),
+        ]));
+    check(loader, "a {\n  b: pkg;\n}\n")
+}
+
+#[test]
+fn relative_from_scheme_root() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route(
+            "pkg:",
+            files(&[
+                ("_lib.scss", "@use 'other';\n$x: other.$o;"),
+                ("_other.scss", "$o: 17;"),
+            ]),
+        )
+        .with_fallback(files(&[(
+            "main.scss",
+            "@use 'pkg:lib';\na { b: lib.$x }",
+        )]));
+    check(loader, "a {\n  b: 17;\n}\n")
+}
+
+#[test]
+fn scheme_from_relative_file() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route("pkg:", files(&[("_t.scss", "$t: 1;")]))
+        .with_fallback(files(&[
+            ("main.scss", "@use 'sub/x';\na { b: x.$x }"),
+            ("sub/_x.scss", "@use 'pkg:t';\n$x: t.$t;"),
+        ]));
+    check(loader, "a {\n  b: 1;\n}\n")
+}
+
+#[test]
+fn fallthrough() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route("pkg:", files(&[("_a.scss", "$a: first;")]))
+        .with_route(
+            "pkg:",
+            files(&[("_a.scss", "$a: second;"), ("_b.scss", "$b: second;")]),
+        )
+        .with_fallback(files(&[(
+            "main.scss",
+            "@use 'pkg:a';\n@use 'pkg:b';\nx { y: a.$a b.$b }",
+        )]));
+    check(loader, "x {\n  y: first second;\n}\n")
+}
+
+#[test]
+fn not_found_in_any_route() -> Result<(), Error> {
+    let loader = RoutingLoader::new()
+        .with_route("pkg:", files(&[("_a.scss", "$a: 1;")]))
+        .with_fallback(files(&[("main.scss", "@use 'pkg:b';")]));
+    let mut context = Context::for_loader(loader);
+    let main = context.load_root("main")?;
+    assert!(context.transform(main).is_err());
+    Ok(())
+}
+
+fn check(loader: RoutingLoader, expected: &str) -> Result<(), Error> {
+    let mut context = Context::for_loader(loader);
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        expected
+    );
+    Ok(())
+}
+
+fn files(files: &[(&str, &str)]) -> MemoryLoader {
+    let mut loader = MemoryLoader::new();
+    for (name, content) in files {
+        loader.insert(*name, *content);
+    }
+    loader
+}
@@ -7,10 +7,11 @@ use std::sync::Arc;
 
 #[test]
 fn simple_value() -> Result<(), Error> {
-    let mut context = FsContext::for_cwd().with_format(Format {
-        style: Style::Compressed,
-        precision: 5,
-    });
+    let mut context = FsContext::for_cwd().with_format(
+        Format::default()
+            .with_style(Style::Compressed)
+            .with_precision(5),
+    );
     context
         .get_scope()
         .define(Name::from_static("color"), Rgba::from_rgb(0, 0, 0).into())?;
@@ -21,10 +22,11 @@ fn simple_value() -> Result<(), Error> {
 
 #[test]
 fn simple_function() -> Result<(), Error> {
-    let mut context = FsContext::for_cwd().with_format(Format {
-        style: Style::Compressed,
-        precision: 5,
-    });
+    let mut context = FsContext::for_cwd().with_format(
+        Format::default()
+            .with_style(Style::Compressed)
+            .with_precision(5),
+    );
     context.get_scope().define_function(
         Name::from_static("get_answer"),
         Function::builtin(
@@ -46,10 +48,11 @@ fn avg(a: Number, b: Number) -> Number {
 
 #[test]
 fn function_with_args() -> Result<(), Error> {
-    let mut context = FsContext::for_cwd().with_format(Format {
-        style: Style::Compressed,
-        precision: 5,
-    });
+    let mut context = FsContext::for_cwd().with_format(
+        Format::default()
+            .with_style(Style::Compressed)
+            .with_precision(5),
+    );
     context.get_scope().define_function(
         Name::from_static("halfway"),
         Function::builtin(
@@ -94,6 +97,74 @@ fn function_with_args() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn typed_function() -> Result<(), Error> {
+    fn halfway(a: Numeric, b: Option<Numeric>) -> Result<Numeric, Error> {
+        let b = b.unwrap_or_else(|| Numeric::scalar(0));
+        if a.unit == b.unit || b.unit.is_none() {
+            Ok(Numeric::new(avg(a.value, b.value), a.unit))
+        } else if a.unit.is_none() {
+            Ok(Numeric::new(avg(a.value, b.value), b.unit))
+        } else {
+            Err(Error::error("Incopatible args."))
+        }
+    }
+    let mut context = FsContext::for_cwd()
+        .with_format(Format::default().with_style(Style::Compressed));
+    let name = Name::from_static("halfway");
+    context.get_scope().define_function(
+        name.clone(),
+        Function::typed(&name, &["a", "b"], halfway),
+    );
+    let output = context.transform(mock_stdin(
+        "p { x: halfway(10px, 18); y: halfway($a: 4em); }",
+    ))?;
+    assert_eq!(String::from_utf8(output).unwrap(), "p{x:14px;y:2em}\n");
+    Ok(())
+}
+
+#[test]
+fn typed_function_lists() -> Result<(), Error> {
+    let mut context = FsContext::for_cwd()
+        .with_format(Format::default().with_style(Style::Compressed));
+    let name = Name::from_static("shout");
+    context.get_scope().define_function(
+        name.clone(),
+        Function::typed(&name, &["words", "loud"], |words, loud| {
+            let words: Vec<String> = words;
+            Ok(words
+                .into_iter()
+                .map(|w| if loud { w.to_uppercase() } else { w })
+                .collect::<Vec<_>>())
+        }),
+    );
+    let output = context.transform(mock_stdin(
+        "p { x: shout(foo \"bar\", true); y: length(shout(a b c, false)) }",
+    ))?;
+    assert_eq!(String::from_utf8(output).unwrap(), "p{x:FOO,BAR;y:3}\n");
+    Ok(())
+}
+
+#[test]
+fn typed_function_bad_argument() {
+    fn double(x: Numeric) -> Result<Numeric, Error> {
+        Ok(Numeric::new(x.value * 2, x.unit))
+    }
+    let mut context = FsContext::for_cwd();
+    let name = Name::from_static("double");
+    context.get_scope().define_function(
+        name.clone(),
+        Function::typed(&name, &["x"], double),
+    );
+    match context.transform(mock_stdin("p { x: double(foo); }")) {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some("Error: $x: foo is not a number."),
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
 fn mock_stdin(data: &str) -> SourceFile {
     SourceFile::scss_bytes(data.as_bytes(), SourceName::root("-"))
 }
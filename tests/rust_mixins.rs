@@ -0,0 +1,85 @@
+//! Tests for mixins implemented in rust.
+use rsass::css::{self, Property, Selectors};
+use rsass::input::MemoryContext;
+use rsass::sass::{FormalArgs, MixinDecl, MixinItem, Name};
+use rsass::Error;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+fn icon_context() -> MemoryContext {
+    let mut context = MemoryContext::new();
+    let name = Name::from_static("icon");
+    context.get_scope().define_mixin(
+        name.clone(),
+        MixinDecl::builtin(
+            "",
+            &name,
+            FormalArgs::new(vec![
+                ("name".into(), None),
+                ("size".into(), Some(rsass::sass::Value::scalar(16))),
+            ]),
+            Arc::new(|s, has_content| {
+                let name = s.get(&"name".into())?;
+                let size = s.get(&"size".into())?;
+                let url =
+                    format!("url(icons/{}.svg)", name.format(s.get_format()));
+                let mut items: Vec<MixinItem> = vec![
+                    Property::new("background".into(), url.into()).into(),
+                    Property::new("width".into(), size).into(),
+                ];
+                if has_content {
+                    let hover =
+                        Selectors::try_from(css::Value::from("&:hover"))
+                            .map_err(|e| Error::error(e.to_string()))?;
+                    items.push(MixinItem::Rule(
+                        hover,
+                        vec![MixinItem::Content],
+                    ));
+                }
+                Ok(items)
+            }),
+        ),
+    );
+    context
+}
+
+#[test]
+fn declarations() -> Result<(), Error> {
+    let mut context = icon_context();
+    context.insert("main.scss", "a { @include icon(home, $size: 2em) }");
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "a {\n  background: url(icons/home.svg);\n  width: 2em;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn nested_rule_with_content() -> Result<(), Error> {
+    let mut context = icon_context();
+    context.insert(
+        "main.scss",
+        "$c: red;\na, b { @include icon(x) { color: $c } }",
+    );
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "a, b {\n  background: url(icons/x.svg);\n  width: 16;\n}\
+         \na:hover, b:hover {\n  color: red;\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn missing_argument() {
+    let mut context = icon_context();
+    context.insert("main.scss", "a { @include icon() }");
+    match context.load_root("main").and_then(|f| context.transform(f)) {
+        Err(e) => assert_eq!(
+            e.to_string().lines().next(),
+            Some("Error: Missing argument $name."),
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
@@ -0,0 +1,163 @@
+//! Tests for the positions and comments in a parsed scss file.
+use rsass::input::{Parsed, SourceFile, SourceName};
+use rsass::sass::visit::{walk_value, Visit};
+use rsass::sass::{Item, Selector, Value};
+use rsass::SourcePos;
+
+fn parse(scss: &str) -> Vec<Item> {
+    let file = SourceFile::scss_bytes(scss, SourceName::root("x.scss"));
+    match file.parse().unwrap() {
+        Parsed::Scss(items) => items,
+        Parsed::Css(_) => panic!("Expected scss"),
+    }
+}
+
+/// The line number, line position and length of a position.
+fn place(pos: &SourcePos) -> (u32, usize, usize) {
+    (pos.line_no(), pos.line_pos(), pos.length())
+}
+
+#[test]
+fn rule_and_property() {
+    let items = parse("a.b {\n  color: red;\n}\n");
+    match &items[..] {
+        [Item::Rule(_, body, pos)] => {
+            assert_eq!(place(pos), (1, 1, 4));
+            assert_eq!(pos.line(), "a.b {");
+            match &body[..] {
+                [Item::Property(_, _, pos)] => {
+                    assert_eq!(place(pos), (2, 3, 10));
+                }
+                body => panic!("Unexpected body {:?}", body),
+            }
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+}
+
+#[test]
+fn control_directives() {
+    let items = parse(
+        "@if $a == 1 {\n  a { b: c }\n}\n\
+         @each $x in 1 2 3 { x { y: $x } }\n\
+         @while $i > 0 { $i: $i - 1; }\n",
+    );
+    let places = items
+        .iter()
+        .map(|item| item.pos().map(place))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        places,
+        vec![Some((1, 1, 12)), Some((4, 1, 18)), Some((5, 1, 14))],
+    );
+}
+
+#[test]
+fn comments_preserved() {
+    let items = parse(
+        "// A silent comment\n\
+         /* A loud comment */\n\
+         a {\n  // Inside\n  b: c; // After\n}\n",
+    );
+    match &items[..] {
+        [Item::SilentComment(silent, spos), Item::Comment(loud, lpos), Item::Rule(_, body, _)] =>
+        {
+            assert_eq!(silent, " A silent comment");
+            assert_eq!(place(spos), (1, 1, 19));
+            assert_eq!(loud.to_string(), " A loud comment ");
+            assert_eq!(place(lpos), (2, 1, 20));
+            match &body[..] {
+                [Item::SilentComment(a, apos), Item::Property(..), Item::SilentComment(b, bpos)] =>
+                {
+                    assert_eq!(
+                        (a.as_ref(), place(apos)),
+                        (" Inside", (4, 3, 9))
+                    );
+                    assert_eq!(
+                        (b.as_ref(), place(bpos)),
+                        (" After", (5, 9, 8))
+                    );
+                }
+                body => panic!("Unexpected body {:?}", body),
+            }
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+}
+
+#[test]
+fn silent_comments_not_in_output() {
+    let mut context = rsass::input::MemoryContext::new();
+    context.insert("main.scss", "// x\na {\n  // y\n  b: c; // z\n}\n// end");
+    let main = context.load_root("main").unwrap();
+    assert_eq!(
+        String::from_utf8(context.transform(main).unwrap()).unwrap(),
+        "a {\n  b: c;\n}\n"
+    );
+}
+
+#[test]
+fn elseif_position() {
+    let items = parse("@if $a { b { c: d } }\n@elseif $e { f { g: h } }\n");
+    match &items[..] {
+        [Item::IfStatement(_, _, else_body, pos)] => {
+            assert_eq!(place(pos), (1, 1, 7));
+            match &else_body[..] {
+                [Item::IfStatement(_, _, _, pos)] => {
+                    assert_eq!(place(pos), (2, 1, 11));
+                    assert_eq!(pos.line(), "@elseif $e { f { g: h } }");
+                }
+                body => panic!("Unexpected else {:?}", body),
+            }
+        }
+        items => panic!("Unexpected items {:?}", items),
+    }
+}
+
+#[test]
+fn value_positions() {
+    struct Values(Vec<(String, usize, usize)>);
+    impl Visit for Values {
+        fn visit_value(&mut self, value: &Value) {
+            let pos = value.pos();
+            let text = &pos.line()[pos.line_pos() - 1..][..pos.length()];
+            self.0.push((text.into(), pos.line_pos(), pos.length()));
+            walk_value(self, value)
+        }
+    }
+    let items = parse("a { b: 1 + $x, f(2) (c: d) [e] !important; }\n");
+    let mut values = Values(vec![]);
+    values.visit_items(&items);
+    assert_eq!(
+        values.0,
+        vec![
+            ("1 + $x, f(2) (c: d) [e] !important".into(), 8, 34),
+            ("1 + $x".into(), 8, 6),
+            ("1".into(), 8, 1),
+            ("$x".into(), 12, 2),
+            ("f(2) (c: d) [e] !important".into(), 16, 26),
+            ("f(2)".into(), 16, 4),
+            ("2".into(), 18, 1),
+            ("(c: d)".into(), 21, 6),
+            ("c".into(), 22, 1),
+            ("d".into(), 25, 1),
+            ("[e]".into(), 28, 3),
+            ("e".into(), 29, 1),
+            ("!important".into(), 32, 10),
+        ],
+    );
+}
+
+#[test]
+fn selector_positions() {
+    struct Selectors(Vec<Option<(u32, usize, usize)>>);
+    impl Visit for Selectors {
+        fn visit_selector(&mut self, selector: &Selector) {
+            self.0.push(selector.pos().map(place));
+        }
+    }
+    let items = parse("a.b, c > d {\n  e: f;\n}\n");
+    let mut selectors = Selectors(vec![]);
+    selectors.visit_items(&items);
+    assert_eq!(selectors.0, vec![Some((1, 1, 3)), Some((1, 6, 6))]);
+}
@@ -0,0 +1,121 @@
+//! Tests for converting sass values with serde.
+#![cfg(feature = "serde")]
+use rsass::css::{self, Value};
+use rsass::input::MemoryContext;
+use rsass::output::Format;
+use rsass::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Tokens {
+    gap: String,
+    columns: u32,
+    dark: bool,
+    brand: BTreeMap<String, String>,
+    fonts: Vec<&'static str>,
+}
+
+#[test]
+fn define_struct() -> Result<(), Error> {
+    let mut brand = BTreeMap::new();
+    brand.insert("primary".into(), "#336699".into());
+    brand.insert("accent".into(), "orange".into());
+    let tokens = Tokens {
+        gap: "4px".into(),
+        columns: 12,
+        dark: false,
+        brand,
+        fonts: vec!["Helvetica", "sans-serif"],
+    };
+    let mut context = MemoryContext::new();
+    context.get_scope().define_serialized(&tokens)?;
+    context.insert(
+        "main.scss",
+        "@use 'sass:map';\n\
+         a {\n  margin: $gap * 2;\n  width: percentage(1 / $columns);\n  \
+         color: if($dark, white, darken(map.get($brand, primary), 10%));\n  \
+         font-family: $fonts;\n  border-color: map.get($brand, accent);\n}",
+    );
+    let main = context.load_root("main")?;
+    assert_eq!(
+        String::from_utf8(context.transform(main)?).unwrap(),
+        "a {\n  margin: 8px;\n  width: 8.3333333333%;\n  color: #264d73;\
+         \n  font-family: Helvetica, sans-serif;\n  border-color: orange;\n}\n"
+    );
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Vars {
+    gap: String,
+    font_size: f64,
+    count: i64,
+    flag: bool,
+    nothing: Option<String>,
+    sizes: BTreeMap<String, String>,
+    list: Vec<i64>,
+}
+
+#[test]
+fn deserialize_variables() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    let scope = context.get_scope();
+    context.insert(
+        "main.scss",
+        "$gap: 2px * 2;\n$font-size: 1.5;\n$count: 3 + 4;\n\
+         $flag: 1 < 2;\n$nothing: null;\n$sizes: (small: 1em, large: 2em);\n\
+         $list: 1 2 3;",
+    );
+    let main = context.load_root("main")?;
+    context.transform(main)?;
+    let result: Vars = scope.deserialize_variables()?;
+    let mut sizes = BTreeMap::new();
+    sizes.insert("small".into(), "1em".into());
+    sizes.insert("large".into(), "2em".into());
+    assert_eq!(
+        result,
+        Vars {
+            gap: "4px".into(),
+            font_size: 1.5,
+            count: 7,
+            flag: true,
+            nothing: None,
+            sizes,
+            list: vec![1, 2, 3],
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn json_round_trip() {
+    let json = json!({
+        "size": "12px",
+        "scale": [1, 1.5, 2],
+        "color": "#fff",
+        "name": "Tokens",
+        "on": true,
+        "off": null,
+    });
+    let value: Value = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        value.format(Format::introspect()).to_string(),
+        "(color: #fff, name: Tokens, off: null, on: true, scale: (1, 1.5, 2), size: 12px)",
+    );
+    assert_eq!(serde_json::to_value(&value).unwrap(), json);
+}
+
+#[test]
+fn not_a_struct() {
+    let mut context = MemoryContext::new();
+    match context.get_scope().define_serialized(&[1, 2]) {
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Error: Expected a struct or map of variables, got list."
+        ),
+        Ok(()) => panic!("Expected an error"),
+    }
+    assert_eq!(css::to_value(&[1, 2]).unwrap().type_name(), "list");
+}
@@ -344,7 +344,6 @@ mod with {
             }
 
             #[test]
-            #[ignore] // missing error
             fn both_configured() {
                 let runner = runner().with_cwd("both_configured");
                 assert_eq!(
@@ -389,7 +388,6 @@ mod with {
     );
             }
             #[test]
-            #[ignore] // missing error
             fn unconfigured_first() {
                 let runner = runner().with_cwd("unconfigured_first");
                 assert_eq!(
@@ -416,7 +414,6 @@ mod with {
             }
 
             #[test]
-            #[ignore] // missing error
             fn both_configured() {
                 let runner = runner().with_cwd("both_configured");
                 assert_eq!(
@@ -436,7 +433,6 @@ mod with {
     );
             }
             #[test]
-            #[ignore] // missing error
             fn load_first() {
                 let runner = runner().with_cwd("load_first");
                 assert_eq!(
@@ -484,7 +480,6 @@ mod with {
     );
             }
             #[test]
-            #[ignore] // missing error
             fn unconfigured_first() {
                 let runner = runner().with_cwd("unconfigured_first");
                 assert_eq!(
@@ -506,7 +501,6 @@ mod with {
         }
     }
     #[test]
-    #[ignore] // missing error
     fn namespace() {
         let runner = runner().with_cwd("namespace");
         assert_eq!(
@@ -523,7 +517,6 @@ mod with {
         );
     }
     #[test]
-    #[ignore] // missing error
     fn nested() {
         let runner = runner().with_cwd("nested");
         assert_eq!(
@@ -540,7 +533,6 @@ mod with {
         );
     }
     #[test]
-    #[ignore] // missing error
     fn not_default() {
         let runner = runner().with_cwd("not_default");
         assert_eq!(
@@ -557,7 +549,6 @@ mod with {
         );
     }
     #[test]
-    #[ignore] // missing error
     fn repeated_variable() {
         let runner = runner().with_cwd("repeated_variable");
         assert_eq!(
@@ -580,7 +571,6 @@ mod with {
         }
 
         #[test]
-        #[ignore] // missing error
         fn test_as() {
             let runner = runner().with_cwd("as");
             assert_eq!(
@@ -597,7 +587,6 @@ mod with {
     );
         }
         #[test]
-        #[ignore] // missing error
         fn hide() {
             let runner = runner().with_cwd("hide");
             assert_eq!(
@@ -614,7 +603,6 @@ mod with {
     );
         }
         #[test]
-        #[ignore] // missing error
         fn show() {
             let runner = runner().with_cwd("show");
             assert_eq!(
@@ -631,7 +619,6 @@ mod with {
     );
         }
         #[test]
-        #[ignore] // missing error
         fn with() {
             let runner = runner().with_cwd("with");
             assert_eq!(
@@ -649,7 +636,6 @@ mod with {
         }
     }
     #[test]
-    #[ignore] // missing error
     fn undefined() {
         let runner = runner().with_cwd("undefined");
         assert_eq!(
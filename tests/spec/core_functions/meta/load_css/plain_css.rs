@@ -91,7 +91,6 @@ mod nested {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn parent_selector() {
         let runner = runner().with_cwd("parent_selector");
         assert_eq!(
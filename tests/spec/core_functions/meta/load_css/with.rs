@@ -105,7 +105,6 @@ mod multi_load {
     }
 
     #[test]
-    #[ignore] // wrong result
     fn empty() {
         let runner = runner().with_cwd("empty");
         assert_eq!(
@@ -121,7 +120,6 @@ mod multi_load {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn forward() {
         let runner = runner().with_cwd("forward");
         assert_eq!(
@@ -138,7 +136,6 @@ mod multi_load {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn test_use() {
         let runner = runner().with_cwd("use");
         assert_eq!(
@@ -215,7 +212,6 @@ mod through_forward {
     }
 
     #[test]
-    #[ignore] // wrong result
     fn test_as() {
         let runner = runner().with_cwd("as");
         assert_eq!(
@@ -229,7 +225,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn bare() {
         let runner = runner().with_cwd("bare");
         assert_eq!(
@@ -243,7 +238,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn hide() {
         let runner = runner().with_cwd("hide");
         assert_eq!(
@@ -257,7 +251,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn show() {
         let runner = runner().with_cwd("show");
         assert_eq!(
@@ -271,7 +264,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn transitive() {
         let runner = runner().with_cwd("transitive");
         assert_eq!(
@@ -304,7 +296,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn null() {
             let runner = runner().with_cwd("null");
             assert_eq!(
@@ -316,7 +307,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unconfigured() {
             let runner = runner().with_cwd("unconfigured");
             assert_eq!(
@@ -366,7 +356,6 @@ mod through_import {
     }
 }
 #[test]
-#[ignore] // wrong result
 fn variable_exists() {
     let runner = runner().with_cwd("variable_exists");
     assert_eq!(
@@ -47,7 +47,6 @@ mod keyframes {
     }
 }
 #[test]
-#[ignore] // wrong result
 fn load_css() {
     let runner = runner().with_cwd("load_css");
     assert_eq!(
@@ -135,7 +135,6 @@ mod multi_configuration {
     }
 
     #[test]
-    #[ignore] // missing error
     fn multi_file() {
         let runner = runner().with_cwd("multi_file");
         assert_eq!(
@@ -157,7 +156,6 @@ mod multi_configuration {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn one_file() {
         let runner = runner().with_cwd("one_file");
         assert_eq!(
@@ -200,7 +198,6 @@ mod multi_configuration {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn unconfigured_first() {
         let runner = runner().with_cwd("unconfigured_first");
         assert_eq!(
@@ -220,7 +217,6 @@ mod multi_configuration {
     }
 }
 #[test]
-#[ignore] // missing error
 fn namespace() {
     let runner = runner().with_cwd("namespace");
     assert_eq!(
@@ -237,7 +233,6 @@ fn namespace() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn nested() {
     let runner = runner().with_cwd("nested");
     assert_eq!(
@@ -254,7 +249,6 @@ fn nested() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn not_default() {
     let runner = runner().with_cwd("not_default");
     assert_eq!(
@@ -271,7 +265,6 @@ fn not_default() {
     );
 }
 #[test]
-#[ignore] // wrong error
 fn repeated_variable() {
     let runner = runner().with_cwd("repeated_variable");
     assert_eq!(
@@ -292,7 +285,6 @@ mod through_forward {
     }
 
     #[test]
-    #[ignore] // missing error
     fn test_as() {
         let runner = runner().with_cwd("as");
         assert_eq!(
@@ -308,7 +300,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn hide() {
         let runner = runner().with_cwd("hide");
         assert_eq!(
@@ -324,7 +315,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn show() {
         let runner = runner().with_cwd("show");
         assert_eq!(
@@ -340,7 +330,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn with() {
         let runner = runner().with_cwd("with");
         assert_eq!(
@@ -357,7 +346,6 @@ mod through_forward {
     }
 }
 #[test]
-#[ignore] // missing error
 fn undefined() {
     let runner = runner().with_cwd("undefined");
     assert_eq!(
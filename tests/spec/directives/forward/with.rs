@@ -307,7 +307,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn test_as() {
         let runner = runner().with_cwd("as");
         assert_eq!(
@@ -318,7 +317,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn bare() {
         let runner = runner().with_cwd("bare");
         assert_eq!(
@@ -329,7 +327,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn hide() {
         let runner = runner().with_cwd("hide");
         assert_eq!(
@@ -340,7 +337,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn show() {
         let runner = runner().with_cwd("show");
         assert_eq!(
@@ -367,7 +363,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn null() {
             let runner = runner().with_cwd("null");
             assert_eq!(
@@ -378,7 +373,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unconfigured() {
             let runner = runner().with_cwd("unconfigured");
             assert_eq!(
@@ -470,7 +464,6 @@ fn used_in_input() {
     );
 }
 #[test]
-#[ignore] // wrong result
 fn variable_exists() {
     let runner = runner().with_cwd("variable_exists");
     assert_eq!(
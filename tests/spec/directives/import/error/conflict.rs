@@ -33,7 +33,6 @@ fn runner() -> crate::TestRunner {
 }
 
 #[test]
-#[ignore] // missing error
 fn all() {
     let runner = runner().with_cwd("all");
     assert_eq!(
@@ -55,7 +54,6 @@ fn all() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn extension() {
     let runner = runner().with_cwd("extension");
     assert_eq!(
@@ -81,7 +79,6 @@ mod import_only {
     }
 
     #[test]
-    #[ignore] // missing error
     fn no_extension() {
         let runner = runner().with_cwd("no_extension");
         assert_eq!(
@@ -101,7 +98,6 @@ mod import_only {
     );
     }
     #[test]
-    #[ignore] // wrong error
     fn with_extension() {
         let runner = runner().with_cwd("with_extension");
         assert_eq!(
@@ -122,7 +118,6 @@ mod import_only {
     }
 }
 #[test]
-#[ignore] // missing error
 fn index() {
     let runner = runner().with_cwd("index");
     assert_eq!(
@@ -142,7 +137,6 @@ fn index() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn partial() {
     let runner = runner().with_cwd("partial");
     assert_eq!(
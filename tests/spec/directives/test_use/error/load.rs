@@ -64,7 +64,6 @@ mod conflict {
     }
 
     #[test]
-    #[ignore] // missing error
     fn all() {
         let runner = runner().with_cwd("all");
         assert_eq!(
@@ -92,7 +91,6 @@ mod conflict {
         }
 
         #[test]
-        #[ignore] // missing error
         fn sass_and_scss() {
             let runner = runner().with_cwd("sass_and_scss");
             assert_eq!(
@@ -113,7 +111,6 @@ mod conflict {
         }
     }
     #[test]
-    #[ignore] // missing error
     fn index() {
         let runner = runner().with_cwd("index");
         assert_eq!(
@@ -133,7 +130,6 @@ mod conflict {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn partial() {
         let runner = runner().with_cwd("partial");
         assert_eq!(
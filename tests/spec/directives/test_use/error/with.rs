@@ -126,7 +126,6 @@ mod multi_configuration {
     }
 
     #[test]
-    #[ignore] // missing error
     fn multi_file() {
         let runner = runner().with_cwd("multi_file");
         assert_eq!(
@@ -148,7 +147,6 @@ mod multi_configuration {
     );
     }
     #[test]
-    #[ignore] // wrong error
     fn one_file() {
         let runner = runner().with_cwd("one_file");
         assert_eq!(
@@ -191,7 +189,6 @@ mod multi_configuration {
     );
     }
     #[test]
-    #[ignore] // wrong error
     fn unconfigured_first() {
         let runner = runner().with_cwd("unconfigured_first");
         assert_eq!(
@@ -211,7 +208,6 @@ mod multi_configuration {
     }
 }
 #[test]
-#[ignore] // missing error
 fn namespace() {
     let runner = runner().with_cwd("namespace");
     assert_eq!(
@@ -227,7 +223,6 @@ fn namespace() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn nested() {
     let runner = runner().with_cwd("nested");
     assert_eq!(
@@ -243,7 +238,6 @@ fn nested() {
     );
 }
 #[test]
-#[ignore] // missing error
 fn not_default() {
     let runner = runner().with_cwd("not_default");
     assert_eq!(
@@ -259,7 +253,6 @@ fn not_default() {
     );
 }
 #[test]
-#[ignore] // wrong error
 fn repeated_variable() {
     let runner = runner().with_cwd("repeated_variable");
     assert_eq!(
@@ -279,7 +272,6 @@ mod through_forward {
     }
 
     #[test]
-    #[ignore] // missing error
     fn test_as() {
         let runner = runner().with_cwd("as");
         assert_eq!(
@@ -295,7 +287,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn hide() {
         let runner = runner().with_cwd("hide");
         assert_eq!(
@@ -311,7 +302,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn show() {
         let runner = runner().with_cwd("show");
         assert_eq!(
@@ -327,7 +317,6 @@ mod through_forward {
     );
     }
     #[test]
-    #[ignore] // missing error
     fn with() {
         let runner = runner().with_cwd("with");
         assert_eq!(
@@ -344,7 +333,6 @@ mod through_forward {
     }
 }
 #[test]
-#[ignore] // missing error
 fn undefined() {
     let runner = runner().with_cwd("undefined");
     assert_eq!(
@@ -244,7 +244,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn test_as() {
         let runner = runner().with_cwd("as");
         assert_eq!(
@@ -255,7 +254,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn bare() {
         let runner = runner().with_cwd("bare");
         assert_eq!(
@@ -266,7 +264,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn hide() {
         let runner = runner().with_cwd("hide");
         assert_eq!(
@@ -277,7 +274,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn show() {
         let runner = runner().with_cwd("show");
         assert_eq!(
@@ -288,7 +284,6 @@ mod through_forward {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn transitive() {
         let runner = runner().with_cwd("transitive");
         assert_eq!(
@@ -366,7 +361,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn null() {
             let runner = runner().with_cwd("null");
             assert_eq!(
@@ -377,7 +371,6 @@ mod through_forward {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unconfigured() {
             let runner = runner().with_cwd("unconfigured");
             assert_eq!(
@@ -452,7 +445,6 @@ fn used_in_input() {
     );
 }
 #[test]
-#[ignore] // wrong result
 fn variable_exists() {
     let runner = runner().with_cwd("variable_exists");
     assert_eq!(
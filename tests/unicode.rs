@@ -15,10 +15,9 @@ fn unicode_in_expanded() {
 
 #[test]
 fn unicode_in_compressed() {
-    let format = output::Format {
-        style: output::Style::Compressed,
-        precision: 5,
-    };
+    let format = output::Format::default()
+        .with_style(output::Style::Compressed)
+        .with_precision(5);
     // Blåbärsöl is a proper swedish word.  Translates to blueberry beer.
     // The charset declaration is replaced with a byte order mark.
     check(
@@ -0,0 +1,139 @@
+//! Tests for visiting the sass and css trees.
+use rsass::css::{self, BodyItem};
+use rsass::input::{MemoryContext, Parsed, SourceFile, SourceName};
+use rsass::output::{write_css, Format};
+use rsass::sass::visit::{
+    walk_item_mut, walk_value, walk_value_mut, Visit, VisitMut,
+};
+use rsass::sass::{Item, Name, Value};
+use rsass::Error;
+
+fn parse(scss: &str) -> Vec<Item> {
+    let file = SourceFile::scss_bytes(scss, SourceName::root("x.scss"));
+    match file.parse().unwrap() {
+        Parsed::Scss(items) => items,
+        Parsed::Css(_) => panic!("Expected scss"),
+    }
+}
+
+/// Collect the names of all variables used.
+#[derive(Default)]
+struct Used(Vec<String>);
+
+impl Visit for Used {
+    fn visit_value(&mut self, value: &Value) {
+        if let Value::Variable(name, _) = value {
+            self.0.push(name.to_string());
+        }
+        walk_value(self, value)
+    }
+}
+
+#[test]
+fn find_variables() {
+    let items = parse(
+        "$a: 1;\n\
+         @function f($x, $y: $a) { @return $x + $y; }\n\
+         p#{$b} {\n  w: f($c, $y: $d);\n  @include m(($e: $f)) { @debug $g; }\n\
+           @each $i in $h { q: $i }\n}\n",
+    );
+    let mut used = Used::default();
+    used.visit_items(&items);
+    assert_eq!(
+        used.0,
+        ["a", "x", "y", "b", "c", "d", "e", "f", "g", "h", "i"]
+    );
+}
+
+/// Rename a variable, both in uses and in declarations.
+struct Rename(Name, Name);
+
+impl VisitMut for Rename {
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        if let Item::VariableDeclaration { name, .. } = item {
+            if *name == self.0 {
+                *name = self.1.clone();
+            }
+        }
+        walk_item_mut(self, item)
+    }
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        if let Value::Variable(name, _) = value {
+            if *name == self.0 {
+                *name = self.1.clone();
+            }
+        }
+        walk_value_mut(self, value)
+    }
+}
+
+#[test]
+fn rename_variable() {
+    let mut items =
+        parse("$old: 1;\na { b: $old * 2; @if $old { c: $other } }");
+    Rename(Name::from("old"), Name::from("new")).visit_items_mut(&mut items);
+    let mut used = Used::default();
+    used.visit_items(&items);
+    assert_eq!(used.0, ["new", "new", "other"]);
+    match &items[0] {
+        Item::VariableDeclaration { name, .. } => {
+            assert_eq!(name, &Name::from("new"))
+        }
+        item => panic!("Unexpected item {:?}", item),
+    }
+}
+
+/// Collect all property names in a css tree.
+#[derive(Default)]
+struct Properties(Vec<String>);
+
+impl css::visit::Visit for Properties {
+    fn visit_property(&mut self, property: &css::Property) {
+        self.0.push(property.name().into());
+    }
+}
+
+#[test]
+fn css_properties() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "a { b: c; @media screen { d: e; f { g: h } } }\n\
+         @supports (x: y) { @media print { i { j: k } } }",
+    );
+    let main = context.load_root("main")?;
+    let css = context.transform_to_ast(main)?;
+    let mut properties = Properties::default();
+    css::visit::Visit::visit_items(&mut properties, &css);
+    assert_eq!(properties.0, ["b", "d", "g", "j"]);
+    Ok(())
+}
+
+/// Remove all comments from rules.
+struct NoComments;
+
+impl css::visit::VisitMut for NoComments {
+    fn visit_rule_mut(&mut self, rule: &mut css::Rule) {
+        let mut result = css::Rule::new(rule.selectors().clone());
+        for item in rule.body() {
+            if !matches!(item, BodyItem::Comment(_)) {
+                result.push(item.clone());
+            }
+        }
+        *rule = result;
+    }
+}
+
+#[test]
+fn css_rewrite() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert("main.scss", "a { /* x */ b: c; /* y */ }");
+    let main = context.load_root("main")?;
+    let mut css = context.transform_to_ast(main)?;
+    css::visit::VisitMut::visit_items_mut(&mut NoComments, &mut css);
+    assert_eq!(
+        String::from_utf8(write_css(&css, Format::default())).unwrap(),
+        "a {\n  b: c;\n}\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! Tests for writing css output directly to an `io::Write`.
+use rsass::input::{FsContext, MemoryContext, SourceFile, SourceName};
+use rsass::output::{Charset, Format, Style};
+use rsass::{compile_scss, Error};
+use std::io::{self, Write};
+
+#[test]
+fn hoisted_import() -> Result<(), Error> {
+    check_same(
+        "a { b: c }\n@import url(foo.css);\nd { e: f }",
+        "@import url(foo.css);\na {\n  b: c;\n}\n\nd {\n  e: f;\n}\n",
+        Style::Expanded,
+    )
+}
+
+#[test]
+fn charset_from_late_content() -> Result<(), Error> {
+    check_same(
+        "a { b: c }\nd { e: \"\u{e9}\" }",
+        "@charset \"UTF-8\";\na {\n  b: c;\n}\n\nd {\n  e: \"\u{e9}\";\n}\n",
+        Style::Expanded,
+    )
+}
+
+#[test]
+fn bom_in_compressed() -> Result<(), Error> {
+    check_same(
+        "a { b: c }\nd { e: \"\u{e9}\" }",
+        "\u{feff}a{b:c}d{e:\"\u{e9}\"}\n",
+        Style::Compressed,
+    )
+}
+
+#[test]
+fn trailing_semicolon_compressed() -> Result<(), Error> {
+    check_same(
+        "@import url(foo.css);\n@import url(bar.css);",
+        "@import url(foo.css);@import url(bar.css)\n",
+        Style::Compressed,
+    )
+}
+
+#[test]
+fn empty_output() -> Result<(), Error> {
+    check_same("$a: b;\n// comment", "", Style::Expanded)
+}
+
+#[test]
+fn written_item_by_item() -> Result<(), Error> {
+    let mut out = CountingWriter::default();
+    context(Style::Expanded).transform_to_writer(
+        input("a { b: \"\u{e9}\" }\nd { e: f }\ng { h: i }"),
+        &mut out,
+    )?;
+    assert_eq!(
+        String::from_utf8(out.data).unwrap(),
+        "@charset \"UTF-8\";\na {\n  b: \"\u{e9}\";\n}\n\nd {\n  e: f;\n}\n\ng {\n  h: i;\n}\n",
+    );
+    assert!(
+        out.writes > 3,
+        "Expected several writes, got {}",
+        out.writes
+    );
+    Ok(())
+}
+
+#[test]
+fn written_before_end() {
+    let mut out = Vec::new();
+    let result = context(Style::Expanded).transform_to_writer(
+        input("a { b: \"\u{e9}\" }\nc { d: e }\n@error \"stop\";"),
+        &mut out,
+    );
+    assert!(result.is_err(), "Expected an error, got {:?}", result);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "@charset \"UTF-8\";\na {\n  b: \"\u{e9}\";\n}\n\nc {\n  d: e;\n}",
+    );
+}
+
+#[test]
+fn held_back_while_ascii() {
+    let mut out = Vec::new();
+    let result = context(Style::Expanded)
+        .transform_to_writer(input("a { b: c }\n@error \"stop\";"), &mut out);
+    assert!(result.is_err(), "Expected an error, got {:?}", result);
+    assert_eq!(out, b"");
+}
+
+#[test]
+fn late_import_hoisted_by_transform() -> Result<(), Error> {
+    let css = compile_scss(
+        b"a { b: \"\xc3\xa9\" }\n@import url(foo.css);",
+        Format::default(),
+    )?;
+    assert_eq!(
+        String::from_utf8(css).unwrap(),
+        "@charset \"UTF-8\";\n@import url(foo.css);\na {\n  b: \"\u{e9}\";\n}\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn late_import_when_written() {
+    let mut out = Vec::new();
+    match context(Style::Expanded).transform_to_writer(
+        input("a { b: \"\u{e9}\" }\n@import url(foo.css);"),
+        &mut out,
+    ) {
+        Err(e) => assert_eq!(
+            e.to_string(),
+            "Error: This @import can't be moved to the start of the output, \
+             since other css is already written.\
+             \n  ,\
+             \n2 | @import url(foo.css);\
+             \n  |         ^^^^^^^^^^^^\
+             \n  '\
+             \n  - 2:9  root stylesheet",
+        ),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn charset_always() -> Result<(), Error> {
+    check_same_in(
+        "a { b: c }",
+        "@charset \"UTF-8\";\na {\n  b: c;\n}\n",
+        Format::default().with_charset(Charset::Always),
+    )
+}
+
+#[test]
+fn charset_never() -> Result<(), Error> {
+    check_same_in(
+        "a { b: \"\u{e9}\" }",
+        "a{b:\"\u{e9}\"}\n",
+        Format::default()
+            .with_style(Style::Compressed)
+            .with_charset(Charset::Never),
+    )
+}
+
+#[test]
+fn not_held_back_with_known_charset() {
+    let mut out = Vec::new();
+    let format = Format::default().with_charset(Charset::Never);
+    let result = FsContext::for_cwd()
+        .with_format(format)
+        .transform_to_writer(input("a { b: c }\n@error \"stop\";"), &mut out);
+    assert!(result.is_err(), "Expected an error, got {:?}", result);
+    assert_eq!(String::from_utf8(out).unwrap(), "a {\n  b: c;\n}");
+}
+
+#[test]
+fn load_css_of_written_module() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\n@use 'other';\nx { y: \"\u{e9}\" }\
+         \na { @include meta.load-css('other') }",
+    );
+    context.insert("_other.scss", "b { c: d }");
+    let main = context.load_root("main")?;
+    let mut out = Vec::new();
+    context.transform_to_writer(main, &mut out)?;
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "@charset \"UTF-8\";\nb {\n  c: d;\n}\n\nx {\n  y: \"\u{e9}\";\n}\n\
+         \na b {\n  c: d;\n}\n",
+    );
+    Ok(())
+}
+
+#[test]
+fn load_css_of_configured_module() -> Result<(), Error> {
+    let mut context = MemoryContext::new();
+    context.insert(
+        "main.scss",
+        "@use 'sass:meta';\n@use 'other' with ($c: e);\nx { y: \"\u{e9}\" }\
+         \na { @include meta.load-css('other') }",
+    );
+    context
+        .insert("_other.scss", "@use 'inner';\n$c: d !default;\nb { c: $c }");
+    context.insert("_inner.scss", "f { g: h }");
+    let main = context.load_root("main")?;
+    let mut out = Vec::new();
+    context.transform_to_writer(main, &mut out)?;
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "@charset \"UTF-8\";\nf {\n  g: h;\n}\n\nb {\n  c: e;\n}\n\
+         \nx {\n  y: \"\u{e9}\";\n}\n\na f {\n  g: h;\n}\na b {\n  c: e;\n}\n",
+    );
+    Ok(())
+}
+
+/// Check that the result is the expected, and the same as from `transform`.
+fn check_same(scss: &str, expected: &str, style: Style) -> Result<(), Error> {
+    let format = Format::default().with_style(style).with_precision(5);
+    check_same_in(scss, expected, format)
+}
+
+fn check_same_in(
+    scss: &str,
+    expected: &str,
+    format: Format,
+) -> Result<(), Error> {
+    let context = || FsContext::for_cwd().with_format(format);
+    let mut out = Vec::new();
+    context().transform_to_writer(input(scss), &mut out)?;
+    assert_eq!(String::from_utf8(out.clone()).unwrap(), expected);
+    assert_eq!(out, context().transform(input(scss))?);
+    Ok(())
+}
+
+fn context(style: Style) -> FsContext {
+    FsContext::for_cwd()
+        .with_format(Format::default().with_style(style).with_precision(5))
+}
+
+fn input(scss: &str) -> SourceFile {
+    SourceFile::scss_bytes(scss, SourceName::root("-"))
+}
+
+#[derive(Default)]
+struct CountingWriter {
+    data: Vec<u8>,
+    writes: usize,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}